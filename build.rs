@@ -12,6 +12,7 @@ fn main() -> std::io::Result<()> {
     //         "src/protos/table_store_filter.proto",
     //         "src/protos/table_store_search.proto",
     //         "src/protos/timeseries.proto",
+    //         "src/protos/tunnel.proto",
     //     ],
     //     &["src/protos/"],
     // )?;
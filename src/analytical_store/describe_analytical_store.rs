@@ -3,10 +3,90 @@ use prost::Message;
 use crate::{
     add_per_request_options,
     error::OtsError,
+    protos::timeseries::AnalyticalStoreSyncType,
     timeseries_model::rules::{validate_analytical_store_name, validate_timeseries_table_name},
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
 
+/// 分析存储的同步状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalyticalStoreSyncStatus {
+    /// 当前所处的构建阶段。全量构建完成之后进入增量构建阶段
+    pub sync_phase: Option<AnalyticalStoreSyncType>,
+
+    /// 最新同步时间，单位为秒
+    pub current_sync_timestamp: Option<i64>,
+}
+
+/// 分析存储占用的存储大小
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalyticalStoreStorageSize {
+    /// 存储大小，单位为字节
+    pub size: Option<i64>,
+
+    /// 统计时间，单位为秒
+    pub timestamp: Option<i64>,
+}
+
+/// 查询时序分析存储描述信息的响应
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DescribeTimeseriesAnalyticalStoreResponse {
+    /// 分析存储名称
+    pub store_name: Option<String>,
+
+    /// 分析存储数据保留时间，单位为秒。`-1` 表示永不过期
+    pub ttl_seconds: Option<i32>,
+
+    /// 创建分析存储时设置的同步方式
+    pub sync_option: Option<AnalyticalStoreSyncType>,
+
+    /// 同步状态
+    pub sync_status: Option<AnalyticalStoreSyncStatus>,
+
+    /// 存储大小
+    pub storage_size: Option<AnalyticalStoreStorageSize>,
+}
+
+impl DescribeTimeseriesAnalyticalStoreResponse {
+    /// 全量构建是否已经完成。全量构建完成之后，才可以通过 SQL 查询到全部的数据
+    pub fn is_full_sync_finished(&self) -> bool {
+        self.sync_status.is_some_and(|s| s.sync_phase == Some(AnalyticalStoreSyncType::SyncTypeIncr))
+    }
+}
+
+impl From<crate::protos::timeseries::DescribeTimeseriesAnalyticalStoreResponse> for DescribeTimeseriesAnalyticalStoreResponse {
+    fn from(value: crate::protos::timeseries::DescribeTimeseriesAnalyticalStoreResponse) -> Self {
+        let crate::protos::timeseries::DescribeTimeseriesAnalyticalStoreResponse {
+            analytical_store,
+            sync_stat,
+            storage_size,
+        } = value;
+
+        let (store_name, ttl_seconds, sync_option) = match analytical_store {
+            Some(store) => (
+                store.store_name,
+                store.time_to_live,
+                store.sync_option.and_then(|n| AnalyticalStoreSyncType::try_from(n).ok()),
+            ),
+            None => (None, None, None),
+        };
+
+        Self {
+            store_name,
+            ttl_seconds,
+            sync_option,
+            sync_status: sync_stat.map(|s| AnalyticalStoreSyncStatus {
+                sync_phase: s.sync_phase.and_then(|n| AnalyticalStoreSyncType::try_from(n).ok()),
+                current_sync_timestamp: s.current_sync_timestamp,
+            }),
+            storage_size: storage_size.map(|s| AnalyticalStoreStorageSize {
+                size: s.size,
+                timestamp: s.timestamp,
+            }),
+        }
+    }
+}
+
 /// 查询时序分析存储描述信息，例如分析存储配置信息、分析存储同步状态、分析存储大小等
 ///
 /// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/describe-timeseries-analytical-store>
@@ -30,7 +110,7 @@ impl DescribeTimeseriesAnalyticalStoreOperation {
         }
     }
 
-    pub async fn send(self) -> OtsResult<crate::protos::timeseries::DescribeTimeseriesAnalyticalStoreResponse> {
+    pub async fn send(self) -> OtsResult<DescribeTimeseriesAnalyticalStoreResponse> {
         if !validate_timeseries_table_name(&self.table_name) {
             return Err(OtsError::ValidationFailed(format!("invalie timeseries table name: {}", self.table_name)));
         }
@@ -59,8 +139,8 @@ impl DescribeTimeseriesAnalyticalStoreOperation {
         };
 
         let resp = client.send(req).await?;
-        Ok(crate::protos::timeseries::DescribeTimeseriesAnalyticalStoreResponse::decode(
-            resp.bytes().await?,
-        )?)
+        let resp_msg = crate::protos::timeseries::DescribeTimeseriesAnalyticalStoreResponse::decode(resp.bytes().await?)?;
+
+        Ok(resp_msg.into())
     }
 }
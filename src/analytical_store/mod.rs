@@ -13,9 +13,60 @@ pub use update_analytical_store::*;
 
 #[cfg(test)]
 mod test_analytical_store {
-    use crate::{test_util::setup, OtsClient};
+    use crate::{
+        protos::timeseries::{AnalyticalStoreSyncStat, AnalyticalStoreSyncType, TimeseriesAnalyticalStore},
+        test_util::setup,
+        timeseries_model::rules::MIN_ANALYTICAL_STORE_TTL_SECONDS,
+        OtsClient,
+    };
 
-    use super::CreateTimeseriesAnalyticalStoreRequest;
+    use super::{
+        AnalyticalStoreStorageSize, AnalyticalStoreSyncStatus, CreateTimeseriesAnalyticalStoreRequest, DescribeTimeseriesAnalyticalStoreResponse,
+        UpdateTimeseriesAnalyticalStoreRequest,
+    };
+
+    #[test]
+    fn test_describe_analytical_store_response() {
+        let msg = crate::protos::timeseries::DescribeTimeseriesAnalyticalStoreResponse {
+            analytical_store: Some(TimeseriesAnalyticalStore {
+                store_name: Some("default_analytical_store".to_string()),
+                time_to_live: Some(MIN_ANALYTICAL_STORE_TTL_SECONDS),
+                sync_option: Some(AnalyticalStoreSyncType::SyncTypeFull as i32),
+            }),
+            sync_stat: Some(AnalyticalStoreSyncStat {
+                sync_phase: Some(AnalyticalStoreSyncType::SyncTypeIncr as i32),
+                current_sync_timestamp: Some(1744119422),
+            }),
+            storage_size: Some(crate::protos::timeseries::AnalyticalStoreStorageSize {
+                size: Some(1024),
+                timestamp: Some(1744119400),
+            }),
+        };
+
+        let resp = DescribeTimeseriesAnalyticalStoreResponse::from(msg);
+        assert_eq!(Some("default_analytical_store"), resp.store_name.as_deref());
+        assert_eq!(Some(MIN_ANALYTICAL_STORE_TTL_SECONDS), resp.ttl_seconds);
+        assert_eq!(Some(AnalyticalStoreSyncType::SyncTypeFull), resp.sync_option);
+        assert_eq!(
+            Some(AnalyticalStoreSyncStatus {
+                sync_phase: Some(AnalyticalStoreSyncType::SyncTypeIncr),
+                current_sync_timestamp: Some(1744119422),
+            }),
+            resp.sync_status
+        );
+        assert_eq!(
+            Some(AnalyticalStoreStorageSize {
+                size: Some(1024),
+                timestamp: Some(1744119400),
+            }),
+            resp.storage_size
+        );
+        assert!(resp.is_full_sync_finished());
+
+        let resp = DescribeTimeseriesAnalyticalStoreResponse::from(crate::protos::timeseries::DescribeTimeseriesAnalyticalStoreResponse::default());
+        assert_eq!(DescribeTimeseriesAnalyticalStoreResponse::default(), resp);
+        assert!(!resp.is_full_sync_finished());
+    }
 
     #[tokio::test]
     async fn test_describe_analytical_store() {
@@ -29,6 +80,36 @@ mod test_analytical_store {
         log::debug!("{:?}", resp);
     }
 
+    async fn test_update_analytical_store_ttl_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        for ttl in [MIN_ANALYTICAL_STORE_TTL_SECONDS * 2, -1] {
+            client
+                .update_timeseries_analytical_store(
+                    UpdateTimeseriesAnalyticalStoreRequest::new("timeseries_demo_with_data", "default_analytical_store").ttl_seconds(ttl),
+                )
+                .send()
+                .await
+                .unwrap();
+
+            let resp = client
+                .describe_timeseries_analytical_store("timeseries_demo_with_data", "default_analytical_store")
+                .send()
+                .await
+                .unwrap();
+            log::debug!("{:?}", resp);
+
+            assert_eq!(Some(ttl), resp.ttl_seconds);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_analytical_store_ttl() {
+        test_update_analytical_store_ttl_impl().await;
+    }
+
     #[tokio::test]
     async fn test_create_analytical_store() {
         setup();
@@ -286,12 +286,19 @@ impl TryFrom<crate::protos::TableInBatchGetRowResponse> for TableInBatchGetRowRe
     type Error = OtsError;
 
     fn try_from(value: crate::protos::TableInBatchGetRowResponse) -> Result<Self, Self::Error> {
+        Self::try_from_checked(value, false)
+    }
+}
+
+impl TableInBatchGetRowResponse {
+    /// 转换响应。`verify_checksum` 为 `true` 的时候在解码行的同时校验行校验码
+    pub(crate) fn try_from_checked(value: crate::protos::TableInBatchGetRowResponse, verify_checksum: bool) -> OtsResult<Self> {
         let crate::protos::TableInBatchGetRowResponse { table_name, rows } = value;
 
         let mut ret_rows = vec![];
 
         for r in rows {
-            ret_rows.push(r.try_into()?);
+            ret_rows.push(RowInBatchGetRowResponse::try_from_checked(r, verify_checksum)?);
         }
 
         Ok(Self { table_name, rows: ret_rows })
@@ -301,6 +308,8 @@ impl TryFrom<crate::protos::TableInBatchGetRowResponse> for TableInBatchGetRowRe
 #[derive(Debug, Default, Clone)]
 pub struct RowInBatchGetRowResponse {
     pub is_ok: bool,
+
+    /// 这一行操作失败时服务端返回的错误，包括错误码和错误消息。操作成功时为 `None`
     pub error: Option<crate::protos::Error>,
     pub consumed: Option<ConsumedCapacity>,
     pub row: Option<Row>,
@@ -311,6 +320,13 @@ impl TryFrom<crate::protos::RowInBatchGetRowResponse> for RowInBatchGetRowRespon
     type Error = OtsError;
 
     fn try_from(value: crate::protos::RowInBatchGetRowResponse) -> Result<Self, Self::Error> {
+        Self::try_from_checked(value, false)
+    }
+}
+
+impl RowInBatchGetRowResponse {
+    /// 转换响应。`verify_checksum` 为 `true` 的时候在解码行的同时校验行校验码
+    pub(crate) fn try_from_checked(value: crate::protos::RowInBatchGetRowResponse, verify_checksum: bool) -> OtsResult<Self> {
         let crate::protos::RowInBatchGetRowResponse {
             is_ok,
             error,
@@ -325,7 +341,7 @@ impl TryFrom<crate::protos::RowInBatchGetRowResponse> for RowInBatchGetRowRespon
             consumed,
             row: if let Some(row_bytes) = row {
                 if !row_bytes.is_empty() {
-                    Some(Row::decode_plain_buffer(row_bytes, MASK_HEADER)?)
+                    Some(Row::decode_plain_buffer_checked(row_bytes, MASK_HEADER, verify_checksum)?)
                 } else {
                     None
                 }
@@ -346,11 +362,18 @@ pub struct BatchGetRowResponse {
 impl TryFrom<crate::protos::BatchGetRowResponse> for BatchGetRowResponse {
     type Error = OtsError;
     fn try_from(value: crate::protos::BatchGetRowResponse) -> OtsResult<Self> {
+        Self::try_from_checked(value, false)
+    }
+}
+
+impl BatchGetRowResponse {
+    /// 转换响应。`verify_checksum` 为 `true` 的时候在解码行的同时校验行校验码
+    pub(crate) fn try_from_checked(value: crate::protos::BatchGetRowResponse, verify_checksum: bool) -> OtsResult<Self> {
         let crate::protos::BatchGetRowResponse { tables } = value;
 
         let mut ret_tables = vec![];
         for t in tables {
-            ret_tables.push(t.try_into()?);
+            ret_tables.push(TableInBatchGetRowResponse::try_from_checked(t, verify_checksum)?);
         }
 
         Ok(Self { tables: ret_tables })
@@ -379,7 +402,11 @@ impl BatchGetRowOperation {
     pub async fn send(self) -> OtsResult<BatchGetRowResponse> {
         self.request.validate()?;
 
-        let Self { client, request, options } = self;
+        let Self { client, mut request, options } = self;
+
+        for table in &mut request.tables {
+            client.prepare_primary_keys(&table.table_name, &mut table.primary_keys).await?;
+        }
 
         let msg: crate::protos::BatchGetRowRequest = request.into();
 
@@ -394,6 +421,6 @@ impl BatchGetRowOperation {
 
         let response_msg = crate::protos::BatchGetRowResponse::decode(response.bytes().await?)?;
 
-        response_msg.try_into()
+        BatchGetRowResponse::try_from_checked(response_msg, client.options.verify_checksums)
     }
 }
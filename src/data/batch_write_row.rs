@@ -1,19 +1,29 @@
 use std::collections::HashSet;
 
 use prost::Message;
+use tokio::task::JoinSet;
 
 use crate::model::rules::validate_table_name;
 use crate::{
     add_per_request_options,
-    error::OtsError,
+    error::{ApiErrorCode, OtsError},
     model::{Filter, Row},
     protos::{
         plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM},
         Condition, ConsumedCapacity, OperationType, ReturnContent, ReturnType, RowExistenceExpectation,
     },
-    OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
+    util::join_next_or_resume,
+    DefaultRetryPolicy, OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
 
+/// 一次 `BatchWriteRow` 最多可以写入的行数
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/batchwriterow>
+const MAX_ROWS_IN_BATCH_WRITE: usize = 200;
+
+/// 拆分之后默认同时发送的 `BatchWriteRow` 请求数量
+const DEFAULT_CHUNK_CONCURRENCY: usize = 1;
+
 /// 在BatchWriteRow操作中，表示要插入、更新和删除的一行信息。
 #[derive(Debug, Default, Clone)]
 pub struct RowInBatchWriteRowRequest {
@@ -83,7 +93,8 @@ impl RowInBatchWriteRowRequest {
         self
     }
 
-    /// 设置行存在性检查
+    /// 设置行存在性检查。每一行的条件单独检查，条件不满足的行不会写入，
+    /// 可以通过响应中的 [`RowInBatchWriteRowResponse::is_condition_check_failed`] 判断
     pub fn row_condition(mut self, row_condition: RowExistenceExpectation) -> Self {
         self.row_condition = row_condition;
 
@@ -193,6 +204,12 @@ impl TableInBatchWriteRowRequest {
             return Err(OtsError::ValidationFailed("invalid rows in table: must not empty".to_string()));
         }
 
+        for row in &self.rows {
+            if let Some(f) = &row.column_condition {
+                f.validate()?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -270,6 +287,22 @@ impl BatchWriteRowRequest {
     /// - tables中任一PutRowInBatchWriteRowRequest包含的Column个数超过1024个。
     /// - tables中任一UpdateRowInBatchWriteRowRequest包含的ColumnUpdate个数超过1024个。
     fn validate(&self) -> OtsResult<()> {
+        self.validate_tables()?;
+
+        let n = self.tables.iter().map(|t| t.rows.len()).sum::<usize>();
+
+        if n > MAX_ROWS_IN_BATCH_WRITE {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid tables. maximum rows to write is {}, you passed {}",
+                MAX_ROWS_IN_BATCH_WRITE, n
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 验证除了总行数以外的限制
+    fn validate_tables(&self) -> OtsResult<()> {
         if self.tables.is_empty() {
             return Err(OtsError::ValidationFailed("tables can not be empty".to_string()));
         }
@@ -282,20 +315,70 @@ impl BatchWriteRowRequest {
             ));
         }
 
-        let n = self.tables.iter().map(|t| t.rows.len()).sum::<usize>();
+        for table in &self.tables {
+            table.validate()?;
+        }
 
-        if n > 200 {
+        Ok(())
+    }
+}
+
+impl BatchWriteRowRequest {
+    /// 验证请求并按照 `BatchWriteRow` 的行数限制拆分成多个请求。
+    /// 批次之间不是原子的，所以设置了 `is_atomic` 并且超过行数限制的请求返回错误
+    pub(crate) fn into_write_chunks(self) -> OtsResult<Vec<BatchWriteRowRequest>> {
+        let rows_total = self.tables.iter().map(|t| t.rows.len()).sum::<usize>();
+
+        if rows_total > MAX_ROWS_IN_BATCH_WRITE && self.is_atomic == Some(true) {
             return Err(OtsError::ValidationFailed(format!(
-                "invalid tables. maximum rows to get is 100, you passed {}",
-                n
+                "atomic batch write can not be split into chunks, rows count limit: {}",
+                MAX_ROWS_IN_BATCH_WRITE
             )));
         }
 
-        for table in &self.tables {
-            table.validate()?;
+        self.validate_tables()?;
+
+        Ok(self.into_chunks(MAX_ROWS_IN_BATCH_WRITE))
+    }
+
+    /// 按照行数把请求拆分成多个请求，每个请求最多 `max_rows` 行，行的顺序保持不变
+    pub(crate) fn into_chunks(self, max_rows: usize) -> Vec<BatchWriteRowRequest> {
+        let BatchWriteRowRequest {
+            tables,
+            transaction_id,
+            is_atomic,
+        } = self;
+
+        let new_chunk = |tables| BatchWriteRowRequest {
+            tables,
+            transaction_id: transaction_id.clone(),
+            is_atomic,
+        };
+
+        let mut chunks = vec![];
+        let mut current = vec![];
+        let mut n = 0;
+
+        for TableInBatchWriteRowRequest { table_name, rows } in tables {
+            let mut rows = rows.into_iter().peekable();
+
+            while rows.peek().is_some() {
+                if n == max_rows {
+                    chunks.push(new_chunk(std::mem::take(&mut current)));
+                    n = 0;
+                }
+
+                let part = rows.by_ref().take(max_rows - n).collect::<Vec<_>>();
+                n += part.len();
+                current.push(TableInBatchWriteRowRequest::new(&table_name).rows(part));
+            }
         }
 
-        Ok(())
+        if !current.is_empty() {
+            chunks.push(new_chunk(current));
+        }
+
+        chunks
     }
 }
 
@@ -320,11 +403,33 @@ impl From<BatchWriteRowRequest> for crate::protos::BatchWriteRowRequest {
 #[derive(Debug, Clone, Default)]
 pub struct RowInBatchWriteRowResponse {
     pub is_ok: bool,
+
+    /// 这一行操作失败时服务端返回的错误，包括错误码和错误消息。操作成功时为 `None`
     pub error: Option<crate::protos::Error>,
     pub consumed: Option<ConsumedCapacity>,
     pub row: Option<Row>,
 }
 
+impl RowInBatchWriteRowResponse {
+    /// 是否因为行存在性检查或者列条件不满足而写入失败
+    pub fn is_condition_check_failed(&self) -> bool {
+        !self.is_ok
+            && self
+                .error
+                .as_ref()
+                .is_some_and(|e| ApiErrorCode::from(e.code.as_str()) == ApiErrorCode::ConditionCheckFail)
+    }
+
+    /// 是否因为行操作冲突、服务端繁忙等临时性的原因写入失败，重新写入这一行可能会成功
+    pub fn is_retriable(&self) -> bool {
+        !self.is_ok
+            && self
+                .error
+                .as_ref()
+                .is_some_and(|e| DefaultRetryPolicy::is_retriable_code(&ApiErrorCode::from(e.code.as_str())))
+    }
+}
+
 impl TryFrom<crate::protos::RowInBatchWriteRowResponse> for RowInBatchWriteRowResponse {
     type Error = OtsError;
 
@@ -374,6 +479,89 @@ pub struct BatchWriteRowResponse {
     pub tables: Vec<TableInBatchWriteRowResponse>,
 }
 
+impl BatchWriteRowResponse {
+    /// 写入失败的行，返回表名、行在请求中该表的行列表中的位置和行的响应。
+    /// 包括条件检查失败的行，可以用 [`RowInBatchWriteRowResponse::is_condition_check_failed`] 区分
+    pub fn failed_rows(&self) -> Vec<(&str, usize, &RowInBatchWriteRowResponse)> {
+        self.tables
+            .iter()
+            .flat_map(|t| t.rows.iter().enumerate().filter(|(_, r)| !r.is_ok).map(|(i, r)| (t.table_name.as_str(), i, r)))
+            .collect()
+    }
+}
+
+/// 把这一次请求的响应合并到最终的响应中，返回 `should_retry` 为 `true` 的行组成的请求，以及这些行在最初的请求中的位置。
+///
+/// `positions[i]` 为 `request` 中第 `i` 个表在最初的请求中的位置，以及这个表的每一行在最初的请求中该表的行列表中的位置。
+/// 返回的请求中不包含没有需要重试的行的表
+pub(crate) fn merge_batch_write_row_response(
+    merged: &mut BatchWriteRowResponse,
+    positions: &[(usize, Vec<usize>)],
+    request: &BatchWriteRowRequest,
+    response: BatchWriteRowResponse,
+    should_retry: impl Fn(&RowInBatchWriteRowResponse) -> bool,
+) -> (BatchWriteRowRequest, Vec<(usize, Vec<usize>)>) {
+    let mut retry_request = BatchWriteRowRequest {
+        tables: vec![],
+        transaction_id: request.transaction_id.clone(),
+        is_atomic: request.is_atomic,
+    };
+    let mut retry_positions = vec![];
+
+    for ((table_req, table_resp), (table_idx, row_positions)) in request.tables.iter().zip(response.tables).zip(positions) {
+        let mut retry_table = TableInBatchWriteRowRequest::new(&table_req.table_name);
+        let mut retry_row_positions = vec![];
+
+        for ((row_req, row_resp), pos) in table_req.rows.iter().zip(table_resp.rows).zip(row_positions) {
+            if !row_resp.is_ok && should_retry(&row_resp) {
+                retry_table.rows.push(row_req.clone());
+                retry_row_positions.push(*pos);
+            }
+
+            merged.tables[*table_idx].rows[*pos] = row_resp;
+        }
+
+        if !retry_table.rows.is_empty() {
+            retry_request.tables.push(retry_table);
+            retry_positions.push((*table_idx, retry_row_positions));
+        }
+    }
+
+    (retry_request, retry_positions)
+}
+
+/// 按照顺序合并拆分之后每一批请求的响应。
+///
+/// `tables` 是拆分之前的请求中每个表的表名和行数。拆分时表和行的顺序都保持不变，
+/// 所以依次把每一批响应中的行追加到还没有填满的表中即可，同一个表在请求中出现多次也不会混淆
+pub(crate) fn merge_chunk_responses(tables: &[(String, usize)], responses: Vec<BatchWriteRowResponse>) -> BatchWriteRowResponse {
+    let mut merged = BatchWriteRowResponse {
+        tables: tables
+            .iter()
+            .map(|(name, n)| TableInBatchWriteRowResponse {
+                table_name: name.clone(),
+                rows: Vec::with_capacity(*n),
+            })
+            .collect(),
+    };
+
+    let mut idx = 0;
+
+    for table_resp in responses.into_iter().flat_map(|r| r.tables) {
+        while idx < tables.len() && merged.tables[idx].rows.len() >= tables[idx].1 {
+            idx += 1;
+        }
+
+        if idx == tables.len() {
+            break;
+        }
+
+        merged.tables[idx].rows.extend(table_resp.rows);
+    }
+
+    merged
+}
+
 impl TryFrom<crate::protos::BatchWriteRowResponse> for BatchWriteRowResponse {
     type Error = OtsError;
 
@@ -411,7 +599,13 @@ impl BatchWriteRowOperation {
     pub async fn send(self) -> OtsResult<BatchWriteRowResponse> {
         self.request.validate()?;
 
-        let Self { client, request, options } = self;
+        let Self { client, mut request, options } = self;
+
+        for table in &mut request.tables {
+            client
+                .prepare_primary_keys(&table.table_name, table.rows.iter_mut().map(|r| &mut r.row.primary_key))
+                .await?;
+        }
 
         let msg: crate::protos::BatchWriteRowRequest = request.into();
 
@@ -428,4 +622,198 @@ impl BatchWriteRowOperation {
 
         response_msg.try_into()
     }
+
+    /// 把超过 200 行的请求拆分成多个 `BatchWriteRow` 请求依次发送，返回每一批的响应。
+    ///
+    /// 每一批完成之后调用 `on_progress(rows_done, rows_total, &chunk_result)`，可以用来显示进度。
+    /// 回调在调用 `send_chunked` 的任务中同步执行，执行期间不会发送下一批请求，所以回调中不要做耗时的操作。
+    /// 某一批请求失败时，调用回调之后立即返回错误，已经写入的批次不会回滚。
+    /// 批次之间不是原子的，所以设置了 `is_atomic` 的请求不能拆分成多批。
+    pub async fn send_chunked<F>(self, mut on_progress: F) -> OtsResult<Vec<BatchWriteRowResponse>>
+    where
+        F: FnMut(usize, usize, &OtsResult<BatchWriteRowResponse>),
+    {
+        let Self { client, request, options } = self;
+
+        let rows_total = request.tables.iter().map(|t| t.rows.len()).sum::<usize>();
+        let mut rows_done = 0;
+        let mut responses = vec![];
+
+        for chunk in request.into_write_chunks()? {
+            let n = chunk.tables.iter().map(|t| t.rows.len()).sum::<usize>();
+
+            let result = Self {
+                client: client.clone(),
+                request: chunk,
+                options: options.clone(),
+            }
+            .send()
+            .await;
+
+            if result.is_ok() {
+                rows_done += n;
+            }
+
+            on_progress(rows_done, rows_total, &result);
+
+            responses.push(result?);
+        }
+
+        Ok(responses)
+    }
+
+    /// 发送请求，并重新写入因为行操作冲突（`OTSRowOperationConflict`）、服务端繁忙等临时性原因失败的行。
+    ///
+    /// 每次重试只发送上一次失败的行。是否重试某一行以及两次重试之间等待的时间由客户端的重试策略
+    /// （[`OtsClientBuilder::rety_policy`](`crate::OtsClientBuilder::rety_policy`)）根据这一行的错误决定，
+    /// 直到全部写入成功，或者重试了 `max_retries` 次，或者重试策略不再重试，或者收到客户端的停机信号。
+    /// 返回的响应中每一行都是这一行最后一次写入的结果，行的顺序和请求中相同，
+    /// 调用 [`BatchWriteRowResponse::failed_rows`] 可以得到最终仍然失败的行和错误码。
+    /// 条件检查失败等不能通过重试解决的错误不会重试。整个请求失败时直接返回错误。
+    pub async fn send_with_retry(self, max_retries: u32) -> OtsResult<BatchWriteRowResponse> {
+        let Self { client, mut request, options } = self;
+
+        let mut merged = BatchWriteRowResponse {
+            tables: request
+                .tables
+                .iter()
+                .map(|t| TableInBatchWriteRowResponse {
+                    table_name: t.table_name.clone(),
+                    rows: vec![RowInBatchWriteRowResponse::default(); t.rows.len()],
+                })
+                .collect(),
+        };
+
+        let mut positions = request
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (i, (0..t.rows.len()).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        let row_error = |row: &RowInBatchWriteRowResponse| OtsError::from_row_error(row.error.clone(), "write row");
+        let mut retried = 0;
+
+        loop {
+            let response = Self {
+                client: client.clone(),
+                request: request.clone(),
+                options: options.clone(),
+            }
+            .send()
+            .await?;
+
+            let (retry_request, retry_positions) = merge_batch_write_row_response(&mut merged, &positions, &request, response, |row| {
+                client.retry_policy.should_retry(retried, OtsOp::BatchWriteRow, &row_error(row))
+            });
+            let retry_rows = retry_positions.iter().map(|(_, p)| p.len()).sum::<usize>();
+
+            if retry_rows == 0 || retried >= max_retries {
+                return Ok(merged);
+            }
+
+            // 等待时间按照第一个需要重试的行的错误计算
+            let (table_idx, row_positions) = &retry_positions[0];
+            let delay_ms = client
+                .retry_policy
+                .delay_ms(retried, OtsOp::BatchWriteRow, &row_error(&merged.tables[*table_idx].rows[row_positions[0]]));
+
+            log::info!(
+                "retry {} failed rows in batch write row after {} ms, retried {} times",
+                retry_rows,
+                delay_ms,
+                retried
+            );
+
+            if client.options.sleep_or_shutdown(delay_ms as u64).await {
+                return Ok(merged);
+            }
+
+            request = retry_request;
+            positions = retry_positions;
+            retried += 1;
+        }
+    }
+}
+
+/// 自动拆分超过 200 行的批量写入请求
+#[derive(Clone)]
+pub struct BatchWriteRowChunkedOperation {
+    client: OtsClient,
+    request: BatchWriteRowRequest,
+    concurrency: usize,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(BatchWriteRowChunkedOperation);
+
+impl BatchWriteRowChunkedOperation {
+    pub(crate) fn new(client: OtsClient, request: BatchWriteRowRequest) -> Self {
+        Self {
+            client,
+            request,
+            concurrency: DEFAULT_CHUNK_CONCURRENCY,
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    /// 同时发送的 `BatchWriteRow` 请求的最大数量，默认为 `1`，即依次发送
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+
+        self
+    }
+
+    /// 等待正在执行的请求完成一个，并把响应放到对应的位置
+    async fn join_next(tasks: &mut JoinSet<(usize, OtsResult<BatchWriteRowResponse>)>, responses: &mut [Option<BatchWriteRowResponse>]) -> OtsResult<()> {
+        if let Some((i, result)) = join_next_or_resume(tasks).await {
+            responses[i] = Some(result?);
+        }
+
+        Ok(())
+    }
+
+    /// 发送请求。按照请求中的顺序每 200 行拆分成一个 `BatchWriteRow` 请求，同一个表的行保持在一起，
+    /// 返回合并之后的响应，其中每个表的行和请求中的顺序相同。
+    ///
+    /// 任意一个请求失败都会返回错误，已经写入的批次不会回滚。批次之间不是原子的，所以设置了 `is_atomic` 的请求不能拆分。
+    /// 每一批请求仍然受请求大小的限制，超过时返回 [`OtsError::RequestTooLarge`]
+    pub async fn send(self) -> OtsResult<BatchWriteRowResponse> {
+        let Self {
+            client,
+            request,
+            concurrency,
+            options,
+        } = self;
+
+        if concurrency == 0 {
+            return Err(OtsError::ValidationFailed("invalid concurrency: must be greater than 0".to_string()));
+        }
+
+        let tables = request.tables.iter().map(|t| (t.table_name.clone(), t.rows.len())).collect::<Vec<_>>();
+        let chunks = request.into_write_chunks()?;
+
+        let mut responses = vec![None; chunks.len()];
+        let mut tasks = JoinSet::new();
+
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            while tasks.len() >= concurrency {
+                Self::join_next(&mut tasks, &mut responses).await?;
+            }
+
+            let op = BatchWriteRowOperation {
+                client: client.clone(),
+                request: chunk,
+                options: options.clone(),
+            };
+
+            tasks.spawn(async move { (i, op.send().await) });
+        }
+
+        while !tasks.is_empty() {
+            Self::join_next(&mut tasks, &mut responses).await?;
+        }
+
+        Ok(merge_chunk_responses(&tables, responses.into_iter().flatten().collect()))
+    }
 }
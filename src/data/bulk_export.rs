@@ -1,15 +1,16 @@
 use std::{collections::HashSet, io::Cursor};
 
-use byteorder::{LittleEndian, ReadBytesExt};
+use futures_core::Stream;
 use prost::Message;
 
+use crate::data::row_stream::{PagedRowOperation, RowStream};
 use crate::model::rules::validate_table_name;
 use crate::{
     add_per_request_options,
     error::OtsError,
-    model::{Filter, PrimaryKey, PrimaryKeyColumn, Row},
+    model::{Filter, PrimaryKey, PrimaryKeyColumn, Row, RowReader},
     protos::{
-        plain_buffer::{HEADER, MASK_HEADER, MASK_ROW_CHECKSUM},
+        plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM},
         simple_row_matrix::SimpleRowMatrix,
         ConsumedCapacity, DataBlockType,
     },
@@ -216,6 +217,10 @@ impl BulkExportRequest {
             ));
         }
 
+        if let Some(f) = &self.filter {
+            f.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -270,26 +275,7 @@ impl TryFrom<crate::protos::BulkExportResponse> for BulkExportResponse {
 
         let rows = if !rows_bytes.is_empty() {
             match data_block_type {
-                DataBlockType::DbtPlainBuffer => {
-                    let mut rows = vec![];
-                    let mut cursor = Cursor::new(rows_bytes);
-                    let header = cursor.read_u32::<LittleEndian>()?;
-
-                    if header != HEADER {
-                        return Err(OtsError::PlainBufferError(format!("invalid message header: {}", header)));
-                    }
-
-                    loop {
-                        if cursor.position() as usize >= cursor.get_ref().len() - 1 {
-                            break;
-                        }
-
-                        let row = Row::read_plain_buffer(&mut cursor)?;
-                        rows.push(row);
-                    }
-
-                    rows
-                }
+                DataBlockType::DbtPlainBuffer => RowReader::new(Cursor::new(rows_bytes), MASK_HEADER).collect::<OtsResult<Vec<_>>>()?,
                 DataBlockType::DbtSimpleRowMatrix => SimpleRowMatrix::new(rows_bytes).get_rows()?,
             }
         } else {
@@ -352,4 +338,40 @@ impl BulkExportOperation {
 
         resp_msg.try_into()
     }
+
+    /// 转换为自动翻页的行读取流。
+    ///
+    /// 在内部使用 `next_start_primary_key` 作为起始主键继续发送请求，直到服务端不再返回断点为止，
+    /// 每一页都使用相同的 `columns_to_get` 等参数。出错之后返回这个错误，然后结束。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let stream = client.bulk_export(request).into_row_stream();
+    /// tokio::pin!(stream);
+    ///
+    /// while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+    ///     let row = row?;
+    ///     println!("{:?}", row);
+    /// }
+    /// ```
+    pub fn into_row_stream(self) -> impl Stream<Item = OtsResult<Row>> {
+        RowStream::new(self)
+    }
+}
+
+impl PagedRowOperation for BulkExportOperation {
+    async fn next_page(self) -> OtsResult<(Vec<Row>, Option<Self>)> {
+        let mut next_op = self.clone();
+        let BulkExportResponse {
+            rows, next_start_primary_key, ..
+        } = self.send().await?;
+
+        let next = next_start_primary_key.map(|pk| {
+            next_op.request.inclusive_start_primary_key = pk;
+            next_op
+        });
+
+        Ok((rows, next))
+    }
 }
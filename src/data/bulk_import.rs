@@ -1,4 +1,5 @@
 use prost::Message;
+use tokio::task::JoinSet;
 
 use crate::model::rules::validate_table_name;
 use crate::{
@@ -9,9 +10,15 @@ use crate::{
         plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM},
         OperationType,
     },
+    util::join_next_or_resume,
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
 
+/// 一次 `BulkImport` 最多可以写入的行数
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/bulkimport>
+const MAX_ROWS_IN_BULK_IMPORT: usize = 200;
+
 #[derive(Debug, Default, Clone)]
 pub struct RowInBulkImportRequest {
     pub operation_type: OperationType,
@@ -113,6 +120,29 @@ impl BulkImportRequest {
         self
     }
 
+    /// 按照顺序把请求中的行拆分成多个请求，每个请求最多 `batch_size` 行。
+    /// `batch_size` 为 `0` 或者超过 200 的时候，按照 200 行拆分
+    pub fn into_batches(self, batch_size: usize) -> Vec<BulkImportRequest> {
+        let batch_size = if batch_size == 0 || batch_size > MAX_ROWS_IN_BULK_IMPORT {
+            MAX_ROWS_IN_BULK_IMPORT
+        } else {
+            batch_size
+        };
+
+        let Self { table_name, rows } = self;
+        let mut batches = vec![];
+        let mut rows = rows.into_iter().peekable();
+
+        while rows.peek().is_some() {
+            batches.push(Self {
+                table_name: table_name.clone(),
+                rows: rows.by_ref().take(batch_size).collect(),
+            });
+        }
+
+        batches
+    }
+
     fn validate(&self) -> OtsResult<()> {
         if !validate_table_name(&self.table_name) {
             return Err(OtsError::ValidationFailed(format!("invalid table name: {}", self.table_name)));
@@ -122,8 +152,11 @@ impl BulkImportRequest {
             return Err(OtsError::ValidationFailed("invalid rows to import, can not be empty".to_string()));
         }
 
-        if self.rows.len() > 200 {
-            return Err(OtsError::ValidationFailed("invalid rows to import, rows count limit: 200".to_string()));
+        if self.rows.len() > MAX_ROWS_IN_BULK_IMPORT {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid rows to import, rows count limit: {}",
+                MAX_ROWS_IN_BULK_IMPORT
+            )));
         }
 
         Ok(())
@@ -141,6 +174,35 @@ impl From<BulkImportRequest> for crate::protos::BulkImportRequest {
     }
 }
 
+/// 并发批量写入的结果
+#[derive(Debug, Default)]
+pub struct BulkImportAllResponse {
+    /// 成功写入的行数
+    pub rows_imported: usize,
+
+    /// 整个请求失败的批次和对应的错误，按照批次在原始请求中的顺序排列
+    pub failed_batches: Vec<(BulkImportRequest, OtsError)>,
+
+    /// 请求成功但是写入失败的行。三个值分别是行在原始请求中的位置、行和错误，按照行在原始请求中的顺序排列
+    pub failed_rows: Vec<(usize, RowInBulkImportRequest, OtsError)>,
+}
+
+impl BulkImportAllResponse {
+    /// 根据响应中每一行的结果累加成功的行数，并记录失败的行。`offset` 是这一批的第一行在原始请求中的位置
+    pub(crate) fn add_batch_response(&mut self, offset: usize, batch: BulkImportRequest, response: crate::protos::BulkImportResponse) {
+        let BulkImportRequest { table_name, rows } = batch;
+
+        for (i, (row, r)) in rows.into_iter().zip(response.rows).enumerate() {
+            if r.is_ok {
+                self.rows_imported += 1;
+            } else {
+                let e = OtsError::from_row_error(r.error, format_args!("bulk import row to table {}", table_name));
+                self.failed_rows.push((offset + i, row, e));
+            }
+        }
+    }
+}
+
 /// 批量写入操作
 #[derive(Clone)]
 pub struct BulkImportOperation {
@@ -178,4 +240,113 @@ impl BulkImportOperation {
 
         Ok(crate::protos::BulkImportResponse::decode(response.bytes().await?)?)
     }
+
+    /// 把超过 200 行的请求拆分成多个 `BulkImport` 请求依次发送，返回每一批的响应。
+    ///
+    /// 每一批完成之后调用 `on_progress(rows_done, rows_total, &chunk_result)`，可以用来显示进度。
+    /// 回调在调用 `send_chunked` 的任务中同步执行，执行期间不会发送下一批请求，所以回调中不要做耗时的操作。
+    /// 某一批请求失败时，调用回调之后立即返回错误，已经写入的批次不会回滚。
+    pub async fn send_chunked<F>(self, mut on_progress: F) -> OtsResult<Vec<crate::protos::BulkImportResponse>>
+    where
+        F: FnMut(usize, usize, &OtsResult<crate::protos::BulkImportResponse>),
+    {
+        let Self { client, request, options } = self;
+
+        if !validate_table_name(&request.table_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid table name: {}", request.table_name)));
+        }
+
+        if request.rows.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid rows to import, can not be empty".to_string()));
+        }
+
+        let rows_total = request.rows.len();
+        let mut rows_done = 0;
+        let mut responses = vec![];
+
+        for batch in request.into_batches(MAX_ROWS_IN_BULK_IMPORT) {
+            let rows_in_batch = batch.rows.len();
+
+            let result = Self {
+                client: client.clone(),
+                request: batch,
+                options: options.clone(),
+            }
+            .send()
+            .await;
+
+            if result.is_ok() {
+                rows_done += rows_in_batch;
+            }
+
+            on_progress(rows_done, rows_total, &result);
+
+            responses.push(result?);
+        }
+
+        Ok(responses)
+    }
+
+    /// 等待正在执行的批次完成一个，并记录结果
+    async fn join_next(
+        tasks: &mut JoinSet<(usize, BulkImportRequest, OtsResult<crate::protos::BulkImportResponse>)>,
+        result: &mut BulkImportAllResponse,
+        failed: &mut Vec<(usize, BulkImportRequest, OtsError)>,
+    ) {
+        match join_next_or_resume(tasks).await {
+            Some((offset, batch, Ok(resp))) => result.add_batch_response(offset, batch, resp),
+            Some((offset, batch, Err(e))) => failed.push((offset, batch, e)),
+            None => {}
+        }
+    }
+
+    /// 把请求按照 200 行拆分成多个 `BulkImport` 请求，最多同时发送 `concurrency` 个。
+    ///
+    /// 和 [`send_chunked`](Self::send_chunked) 不同，某一批失败之后会继续发送其他批次，
+    /// 返回成功写入的行数、整个请求失败的批次以及写入失败的行，可以用失败的批次和行重新发起请求
+    pub async fn send_concurrently(self, concurrency: usize) -> OtsResult<BulkImportAllResponse> {
+        let Self { client, request, options } = self;
+
+        if concurrency == 0 {
+            return Err(OtsError::ValidationFailed("invalid concurrency: must be greater than 0".to_string()));
+        }
+
+        if !validate_table_name(&request.table_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid table name: {}", request.table_name)));
+        }
+
+        if request.rows.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid rows to import, can not be empty".to_string()));
+        }
+
+        let mut result = BulkImportAllResponse::default();
+        let mut failed = vec![];
+        let mut tasks = JoinSet::new();
+
+        for (i, batch) in request.into_batches(MAX_ROWS_IN_BULK_IMPORT).into_iter().enumerate() {
+            let offset = i * MAX_ROWS_IN_BULK_IMPORT;
+
+            while tasks.len() >= concurrency {
+                Self::join_next(&mut tasks, &mut result, &mut failed).await;
+            }
+
+            let op = Self {
+                client: client.clone(),
+                request: batch.clone(),
+                options: options.clone(),
+            };
+
+            tasks.spawn(async move { (offset, batch, op.send().await) });
+        }
+
+        while !tasks.is_empty() {
+            Self::join_next(&mut tasks, &mut result, &mut failed).await;
+        }
+
+        failed.sort_by_key(|(i, _, _)| *i);
+        result.failed_batches = failed.into_iter().map(|(_, batch, e)| (batch, e)).collect();
+        result.failed_rows.sort_by_key(|(i, _, _)| *i);
+
+        Ok(result)
+    }
 }
@@ -0,0 +1,168 @@
+use tokio::task::JoinSet;
+
+use crate::model::rules::validate_table_name;
+use crate::{
+    add_per_request_options,
+    error::OtsError,
+    model::{PrimaryKey, Row},
+    util::join_next_or_resume,
+    OtsClient, OtsRequestOptions, OtsResult,
+};
+
+use super::{BatchWriteRowRequest, GetRangeRequest, RowInBatchWriteRowRequest, TableInBatchWriteRowRequest};
+
+/// 一次 `BatchWriteRow` 最多可以删除的行数
+const MAX_ROWS_IN_BATCH: usize = 200;
+
+/// 删除主键范围的响应
+#[derive(Debug, Clone, Default)]
+pub struct DeleteRangeResponse {
+    /// 删除的行数
+    pub rows_deleted: u64,
+}
+
+/// 删除指定主键范围 `[start, end)` 内的全部行。
+///
+/// 服务端没有范围删除的接口，这里先通过 `GetRange` 只读取主键，然后通过 `BatchWriteRow` 分批删除，每批最多 200 行。
+/// 删除不是原子的：如果中途出错，已经删除的行不会恢复，可以使用相同的范围重新执行。
+#[derive(Clone)]
+pub struct DeleteRangeOperation {
+    client: OtsClient,
+    table_name: String,
+    inclusive_start_primary_key: PrimaryKey,
+    exclusive_end_primary_key: PrimaryKey,
+    concurrency: usize,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(DeleteRangeOperation);
+
+impl DeleteRangeOperation {
+    pub(crate) fn new(client: OtsClient, table_name: &str, start_pk: PrimaryKey, end_pk: PrimaryKey) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            inclusive_start_primary_key: start_pk,
+            exclusive_end_primary_key: end_pk,
+            concurrency: 1,
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    /// 同时发送的 `BatchWriteRow` 请求的最大数量，默认为 `1`
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+
+        self
+    }
+
+    fn validate(&self) -> OtsResult<()> {
+        if !validate_table_name(&self.table_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid table name: {}", self.table_name)));
+        }
+
+        if self.inclusive_start_primary_key.columns.is_empty() || self.exclusive_end_primary_key.columns.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid primary key: empty primary key columns".to_string()));
+        }
+
+        if self.concurrency == 0 {
+            return Err(OtsError::ValidationFailed("invalid concurrency: must be greater than 0".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// 删除一批行，返回删除的行数。只要有一行删除失败，就返回错误
+    async fn delete_rows(client: OtsClient, table_name: String, primary_keys: Vec<PrimaryKey>, options: OtsRequestOptions) -> OtsResult<u64> {
+        let n = primary_keys.len() as u64;
+
+        let table = TableInBatchWriteRowRequest::new(&table_name).rows(
+            primary_keys
+                .into_iter()
+                .map(|pk| RowInBatchWriteRowRequest::delete_row(Row::new().primary_key(pk))),
+        );
+
+        let mut op = client.batch_write_row(BatchWriteRowRequest::new().table(table));
+
+        if let Some(ms) = options.timeout_ms {
+            op = op.timeout_ms(ms);
+        }
+
+        let resp = op.send().await?;
+
+        for t in resp.tables {
+            for r in t.rows {
+                if !r.is_ok {
+                    return Err(OtsError::from_row_error(r.error, format_args!("delete row in table {}", t.table_name)));
+                }
+            }
+        }
+
+        Ok(n)
+    }
+
+    /// 等待正在执行的删除任务完成一个，并累加删除的行数
+    async fn join_next(tasks: &mut JoinSet<OtsResult<u64>>, rows_deleted: &mut u64) -> OtsResult<()> {
+        if let Some(result) = join_next_or_resume(tasks).await {
+            *rows_deleted += result?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn send(self) -> OtsResult<DeleteRangeResponse> {
+        self.validate()?;
+
+        let Self {
+            client,
+            table_name,
+            inclusive_start_primary_key,
+            exclusive_end_primary_key,
+            concurrency,
+            options,
+        } = self;
+
+        // 只需要主键，所以只读取第一个主键列，避免返回数据列
+        let first_pk_name = inclusive_start_primary_key.columns[0].name.clone();
+
+        let mut start_pk = inclusive_start_primary_key;
+        let mut rows_deleted = 0u64;
+        let mut tasks = JoinSet::new();
+
+        loop {
+            let mut op = client.get_range(
+                GetRangeRequest::new(&table_name)
+                    .primary_key_range(start_pk, exclusive_end_primary_key.clone())
+                    .column_to_get(&first_pk_name)
+                    .max_versions(1),
+            );
+
+            if let Some(ms) = options.timeout_ms {
+                op = op.timeout_ms(ms);
+            }
+
+            let resp = op.send().await?;
+
+            let primary_keys = resp.rows.into_iter().map(|r| r.primary_key).collect::<Vec<_>>();
+
+            for chunk in primary_keys.chunks(MAX_ROWS_IN_BATCH) {
+                while tasks.len() >= concurrency {
+                    Self::join_next(&mut tasks, &mut rows_deleted).await?;
+                }
+
+                tasks.spawn(Self::delete_rows(client.clone(), table_name.clone(), chunk.to_vec(), options.clone()));
+            }
+
+            match resp.next_start_primary_key {
+                Some(pk_cols) => start_pk = PrimaryKey { columns: pk_cols },
+                None => break,
+            }
+        }
+
+        while !tasks.is_empty() {
+            Self::join_next(&mut tasks, &mut rows_deleted).await?;
+        }
+
+        Ok(DeleteRangeResponse { rows_deleted })
+    }
+}
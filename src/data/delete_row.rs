@@ -163,6 +163,10 @@ impl DeleteRowRequest {
             }
         }
 
+        if let Some(f) = &self.column_condition {
+            f.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -250,7 +254,9 @@ impl DeleteRowOperation {
     pub async fn send(self) -> OtsResult<DeleteRowResponse> {
         self.request.validate()?;
 
-        let Self { client, request, options } = self;
+        let Self { client, mut request, options } = self;
+
+        client.prepare_primary_keys(&request.table_name, [&mut request.primary_key]).await?;
 
         let msg: crate::protos::DeleteRowRequest = request.into();
 
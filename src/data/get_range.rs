@@ -1,15 +1,16 @@
-use crate::model::rules::validate_table_name;
-use crate::model::Row;
-use crate::protos::plain_buffer::{HEADER, MASK_HEADER, MASK_ROW_CHECKSUM};
+use crate::data::row_stream::{PagedRowOperation, RowStream};
+use crate::model::rules::{find_undefined_columns, validate_table_name, validate_time_range};
+use crate::model::{Row, RowReader};
+use crate::protos::plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM};
 use crate::protos::ConsumedCapacity;
 use crate::{
     add_per_request_options,
     error::OtsError,
-    model::{Filter, PrimaryKey, PrimaryKeyColumn},
+    model::{Filter, PrimaryKey, PrimaryKeyColumn, PrimaryKeyValue},
     protos::{Direction, TimeRange},
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
-use byteorder::{LittleEndian, ReadBytesExt};
+use futures_core::Stream;
 use prost::Message;
 use std::collections::HashSet;
 use std::io::Cursor;
@@ -243,6 +244,20 @@ impl GetRangeRequest {
         self
     }
 
+    /// 设置时间范围的起始时间戳（包含），单位是毫秒
+    pub fn time_range_start_ms(mut self, start_ms: i64) -> Self {
+        self.time_range_start_ms = Some(start_ms);
+
+        self
+    }
+
+    /// 设置时间范围的结束时间戳（不包含），单位是毫秒
+    pub fn time_range_end_ms(mut self, end_ms: i64) -> Self {
+        self.time_range_end_ms = Some(end_ms);
+
+        self
+    }
+
     /// 指定精确的时间戳
     pub fn specific_time_ms(mut self, time_ms: i64) -> Self {
         self.time_range_specific_ms = Some(time_ms);
@@ -289,7 +304,7 @@ impl GetRangeRequest {
     }
 
     /// 验证请求参数
-    fn validate(&self) -> OtsResult<()> {
+    pub(crate) fn validate(&self) -> OtsResult<()> {
         if !validate_table_name(&self.table_name) {
             return Err(OtsError::ValidationFailed(format!("invalid table name: {}", self.table_name)));
         }
@@ -308,6 +323,12 @@ impl GetRangeRequest {
             ));
         }
 
+        validate_time_range(self.time_range_start_ms, self.time_range_end_ms, self.time_range_specific_ms)?;
+
+        if let Some(f) = &self.filter {
+            f.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -382,6 +403,41 @@ pub struct GetRangeResponse {
     pub next_start_primary_key: Option<Vec<PrimaryKeyColumn>>,
 }
 
+impl GetRangeResponse {
+    /// 范围内是否还有没有读取的数据。为 `true` 时需要从 `next_start_primary_key` 继续读取
+    pub fn has_more(&self) -> bool {
+        self.next_start_primary_key.is_some()
+    }
+
+    /// 估算已经读取的数据占整个请求范围的比例，取值为 `[0.0, 1.0]`。
+    ///
+    /// `request` 是整个扫描任务最初的请求（即使用最初的起始主键，而不是断点主键）。计算时会找到起始主键和结束主键中第一个值不同的主键列，
+    /// 按照这一列的值线性估算进度，所以只有当这一列是整数类型，并且起止值都是有限值（不是 `InfMin` 或者 `InfMax`）时，估算才有意义，
+    /// 否则返回 `None`。没有更多数据时总是返回 `Some(1.0)`。正序和逆序读取都适用。
+    pub fn progress(&self, request: &GetRangeRequest) -> Option<f64> {
+        let next_pk = match &self.next_start_primary_key {
+            Some(pk) => pk,
+            None => return Some(1.0),
+        };
+
+        let start_cols = &request.inclusive_start_primary_key.columns;
+        let end_cols = &request.exclusive_end_primary_key.columns;
+
+        let idx = start_cols.iter().zip(end_cols.iter()).position(|(s, e)| s.value != e.value)?;
+
+        match (&start_cols[idx].value, &end_cols[idx].value, &next_pk.get(idx)?.value) {
+            (PrimaryKeyValue::Integer(start), PrimaryKeyValue::Integer(end), PrimaryKeyValue::Integer(next)) => {
+                let total = *end as f64 - *start as f64;
+                let done = *next as f64 - *start as f64;
+
+                Some((done / total).clamp(0.0, 1.0))
+            }
+
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<crate::protos::GetRangeResponse> for GetRangeResponse {
     type Error = OtsError;
 
@@ -408,25 +464,7 @@ impl TryFrom<crate::protos::GetRangeResponse> for GetRangeResponse {
             None
         };
 
-        let mut rows = vec![];
-
-        if !rows_bytes.is_empty() {
-            let mut cursor = Cursor::new(rows_bytes);
-            let header = cursor.read_u32::<LittleEndian>()?;
-
-            if header != HEADER {
-                return Err(OtsError::PlainBufferError(format!("invalid message header: {}", header)));
-            }
-
-            loop {
-                if cursor.position() as usize >= cursor.get_ref().len() - 1 {
-                    break;
-                }
-
-                let row = Row::read_plain_buffer(&mut cursor)?;
-                rows.push(row);
-            }
-        }
+        let rows = RowReader::new(Cursor::new(rows_bytes), MASK_HEADER).collect::<OtsResult<Vec<_>>>()?;
 
         Ok(Self {
             consumed,
@@ -460,7 +498,24 @@ impl GetRangeOperation {
     pub async fn send(self) -> OtsResult<GetRangeResponse> {
         self.request.validate()?;
 
-        let Self { client, request, options } = self;
+        let Self { client, mut request, options } = self;
+
+        client
+            .prepare_primary_keys(
+                &request.table_name,
+                [&mut request.inclusive_start_primary_key, &mut request.exclusive_end_primary_key],
+            )
+            .await?;
+
+        if let Some(table_meta) = client.cached_table_meta(&request.table_name).await {
+            for name in find_undefined_columns(&table_meta, &request.columns_to_get) {
+                log::warn!(
+                    "column {} in columns_to_get is neither primary key nor defined column of table {}",
+                    name,
+                    request.table_name
+                );
+            }
+        }
 
         let msg: crate::protos::GetRangeRequest = request.into();
 
@@ -476,4 +531,40 @@ impl GetRangeOperation {
 
         response_msg.try_into()
     }
+
+    /// 转换为自动翻页的行读取流。
+    ///
+    /// 在内部使用 `next_start_primary_key` 作为起始主键继续发送请求，直到服务端不再返回断点为止。
+    /// 请求中的 `limit` 作为每一页的行数。出错之后返回这个错误，然后结束。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let stream = client.get_range(request).into_row_stream();
+    /// tokio::pin!(stream);
+    ///
+    /// while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+    ///     let row = row?;
+    ///     println!("{:?}", row);
+    /// }
+    /// ```
+    pub fn into_row_stream(self) -> impl Stream<Item = OtsResult<Row>> {
+        RowStream::new(self)
+    }
+}
+
+impl PagedRowOperation for GetRangeOperation {
+    async fn next_page(self) -> OtsResult<(Vec<Row>, Option<Self>)> {
+        let mut next_op = self.clone();
+        let GetRangeResponse {
+            rows, next_start_primary_key, ..
+        } = self.send().await?;
+
+        let next = next_start_primary_key.map(|columns| {
+            next_op.request.inclusive_start_primary_key = PrimaryKey { columns };
+            next_op
+        });
+
+        Ok((rows, next))
+    }
 }
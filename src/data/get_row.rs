@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use prost::Message;
 
-use crate::model::rules::validate_table_name;
+use crate::model::rules::{find_undefined_columns, validate_table_name, validate_time_range};
 use crate::OtsRequestOptions;
 use crate::{
     add_per_request_options,
@@ -32,6 +32,10 @@ pub struct GetRowRequest {
     pub max_versions: Option<i32>,
     pub start_column: Option<String>,
     pub end_column: Option<String>,
+
+    /// 宽行读取时上一次响应中的 `next_token`，用来从上一次结束的位置继续读取
+    pub token: Option<Vec<u8>>,
+
     pub transaction_id: Option<String>,
 }
 
@@ -130,7 +134,21 @@ impl GetRowRequest {
         self
     }
 
-    /// 指定精确的时间戳
+    /// 设置时间范围的起始时间戳（包含），单位是毫秒
+    pub fn time_range_start_ms(mut self, start_ms: i64) -> Self {
+        self.time_range_start_ms = Some(start_ms);
+
+        self
+    }
+
+    /// 设置时间范围的结束时间戳（不包含），单位是毫秒
+    pub fn time_range_end_ms(mut self, end_ms: i64) -> Self {
+        self.time_range_end_ms = Some(end_ms);
+
+        self
+    }
+
+    /// 指定精确的时间戳。不能和时间范围同时设置
     pub fn specific_time_ms(mut self, time_ms: i64) -> Self {
         self.time_range_specific_ms = Some(time_ms);
 
@@ -161,6 +179,33 @@ impl GetRowRequest {
         self
     }
 
+    /// 宽行读取时，设置为上一次响应中的 `next_token`，从上一次结束的位置继续读取这一行剩余的列。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut request = GetRowRequest::new("wide_table").primary_key_column_string("id", "row-1");
+    /// let mut columns = vec![];
+    ///
+    /// loop {
+    ///     let resp = client.get_row(request.clone()).send().await?;
+    ///
+    ///     if let Some(row) = resp.row {
+    ///         columns.extend(row.columns);
+    ///     }
+    ///
+    ///     match resp.next_token {
+    ///         Some(token) => request = request.token(token),
+    ///         None => break,
+    ///     }
+    /// }
+    /// ```
+    pub fn token(mut self, token: impl Into<Vec<u8>>) -> Self {
+        self.token = Some(token.into());
+
+        self
+    }
+
     /// 局部事务ID。当使用局部事务功能读取数据时必须设置此参数。
     pub fn transaction_id(mut self, tx_id: impl Into<String>) -> Self {
         self.transaction_id = Some(tx_id.into());
@@ -169,7 +214,7 @@ impl GetRowRequest {
     }
 
     /// Validate request parameter
-    fn validate(&self) -> OtsResult<()> {
+    pub(crate) fn validate(&self) -> OtsResult<()> {
         if !validate_table_name(&self.table_name) {
             return Err(OtsError::ValidationFailed(format!("Invalid table name: {}", self.table_name)));
         }
@@ -178,6 +223,8 @@ impl GetRowRequest {
             return Err(OtsError::ValidationFailed("The row's primary key can not be empty".to_string()));
         }
 
+        validate_time_range(self.time_range_start_ms, self.time_range_end_ms, self.time_range_specific_ms)?;
+
         Ok(())
     }
 }
@@ -194,6 +241,7 @@ impl From<GetRowRequest> for crate::protos::GetRowRequest {
             max_versions,
             start_column,
             end_column,
+            token,
             transaction_id,
         } = value;
 
@@ -223,7 +271,7 @@ impl From<GetRowRequest> for crate::protos::GetRowRequest {
             filter: None,
             start_column,
             end_column,
-            token: None,
+            token,
             transaction_id,
         }
     }
@@ -234,6 +282,8 @@ impl From<GetRowRequest> for crate::protos::GetRowRequest {
 pub struct GetRowResponse {
     pub consumed: ConsumedCapacity,
     pub row: Option<Row>,
+
+    /// 宽行读取时，这一行还有没有返回的列。使用 [`GetRowRequest::token`] 继续读取
     pub next_token: Option<Vec<u8>>,
 }
 
@@ -241,6 +291,13 @@ impl TryFrom<crate::protos::GetRowResponse> for GetRowResponse {
     type Error = OtsError;
 
     fn try_from(value: crate::protos::GetRowResponse) -> Result<Self, Self::Error> {
+        Self::try_from_checked(value, false)
+    }
+}
+
+impl GetRowResponse {
+    /// 转换响应。`verify_checksum` 为 `true` 的时候在解码行的同时校验行校验码
+    pub(crate) fn try_from_checked(value: crate::protos::GetRowResponse, verify_checksum: bool) -> OtsResult<Self> {
         let crate::protos::GetRowResponse {
             consumed,
             row: row_bytes,
@@ -248,7 +305,7 @@ impl TryFrom<crate::protos::GetRowResponse> for GetRowResponse {
         } = value;
 
         let row = if !row_bytes.is_empty() {
-            Some(Row::decode_plain_buffer(row_bytes, MASK_HEADER)?)
+            Some(Row::decode_plain_buffer_checked(row_bytes, MASK_HEADER, verify_checksum)?)
         } else {
             None
         };
@@ -282,7 +339,19 @@ impl GetRowOperation {
     pub async fn send(self) -> OtsResult<GetRowResponse> {
         self.request.validate()?;
 
-        let Self { client, request, options } = self;
+        let Self { client, mut request, options } = self;
+
+        client.prepare_primary_keys(&request.table_name, [&mut request.primary_key]).await?;
+
+        if let Some(table_meta) = client.cached_table_meta(&request.table_name).await {
+            for name in find_undefined_columns(&table_meta, &request.columns_to_get) {
+                log::warn!(
+                    "column {} in columns_to_get is neither primary key nor defined column of table {}",
+                    name,
+                    request.table_name
+                );
+            }
+        }
 
         let msg: crate::protos::GetRowRequest = request.into();
 
@@ -296,6 +365,6 @@ impl GetRowOperation {
         let response = client.send(req).await?;
         let response_msg = crate::protos::GetRowResponse::decode(response.bytes().await?)?;
 
-        response_msg.try_into()
+        GetRowResponse::try_from_checked(response_msg, client.options.verify_checksums)
     }
 }
@@ -3,39 +3,53 @@ mod batch_get_row;
 mod batch_write_row;
 mod bulk_export;
 mod bulk_import;
+mod delete_range;
 mod delete_row;
 mod get_range;
 mod get_row;
 mod put_row;
+pub(crate) mod row_stream;
+mod scan_table;
 mod update_row;
 
 pub use batch_get_row::*;
 pub use batch_write_row::*;
 pub use bulk_export::*;
 pub use bulk_import::*;
+pub use delete_range::*;
 pub use delete_row::*;
 pub use get_range::*;
 pub use get_row::*;
 pub use put_row::*;
+pub(crate) use scan_table::scan_table;
 pub use update_row::*;
 
 #[cfg(test)]
 mod test_row_operations {
 
+    use futures_core::Stream;
+
     use fake::{faker::name::raw::Name, locales::ZH_CN, uuid::UUIDv4, Fake};
 
     use crate::{
-        data::{DeleteRowRequest, GetRowRequest, PutRowRequest, UpdateRowRequest},
-        error::OtsError,
-        model::{Column, ColumnValue, CompositeColumnValueFilter, Filter, PrimaryKey, PrimaryKeyValue, Row, SingleColumnValueFilter},
-        protos::{filter::LogicalOperator, Direction, ReturnType},
+        data::{DeleteRowRequest, GetRowRequest, PutRowRequest, PutRowResponse, UpdateRowRequest, UpdateRowResponse},
+        error::{ApiErrorCode, OtsError},
+        model::{Column, ColumnValue, CompositeColumnValueFilter, Filter, PrimaryKey, PrimaryKeyColumn, PrimaryKeyValue, Row, SingleColumnValueFilter},
+        protos::{
+            filter::LogicalOperator,
+            plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM},
+            Direction, ReturnType, RowExistenceExpectation,
+        },
         test_util::setup,
-        OtsClient,
+        OtsClient, OtsResult,
     };
 
+    use super::batch_write_row::{merge_batch_write_row_response, merge_chunk_responses};
+    use super::row_stream::{concurrent_row_stream, PagedRowOperation, RowStream};
     use super::{
-        BatchGetRowRequest, BatchWriteRowRequest, BulkExportRequest, BulkImportRequest, GetRangeRequest, RowInBatchWriteRowRequest, TableInBatchGetRowRequest,
-        TableInBatchWriteRowRequest,
+        BatchGetRowRequest, BatchWriteRowRequest, BatchWriteRowResponse, BulkExportRequest, BulkImportAllResponse, BulkImportRequest, GetRangeRequest,
+        GetRangeResponse, RowInBatchWriteRowRequest, RowInBatchWriteRowResponse, RowInBulkImportRequest, TableInBatchGetRowRequest,
+        TableInBatchWriteRowRequest, TableInBatchWriteRowResponse,
     };
 
     async fn test_get_row_impl() {
@@ -70,6 +84,267 @@ mod test_row_operations {
         test_get_row_impl().await;
     }
 
+    #[test]
+    fn test_get_row_request_columns_to_get() {
+        let req = GetRowRequest::new("data_types").primary_key_column_string("str_id", "1");
+        let msg: crate::protos::GetRowRequest = req.clone().into();
+        assert!(msg.columns_to_get.is_empty());
+
+        let msg: crate::protos::GetRowRequest = req.columns_to_get(["str_col"]).into();
+        assert_eq!(vec!["str_col".to_string()], msg.columns_to_get);
+    }
+
+    async fn test_get_row_columns_to_get_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let id: String = UUIDv4.fake();
+
+        client
+            .put_row(
+                PutRowRequest::new("data_types").row(
+                    Row::new()
+                        .primary_key_column_string("str_id", &id)
+                        .column_string("str_col", "only me")
+                        .column_integer("int_col", 1)
+                        .column_bool("bool_col", true),
+                ),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        let row = client
+            .get_row(
+                GetRowRequest::new("data_types")
+                    .primary_key_column_string("str_id", &id)
+                    .columns_to_get(["str_col"]),
+            )
+            .send()
+            .await
+            .unwrap()
+            .row
+            .unwrap();
+
+        assert_eq!(Some(&ColumnValue::String("only me".to_string())), row.get_column_value("str_col"));
+        assert!(row.get_column_value("int_col").is_none());
+        assert!(row.get_column_value("bool_col").is_none());
+
+        client
+            .delete_row(DeleteRowRequest::new("data_types").primary_key_column_string("str_id", &id))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_row_columns_to_get() {
+        test_get_row_columns_to_get_impl().await;
+    }
+
+    #[test]
+    fn test_get_row_request_time_range() {
+        let req = GetRowRequest::new("data_types").primary_key_column_string("str_id", "1");
+
+        assert!(req.clone().time_range_start_ms(1000).time_range_end_ms(2000).validate().is_ok());
+        assert!(req.clone().time_range_start_ms(1000).validate().is_ok());
+        assert!(req.clone().specific_time_ms(1000).validate().is_ok());
+        assert!(req.clone().time_range(2000, 2000).validate().is_err());
+        assert!(req.clone().time_range(3000, 2000).validate().is_err());
+        assert!(req.clone().time_range_end_ms(2000).specific_time_ms(1000).validate().is_err());
+
+        let msg: crate::protos::GetRowRequest = req.time_range_start_ms(1000).time_range_end_ms(2000).into();
+        let time_range = msg.time_range.unwrap();
+        assert_eq!(Some(1000), time_range.start_time);
+        assert_eq!(Some(2000), time_range.end_time);
+        assert_eq!(None, time_range.specific_time);
+        assert_eq!(None, msg.max_versions);
+    }
+
+    #[test]
+    fn test_get_range_request_time_range() {
+        let req = GetRangeRequest::new("data_types")
+            .start_primary_key_column_inf_min("str_id")
+            .end_primary_key_column_inf_max("str_id");
+
+        assert!(req.clone().time_range_start_ms(1000).time_range_end_ms(2000).validate().is_ok());
+        assert!(req.clone().time_range_end_ms(2000).validate().is_ok());
+        assert!(req.clone().time_range_start_ms(2000).time_range_end_ms(2000).validate().is_err());
+        assert!(req.clone().time_range_start_ms(1000).specific_time_ms(1500).validate().is_err());
+
+        let msg: crate::protos::GetRangeRequest = req.time_range_start_ms(1000).time_range_end_ms(2000).into();
+        let time_range = msg.time_range.unwrap();
+        assert_eq!(Some(1000), time_range.start_time);
+        assert_eq!(Some(2000), time_range.end_time);
+        assert_eq!(None, msg.max_versions);
+    }
+
+    #[test]
+    fn test_get_row_request_token() {
+        let msg: crate::protos::GetRowRequest = GetRowRequest::new("data_types")
+            .primary_key_column_string("str_id", "1")
+            .start_column("a")
+            .end_column("z")
+            .token(vec![1u8, 2, 3])
+            .into();
+
+        assert_eq!(Some("a".to_string()), msg.start_column);
+        assert_eq!(Some("z".to_string()), msg.end_column);
+        assert_eq!(Some(vec![1u8, 2, 3]), msg.token);
+    }
+
+    async fn test_get_row_column_pages_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let id: String = UUIDv4.fake();
+        let mut row = Row::new().primary_key_column_string("str_id", &id);
+        for i in 0..200 {
+            row = row.column_integer(&format!("wide_{:03}", i), i);
+        }
+
+        client.put_row(PutRowRequest::new("data_types").row(row)).send().await.unwrap();
+
+        // 按照列名分两页读取，第一页不包含结束列，第二页从这一列开始
+        let request = GetRowRequest::new("data_types").primary_key_column_string("str_id", &id);
+        let mut names = vec![];
+
+        for page in [request.clone().end_column("wide_100"), request.start_column("wide_100")] {
+            let mut page = page;
+
+            loop {
+                let resp = client.get_row(page.clone()).send().await.unwrap();
+                names.extend(resp.row.unwrap().columns.into_iter().map(|c| c.name));
+
+                match resp.next_token {
+                    Some(token) => page = page.token(token),
+                    None => break,
+                }
+            }
+        }
+
+        let expected = (0..200).map(|i| format!("wide_{:03}", i)).collect::<Vec<_>>();
+        assert_eq!(expected, names);
+
+        client
+            .delete_row(DeleteRowRequest::new("data_types").primary_key_column_string("str_id", &id))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_row_column_pages() {
+        test_get_row_column_pages_impl().await;
+    }
+
+    /// 写入一行，`str_col` 列有 3 个版本，版本号分别是 3 小时、2 小时和 1 小时之前。
+    /// 版本号要在表的数据生命周期之内，否则写入之后读取不到。返回版本号，从旧到新排列
+    async fn put_row_with_versions(client: &OtsClient, id: &str) -> [u64; 3] {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let hour = 3600 * 1000;
+        let timestamps = [now - 3 * hour, now - 2 * hour, now - hour];
+
+        let mut row = Row::new().primary_key_column_string("str_id", id);
+
+        for ts in timestamps {
+            row = row.column(Column {
+                timestamp: Some(ts),
+                ..Column::from_string("str_col", format!("version {}", ts))
+            });
+        }
+
+        client.put_row(PutRowRequest::new("data_types").row(row)).send().await.unwrap();
+
+        timestamps
+    }
+
+    async fn test_get_row_time_range_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let id: String = UUIDv4.fake();
+        let timestamps = put_row_with_versions(&client, &id).await;
+
+        // 只包含中间一个版本
+        let row = client
+            .get_row(
+                GetRowRequest::new("data_types")
+                    .primary_key_column_string("str_id", &id)
+                    .column_to_get("str_col")
+                    .time_range_start_ms(timestamps[1] as i64 - 1000)
+                    .time_range_end_ms(timestamps[1] as i64 + 1000),
+            )
+            .send()
+            .await
+            .unwrap()
+            .row
+            .unwrap();
+
+        assert_eq!(1, row.columns.len());
+        assert_eq!(Some(timestamps[1]), row.columns[0].timestamp);
+        assert_eq!(ColumnValue::String(format!("version {}", timestamps[1])), row.columns[0].value);
+
+        client
+            .delete_row(DeleteRowRequest::new("data_types").primary_key_column_string("str_id", &id))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_row_time_range() {
+        test_get_row_time_range_impl().await;
+    }
+
+    async fn test_update_row_delete_version_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let id: String = UUIDv4.fake();
+        let timestamps = put_row_with_versions(&client, &id).await;
+
+        client
+            .update_row(
+                UpdateRowRequest::new("data_types").row(
+                    Row::new()
+                        .primary_key_column_string("str_id", &id)
+                        .column_to_delete_version("str_col", timestamps[1]),
+                ),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        let row = client
+            .get_row(
+                GetRowRequest::new("data_types")
+                    .primary_key_column_string("str_id", &id)
+                    .column_to_get("str_col")
+                    .max_versions(3),
+            )
+            .send()
+            .await
+            .unwrap()
+            .row
+            .unwrap();
+
+        let mut remaining = row.columns.iter().filter_map(|c| c.timestamp).collect::<Vec<_>>();
+        remaining.sort();
+        assert_eq!(vec![timestamps[0], timestamps[2]], remaining);
+
+        client
+            .delete_row(DeleteRowRequest::new("data_types").primary_key_column_string("str_id", &id))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_row_delete_version() {
+        test_update_row_delete_version_impl().await;
+    }
+
     async fn test_get_range_with_single_filter_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -134,6 +409,172 @@ mod test_row_operations {
         test_get_range_with_single_filter_impl().await;
     }
 
+    async fn test_get_range_row_stream_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let request = GetRangeRequest::new("data_types")
+            .start_primary_key_column_inf_min("str_id")
+            .end_primary_key_column_inf_max("str_id")
+            .limit(100);
+
+        let mut manual_rows = 0;
+        let mut req = request.clone();
+
+        loop {
+            let resp = client.get_range(req.clone()).send().await.unwrap();
+            manual_rows += resp.rows.len();
+
+            match resp.next_start_primary_key {
+                Some(pk) => req = req.start_primary_key_columns(pk),
+                None => break,
+            }
+        }
+
+        let stream = client.get_range(request).into_row_stream();
+        tokio::pin!(stream);
+
+        let mut stream_rows = 0;
+
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            assert!(row.is_ok());
+            stream_rows += 1;
+        }
+
+        log::debug!("total read rows: {}", stream_rows);
+        assert_eq!(manual_rows, stream_rows);
+    }
+
+    #[tokio::test]
+    async fn test_get_range_row_stream() {
+        test_get_range_row_stream_impl().await;
+    }
+
+    async fn test_get_range_accept_compression_impl() {
+        setup();
+        let client = OtsClient::from_env();
+        let mut compressed_client = OtsClient::from_env();
+        compressed_client.options.accept_compression = true;
+
+        let request = GetRangeRequest::new("data_types")
+            .start_primary_key_column_inf_min("str_id")
+            .end_primary_key_column_inf_max("str_id")
+            .limit(5000);
+
+        let resp = client.get_range(request.clone()).send().await.unwrap();
+        let compressed_resp = compressed_client.get_range(request).send().await;
+        assert!(compressed_resp.is_ok());
+
+        let compressed_resp = compressed_resp.unwrap();
+        assert_eq!(resp.rows.len(), compressed_resp.rows.len());
+        assert!(resp.rows.iter().zip(&compressed_resp.rows).all(|(a, b)| a.primary_key == b.primary_key));
+    }
+
+    #[tokio::test]
+    async fn test_get_range_accept_compression() {
+        test_get_range_accept_compression_impl().await;
+    }
+
+    async fn test_get_range_time_range_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let id: String = UUIDv4.fake();
+        let now = chrono::Utc::now().timestamp_millis();
+        let hour_ago = now - 3600 * 1000;
+
+        let row = Row::new()
+            .primary_key_column_string("str_id", &id)
+            .column(Column {
+                timestamp: Some((now - 2 * 3600 * 1000) as u64),
+                ..Column::from_string("str_col", "old")
+            })
+            .column(Column {
+                timestamp: Some((now - 600 * 1000) as u64),
+                ..Column::from_string("str_col", "recent")
+            });
+
+        client.put_row(PutRowRequest::new("data_types").row(row)).send().await.unwrap();
+
+        let resp = client
+            .get_range(
+                GetRangeRequest::new("data_types")
+                    .start_primary_key_column_string("str_id", &id)
+                    .end_primary_key_column_string("str_id", format!("{}~", id))
+                    .column_to_get("str_col")
+                    .time_range_start_ms(hour_ago)
+                    .time_range_end_ms(now + 1),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(1, resp.rows.len());
+        let columns = &resp.rows[0].columns;
+        assert_eq!(1, columns.len());
+        assert_eq!(ColumnValue::String("recent".to_string()), columns[0].value);
+        assert!(columns.iter().all(|c| c.timestamp.is_some_and(|ts| ts as i64 >= hour_ago)));
+
+        client
+            .delete_row(DeleteRowRequest::new("data_types").primary_key_column_string("str_id", &id))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_range_time_range() {
+        test_get_range_time_range_impl().await;
+    }
+
+    async fn test_scan_table_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let stream = client
+            .get_range(
+                GetRangeRequest::new("data_types")
+                    .start_primary_key_column_inf_min("str_id")
+                    .end_primary_key_column_inf_max("str_id"),
+            )
+            .into_row_stream();
+        tokio::pin!(stream);
+
+        let mut range_rows = 0;
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            assert!(row.is_ok());
+            range_rows += 1;
+        }
+
+        let stream = client.scan_table("data_types", 4);
+        tokio::pin!(stream);
+
+        let mut scan_rows = 0;
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            assert!(row.is_ok());
+            scan_rows += 1;
+        }
+
+        log::debug!("total scanned rows: {}", scan_rows);
+        assert_eq!(range_rows, scan_rows);
+    }
+
+    #[tokio::test]
+    async fn test_scan_table() {
+        test_scan_table_impl().await;
+    }
+
+    #[tokio::test]
+    async fn test_scan_table_invalid_concurrency() {
+        let client = OtsClient::new("aid", "asec", "https://instance.cn-hangzhou.ots.aliyuncs.com");
+        let stream = client.scan_table("data_types", 0);
+        tokio::pin!(stream);
+
+        let item = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+        assert!(matches!(item, Some(Err(OtsError::ValidationFailed(_)))));
+        assert!(std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await.is_none());
+    }
+
     async fn test_put_row_impl() {
         setup();
 
@@ -157,6 +598,11 @@ mod test_row_operations {
         log::debug!("{:#?}", response);
 
         assert!(response.row.is_some());
+        assert!(response.consumed.capacity_unit.write.unwrap_or_default() > 0);
+
+        let id = response.auto_increment_value("id");
+        assert!(id.is_some_and(|id| id > 0));
+        assert_eq!(None, response.auto_increment_value("school_id"));
 
         let row = response.row;
         assert!(row.is_some());
@@ -170,6 +616,92 @@ mod test_row_operations {
         test_put_row_impl().await;
     }
 
+    async fn test_put_row_expect_not_exist_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let id: String = UUIDv4.fake();
+
+        let req = PutRowRequest::new("data_types")
+            .row(Row::new().primary_key_column_string("str_id", &id).column_string("str_col", "first"))
+            .row_condition(RowExistenceExpectation::ExpectNotExist);
+
+        let msg: crate::protos::PutRowRequest = req.clone().into();
+        assert_eq!(RowExistenceExpectation::ExpectNotExist as i32, msg.condition.row_existence);
+
+        let resp = client.put_row(req.clone()).send().await.unwrap();
+        assert!(resp.consumed.capacity_unit.write.unwrap_or_default() > 0);
+
+        let res = client.put_row(req).send().await;
+        log::debug!("{:?}", res);
+
+        match res {
+            Err(e @ OtsError::ApiError(_, _)) => assert_eq!(Some(ApiErrorCode::ConditionCheckFail), e.code()),
+            _ => panic!("expect condition check failed error, got: {:?}", res),
+        }
+
+        client
+            .delete_row(DeleteRowRequest::new("data_types").primary_key_column_string("str_id", &id))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_put_row_expect_not_exist() {
+        test_put_row_expect_not_exist_impl().await;
+    }
+
+    #[cfg(feature = "serde")]
+    async fn test_put_row_from_serialize_impl() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct DataTypes {
+            str_id: String,
+            str_col: String,
+            int_col: i64,
+            double_col: f64,
+            bool_col: bool,
+            blob_col: Vec<u8>,
+        }
+
+        setup();
+        let client = OtsClient::from_env();
+
+        let value = DataTypes {
+            str_id: UUIDv4.fake(),
+            str_col: "serde".to_string(),
+            int_col: 42,
+            double_col: 3.25,
+            bool_col: true,
+            blob_col: vec![1, 2, 3],
+        };
+
+        let row = Row::from_serialize(&value, &["str_id"]).unwrap();
+        client.put_row(PutRowRequest::new("data_types").row(row)).send().await.unwrap();
+
+        let row = client
+            .get_row(GetRowRequest::new("data_types").primary_key_column_string("str_id", &value.str_id))
+            .send()
+            .await
+            .unwrap()
+            .row
+            .unwrap();
+
+        assert_eq!(value, row.deserialize::<DataTypes>().unwrap());
+
+        client
+            .delete_row(DeleteRowRequest::new("data_types").primary_key_column_string("str_id", &value.str_id))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn test_put_row_from_serialize() {
+        test_put_row_from_serialize_impl().await;
+    }
+
     async fn test_update_row_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -241,6 +773,55 @@ mod test_row_operations {
         test_update_row_impl().await;
     }
 
+    async fn test_update_row_after_modify_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let table_name = "data_types";
+        let id: String = UUIDv4.fake();
+
+        client
+            .put_row(
+                PutRowRequest::new(table_name).row(
+                    Row::new()
+                        .primary_key_column_string("str_id", &id)
+                        .column_string("str_col", "a")
+                        .column_integer("int_col", 10),
+                ),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        let response = client
+            .update_row(
+                UpdateRowRequest::new(table_name)
+                    .row(
+                        Row::new()
+                            .primary_key_column_string("str_id", &id)
+                            .column_to_increse("int_col", 5)
+                            .column_string("str_col", "b"),
+                    )
+                    .return_type(ReturnType::RtAfterModify)
+                    .return_columns(["int_col", "str_col"]),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        log::debug!("update row response: {:#?}", response);
+
+        let row = response.row.unwrap();
+        assert_eq!(Some(&PrimaryKeyValue::String(id)), row.get_primary_key_value("str_id"));
+        assert_eq!(Some(&ColumnValue::Integer(15)), row.get_column_value("int_col"));
+        assert_eq!(Some(&ColumnValue::String("b".to_string())), row.get_column_value("str_col"));
+    }
+
+    #[tokio::test]
+    async fn test_update_row_after_modify() {
+        test_update_row_after_modify_impl().await;
+    }
+
     async fn test_delete_row_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -322,6 +903,36 @@ mod test_row_operations {
         test_batch_get_row_impl().await;
     }
 
+    /// 一行有效、一行主键类型错误，只有错误的行有错误信息
+    async fn test_batch_get_row_row_error_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let t = TableInBatchGetRowRequest::new("data_types")
+            .primary_key(PrimaryKey::new().column_string("str_id", "1"))
+            .primary_key(PrimaryKey::new().column_integer("str_id", 1));
+
+        let resp = client.batch_get_row(BatchGetRowRequest::new().table(t)).send().await;
+        log::debug!("batch get row response: {:#?}", resp);
+
+        let resp = resp.unwrap();
+        let rows = &resp.tables[0].rows;
+        assert_eq!(2, rows.len());
+
+        assert!(rows[0].is_ok);
+        assert!(rows[0].error.is_none());
+
+        assert!(!rows[1].is_ok);
+        let error = rows[1].error.as_ref().unwrap();
+        assert!(!error.code.is_empty());
+        log::debug!("row error: {} {:?}", error.code, error.message);
+    }
+
+    #[tokio::test]
+    async fn test_batch_get_row_row_error() {
+        test_batch_get_row_row_error_impl().await;
+    }
+
     async fn test_batch_write_row_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -409,14 +1020,8 @@ mod test_row_operations {
 
         assert!(resp.is_err());
 
-        if let Err(OtsError::ApiError(apie)) = resp {
-            let crate::protos::Error {
-                code,
-                message: _,
-                access_denied_detail: _,
-            } = *apie;
-
-            assert_eq!("OTSConditionCheckFail", code);
+        if let Err(e @ OtsError::ApiError(_, _)) = resp {
+            assert_eq!(Some(ApiErrorCode::ConditionCheckFail), e.code());
         } else {
             panic!("the update operation should be failed with api error code: OTSConditionCheckFail")
         }
@@ -462,6 +1067,471 @@ mod test_row_operations {
         test_bulk_import_impl().await
     }
 
+    async fn test_bulk_import_chunked_impl() {
+        setup();
+        let client = OtsClient::from_env();
+        let mut req = BulkImportRequest::new("data_types");
+        for i in 0..450 {
+            let id: String = UUIDv4.fake();
+            let row = Row::new().primary_key_column_string("str_id", &id).column_integer("int_col", i);
+
+            req = req.put_row(row);
+        }
+
+        let mut progress = vec![];
+        let resp = client
+            .bulk_import(req)
+            .send_chunked(|rows_done, rows_total, result| {
+                assert!(result.is_ok());
+                progress.push((rows_done, rows_total));
+            })
+            .await;
+
+        assert!(resp.is_ok());
+        assert_eq!(3, resp.unwrap().len());
+        assert_eq!(vec![(200, 450), (400, 450), (450, 450)], progress);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_chunked() {
+        test_bulk_import_chunked_impl().await
+    }
+
+    #[test]
+    fn test_bulk_import_into_batches() {
+        let req = BulkImportRequest::new("data_types").rows((0..450).map(|i| RowInBulkImportRequest::put_row(Row::new().primary_key_column_integer("id", i))));
+
+        let batches = req.clone().into_batches(200);
+        assert_eq!(vec![200, 200, 50], batches.iter().map(|b| b.rows.len()).collect::<Vec<_>>());
+        assert!(batches.iter().all(|b| b.table_name == "data_types"));
+        assert_eq!(Some(&PrimaryKeyValue::Integer(200)), batches[1].rows[0].row.get_primary_key_value("id"));
+
+        assert_eq!(5, req.clone().into_batches(100).len());
+        assert_eq!(3, req.into_batches(0).len());
+        assert!(BulkImportRequest::new("data_types").into_batches(200).is_empty());
+    }
+
+    async fn test_bulk_import_all_impl() {
+        setup();
+        let client = OtsClient::from_env();
+        let mut req = BulkImportRequest::new("data_types");
+        for i in 0..1000 {
+            let id: String = UUIDv4.fake();
+            req = req.put_row(Row::new().primary_key_column_string("str_id", &id).column_integer("int_col", i));
+        }
+
+        let resp = client.bulk_import_all(req, 4).await;
+        log::debug!("{:?}", resp);
+        assert!(resp.is_ok());
+
+        let resp = resp.unwrap();
+        assert_eq!(1000, resp.rows_imported);
+        assert!(resp.failed_batches.is_empty());
+        assert!(resp.failed_rows.is_empty());
+    }
+
+    #[test]
+    fn test_bulk_import_all_response_failed_rows() {
+        let batch = BulkImportRequest::new("data_types").rows((0..3).map(|i| RowInBulkImportRequest::put_row(Row::new().primary_key_column_integer("id", i))));
+
+        let row_ok = crate::protos::RowInBulkImportResponse {
+            is_ok: true,
+            error: None,
+            consumed: None,
+        };
+
+        let row_failed = crate::protos::RowInBulkImportResponse {
+            is_ok: false,
+            error: Some(crate::protos::Error {
+                code: "OTSConditionCheckFail".to_string(),
+                message: Some("Condition check failed.".to_string()),
+                access_denied_detail: None,
+            }),
+            consumed: None,
+        };
+
+        let response = crate::protos::BulkImportResponse {
+            table_name: "data_types".to_string(),
+            rows: vec![row_ok.clone(), row_failed, row_ok],
+        };
+
+        let mut result = BulkImportAllResponse::default();
+        result.add_batch_response(200, batch, response);
+
+        assert_eq!(2, result.rows_imported);
+        assert_eq!(1, result.failed_rows.len());
+
+        let (i, row, e) = &result.failed_rows[0];
+        assert_eq!(201, *i);
+        assert_eq!(Some(&PrimaryKeyValue::Integer(1)), row.row.get_primary_key_value("id"));
+        assert_eq!(Some(ApiErrorCode::ConditionCheckFail), e.code());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_all() {
+        test_bulk_import_all_impl().await
+    }
+
+    /// 按顺序返回预先设置好的每一页数据
+    struct FakePages(Vec<OtsResult<Vec<Row>>>);
+
+    impl PagedRowOperation for FakePages {
+        async fn next_page(mut self) -> OtsResult<(Vec<Row>, Option<Self>)> {
+            let rows = self.0.remove(0)?;
+            let next = if self.0.is_empty() { None } else { Some(self) };
+
+            Ok((rows, next))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_row_stream_pages() {
+        let page = |ids: &[i64]| Ok(ids.iter().map(|id| Row::new().primary_key_column_integer("id", *id)).collect::<Vec<_>>());
+
+        let stream = RowStream::new(FakePages(vec![page(&[1, 2]), page(&[]), page(&[3])]));
+        tokio::pin!(stream);
+
+        let mut ids = vec![];
+
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            ids.push(row.unwrap().get_primary_key_value("id").cloned());
+        }
+
+        assert_eq!(
+            vec![
+                Some(PrimaryKeyValue::Integer(1)),
+                Some(PrimaryKeyValue::Integer(2)),
+                Some(PrimaryKeyValue::Integer(3))
+            ],
+            ids
+        );
+
+        // 出错之后返回错误，然后结束
+        let stream = RowStream::new(FakePages(vec![page(&[1]), Err(OtsError::ValidationFailed("test".to_string())), page(&[2])]));
+        tokio::pin!(stream);
+
+        assert!(std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await.unwrap().is_ok());
+        assert!(std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await.unwrap().is_err());
+        assert!(std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_row_stream() {
+        let page = |ids: &[i64]| Ok(ids.iter().map(|id| Row::new().primary_key_column_integer("id", *id)).collect::<Vec<_>>());
+
+        let splits = vec![
+            FakePages(vec![page(&[1, 2]), page(&[3])]),
+            FakePages(vec![page(&[4])]),
+            FakePages(vec![page(&[5, 6])]),
+        ];
+        let stream = concurrent_row_stream(2, async move { Ok(splits) });
+        tokio::pin!(stream);
+
+        let mut ids = vec![];
+
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            if let Some(PrimaryKeyValue::Integer(id)) = row.unwrap().get_primary_key_value("id") {
+                ids.push(*id);
+            }
+        }
+
+        ids.sort();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], ids);
+
+        // 获取分片失败的时候只返回这个错误
+        let stream = concurrent_row_stream::<FakePages, _>(2, async { Err(OtsError::ValidationFailed("test".to_string())) });
+        tokio::pin!(stream);
+
+        assert!(std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await.unwrap().is_err());
+        assert!(std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await.is_none());
+    }
+
+    /// 读取第一页的时候 panic
+    struct PanicPages;
+
+    impl PagedRowOperation for PanicPages {
+        async fn next_page(self) -> OtsResult<(Vec<Row>, Option<Self>)> {
+            panic!("read split panicked")
+        }
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "read split panicked")]
+    async fn test_concurrent_row_stream_panic() {
+        let stream = concurrent_row_stream(2, async { Ok(vec![PanicPages]) });
+        tokio::pin!(stream);
+
+        while std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await.is_some() {}
+    }
+
+    #[test]
+    fn test_batch_write_row_failed_rows() {
+        let resp = BatchWriteRowResponse {
+            tables: vec![TableInBatchWriteRowResponse {
+                table_name: "data_types".to_string(),
+                rows: vec![
+                    RowInBatchWriteRowResponse {
+                        is_ok: true,
+                        ..Default::default()
+                    },
+                    RowInBatchWriteRowResponse {
+                        is_ok: false,
+                        error: Some(crate::protos::Error {
+                            code: "OTSConditionCheckFail".to_string(),
+                            message: Some("Condition check failed.".to_string()),
+                            access_denied_detail: None,
+                        }),
+                        ..Default::default()
+                    },
+                    RowInBatchWriteRowResponse {
+                        is_ok: false,
+                        error: Some(crate::protos::Error {
+                            code: "OTSParameterInvalid".to_string(),
+                            message: None,
+                            access_denied_detail: None,
+                        }),
+                        ..Default::default()
+                    },
+                ],
+            }],
+        };
+
+        let failed = resp.failed_rows();
+        assert_eq!(2, failed.len());
+        assert_eq!(("data_types", 1), (failed[0].0, failed[0].1));
+        assert!(failed[0].2.is_condition_check_failed());
+        assert_eq!(2, failed[1].1);
+        assert!(!failed[1].2.is_condition_check_failed());
+        assert!(!resp.tables[0].rows[0].is_condition_check_failed());
+    }
+
+    #[test]
+    fn test_merge_batch_write_row_response() {
+        let error = |code: &str| crate::protos::Error {
+            code: code.to_string(),
+            message: None,
+            access_denied_detail: None,
+        };
+        let ok = || RowInBatchWriteRowResponse {
+            is_ok: true,
+            ..Default::default()
+        };
+        let failed = |code: &str| RowInBatchWriteRowResponse {
+            is_ok: false,
+            error: Some(error(code)),
+            ..Default::default()
+        };
+        let rows = |n: i64| (0..n).map(|i| RowInBatchWriteRowRequest::put_row(Row::new().primary_key_column_integer("id", i)));
+
+        let request = BatchWriteRowRequest::new()
+            .table(TableInBatchWriteRowRequest::new("t1").rows(rows(2)))
+            .table(TableInBatchWriteRowRequest::new("t2").rows(rows(3)));
+
+        let mut merged = BatchWriteRowResponse {
+            tables: request
+                .tables
+                .iter()
+                .map(|t| TableInBatchWriteRowResponse {
+                    table_name: t.table_name.clone(),
+                    rows: vec![RowInBatchWriteRowResponse::default(); t.rows.len()],
+                })
+                .collect(),
+        };
+        let positions = vec![(0, vec![0, 1]), (1, vec![0, 1, 2])];
+
+        // t2 的第 2 行冲突，第 3 行条件检查失败
+        let response = BatchWriteRowResponse {
+            tables: vec![
+                TableInBatchWriteRowResponse {
+                    table_name: "t1".to_string(),
+                    rows: vec![ok(), ok()],
+                },
+                TableInBatchWriteRowResponse {
+                    table_name: "t2".to_string(),
+                    rows: vec![ok(), failed("OTSRowOperationConflict"), failed("OTSConditionCheckFail")],
+                },
+            ],
+        };
+
+        let (retry_request, retry_positions) =
+            merge_batch_write_row_response(&mut merged, &positions, &request, response, RowInBatchWriteRowResponse::is_retriable);
+        assert_eq!(vec![(1, vec![1])], retry_positions);
+        assert_eq!(1, retry_request.tables.len());
+        assert_eq!("t2", retry_request.tables[0].table_name);
+        assert_eq!(
+            Some(&PrimaryKeyValue::Integer(1)),
+            retry_request.tables[0].rows[0].row.get_primary_key_value("id")
+        );
+        assert_eq!(2, merged.failed_rows().len());
+
+        // 重试成功之后只剩下条件检查失败的行
+        let response = BatchWriteRowResponse {
+            tables: vec![TableInBatchWriteRowResponse {
+                table_name: "t2".to_string(),
+                rows: vec![ok()],
+            }],
+        };
+
+        let (retry_request, retry_positions) = merge_batch_write_row_response(
+            &mut merged,
+            &retry_positions,
+            &retry_request,
+            response,
+            RowInBatchWriteRowResponse::is_retriable,
+        );
+        assert!(retry_request.tables.is_empty());
+        assert!(retry_positions.is_empty());
+
+        let failed = merged.failed_rows();
+        assert_eq!(1, failed.len());
+        assert_eq!(("t2", 2), (failed[0].0, failed[0].1));
+        assert!(failed[0].2.is_condition_check_failed());
+        assert!(!failed[0].2.is_retriable());
+    }
+
+    /// 测试批量写入的时候每行使用不同的条件
+    async fn test_batch_write_row_with_conditions_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let uuid: String = UUIDv4.fake();
+
+        let t = TableInBatchWriteRowRequest::new("data_types").rows(vec![
+            RowInBatchWriteRowRequest::put_row(Row::new().primary_key_column_string("str_id", &uuid).column_string("str_col", "created"))
+                .row_condition(RowExistenceExpectation::ExpectNotExist),
+            RowInBatchWriteRowRequest::update_row(
+                Row::new()
+                    .primary_key_column_string("str_id", "not-exist-row-for-batch-write-condition")
+                    .column_string("str_col", "updated"),
+            )
+            .row_condition(RowExistenceExpectation::ExpectExist),
+        ]);
+
+        let resp = client.batch_write_row(BatchWriteRowRequest::new().table(t)).send().await;
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+
+        let resp = resp.unwrap();
+        let failed = resp.failed_rows();
+        assert_eq!(1, failed.len());
+        assert_eq!(1, failed[0].1);
+        assert!(failed[0].2.is_condition_check_failed());
+    }
+
+    #[tokio::test]
+    async fn test_batch_write_row_with_conditions() {
+        test_batch_write_row_with_conditions_impl().await
+    }
+
+    #[test]
+    fn test_batch_write_row_into_chunks() {
+        let rows = |n: usize| (0..n).map(|i| RowInBatchWriteRowRequest::put_row(Row::new().primary_key_column_integer("id", i as i64)));
+
+        let req = BatchWriteRowRequest::new()
+            .table(TableInBatchWriteRowRequest::new("t1").rows(rows(150)))
+            .table(TableInBatchWriteRowRequest::new("t2").rows(rows(300)));
+
+        let chunks = req.into_chunks(200);
+        let layout = chunks
+            .iter()
+            .map(|c| c.tables.iter().map(|t| (t.table_name.as_str(), t.rows.len())).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![vec![("t1", 150), ("t2", 50)], vec![("t2", 200)], vec![("t2", 50)]], layout);
+
+        // 行的顺序保持不变
+        assert_eq!(Some(&PrimaryKeyValue::Integer(50)), chunks[1].tables[0].rows[0].row.get_primary_key_value("id"));
+    }
+
+    #[test]
+    fn test_batch_write_row_into_write_chunks() {
+        let rows = || (0..450).map(|i| RowInBatchWriteRowRequest::put_row(Row::new().primary_key_column_integer("id", i)));
+
+        let chunks = BatchWriteRowRequest::new()
+            .table(TableInBatchWriteRowRequest::new("t1").rows(rows()))
+            .into_write_chunks()
+            .unwrap();
+
+        let sizes = chunks.iter().map(|c| c.tables.iter().map(|t| t.rows.len()).sum::<usize>()).collect::<Vec<_>>();
+        assert_eq!(vec![200, 200, 50], sizes);
+        assert!(sizes.iter().all(|n| *n <= 200));
+
+        // 原子写入不能拆分
+        let res = BatchWriteRowRequest::new()
+            .table(TableInBatchWriteRowRequest::new("t1").rows(rows()))
+            .is_atomic(true)
+            .into_write_chunks();
+        assert!(matches!(res, Err(OtsError::ValidationFailed(_))));
+    }
+
+    #[test]
+    fn test_merge_chunk_responses() {
+        let resp = |tables: &[(&str, usize)]| BatchWriteRowResponse {
+            tables: tables
+                .iter()
+                .map(|(name, n)| TableInBatchWriteRowResponse {
+                    table_name: name.to_string(),
+                    rows: (0..*n)
+                        .map(|i| RowInBatchWriteRowResponse {
+                            is_ok: true,
+                            row: Some(Row::new().primary_key_column_integer("id", i as i64)),
+                            ..Default::default()
+                        })
+                        .collect(),
+                })
+                .collect(),
+        };
+
+        let tables = vec![("t1".to_string(), 150), ("t2".to_string(), 300)];
+        let merged = merge_chunk_responses(&tables, vec![resp(&[("t1", 150), ("t2", 50)]), resp(&[("t2", 200)]), resp(&[("t2", 50)])]);
+
+        assert_eq!(
+            vec![("t1", 150), ("t2", 300)],
+            merged.tables.iter().map(|t| (t.table_name.as_str(), t.rows.len())).collect::<Vec<_>>()
+        );
+
+        // 第二批的第一行是 t2 的第 51 行
+        assert_eq!(
+            Some(&PrimaryKeyValue::Integer(0)),
+            merged.tables[1].rows[50].row.as_ref().and_then(|r| r.get_primary_key_value("id"))
+        );
+    }
+
+    async fn test_batch_write_row_chunked_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let rows = (0..450).map(|i| {
+            let id: String = UUIDv4.fake();
+            RowInBatchWriteRowRequest::put_row(Row::new().primary_key_column_string("str_id", &id).column_integer("int_col", i))
+        });
+
+        let request = BatchWriteRowRequest::new().table(TableInBatchWriteRowRequest::new("data_types").rows(rows));
+
+        let resp = client.batch_write_row_chunked(request).concurrency(2).send().await;
+        log::debug!("{:?}", resp.as_ref().map(|r| r.tables.len()));
+        assert!(resp.is_ok());
+
+        let resp = resp.unwrap();
+        assert_eq!(1, resp.tables.len());
+        assert_eq!(450, resp.tables[0].rows.len());
+        assert!(resp.failed_rows().is_empty());
+
+        // 原子写入不能拆分
+        let rows = (0..201).map(|i| RowInBatchWriteRowRequest::put_row(Row::new().primary_key_column_string("str_id", format!("atomic-{}", i))));
+        let request = BatchWriteRowRequest::new()
+            .table(TableInBatchWriteRowRequest::new("data_types").rows(rows))
+            .is_atomic(true);
+
+        let resp = client.batch_write_row_chunked(request).send().await;
+        assert!(matches!(resp, Err(OtsError::ValidationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_batch_write_row_chunked() {
+        test_batch_write_row_chunked_impl().await
+    }
+
     async fn test_bulk_export_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -497,4 +1567,249 @@ mod test_row_operations {
     async fn test_bulk_export() {
         test_bulk_export_impl().await
     }
+
+    async fn test_bulk_export_row_stream_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let request = BulkExportRequest::new("data_types")
+            .start_primary_key_column_inf_min("str_id")
+            .end_primary_key_column_inf_max("str_id")
+            .columns_to_get(["str_id", "str_col", "int_col", "double_col", "blob_col", "bool_col"]);
+
+        let stream = client.bulk_export(request).into_row_stream();
+        tokio::pin!(stream);
+
+        let mut total_rows = 0;
+
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            let row = row.unwrap();
+            assert!(row.get_primary_key_value("str_id").is_some());
+            total_rows += 1;
+        }
+
+        log::debug!("total read rows: {}", total_rows);
+        assert!(total_rows > 0);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_export_row_stream() {
+        test_bulk_export_row_stream_impl().await
+    }
+
+    async fn test_delete_range_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let uuid: String = UUIDv4.fake();
+        let prefix = format!("delete_range_{}", uuid);
+
+        let mut req = BulkImportRequest::new("data_types");
+        for i in 0..3 {
+            req = req.put_row(
+                Row::new()
+                    .primary_key_column_string("str_id", format!("{}_{}", prefix, i))
+                    .column_string("str_col", "row to delete"),
+            );
+        }
+
+        let resp = client.bulk_import(req).send().await;
+        assert!(resp.is_ok());
+
+        let resp = client
+            .delete_range(
+                "data_types",
+                PrimaryKey::new().column_string("str_id", format!("{}_", prefix)),
+                PrimaryKey::new().column_string("str_id", format!("{}_~", prefix)),
+            )
+            .concurrency(2)
+            .send()
+            .await;
+
+        log::debug!("delete range response: {:?}", resp);
+
+        assert!(resp.is_ok());
+        assert_eq!(3, resp.unwrap().rows_deleted);
+    }
+
+    #[tokio::test]
+    async fn test_delete_range() {
+        test_delete_range_impl().await
+    }
+
+    async fn test_increment_column_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let table_name = "data_types";
+        let id: String = UUIDv4.fake();
+        let pk = PrimaryKey::new().column_string("str_id", &id);
+
+        let first = client.increment_column(table_name, pk.clone(), "int_col", 3).await.unwrap();
+        assert_eq!(3, first);
+
+        let second = client.increment_column(table_name, pk.clone(), "int_col", 3).await.unwrap();
+        assert_eq!(first + 3, second);
+
+        let response = client.get_row(GetRowRequest::new(table_name).primary_key(pk.clone())).send().await.unwrap();
+        assert_eq!(Some(6), response.row.and_then(|r| r.get_column_value("int_col").and_then(|v| v.as_i64())));
+
+        // 不是整数的列不能递增
+        client
+            .update_row(UpdateRowRequest::new(table_name).row(Row::new().primary_key(pk.clone()).column_string("str_col", "a")))
+            .send()
+            .await
+            .unwrap();
+        assert!(client.increment_column(table_name, pk, "str_col", 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_increment_column() {
+        test_increment_column_impl().await;
+    }
+
+    #[tokio::test]
+    async fn test_validate_against_schema() {
+        // 端口 1 上没有服务，如果请求被发送出去会得到网络错误，而不是校验错误
+        let client = OtsClient::builder("aid", "asec")
+            .endpoint("http://127.0.0.1:1")
+            .instance_name("instance")
+            .region("cn-hangzhou")
+            .validate_against_schema(true)
+            .build()
+            .unwrap();
+
+        client.schema_cache.write().unwrap().insert(
+            "users".to_string(),
+            crate::protos::TableMeta {
+                table_name: "users".to_string(),
+                primary_key: vec![crate::protos::PrimaryKeySchema {
+                    name: "user_id".to_string(),
+                    r#type: crate::protos::PrimaryKeyType::String as i32,
+                    option: None,
+                }],
+                defined_column: vec![],
+            },
+        );
+
+        let put = client
+            .put_row(PutRowRequest::new("users").row(Row::new().primary_key_column_integer("user_id", 1).column_string("name", "a")))
+            .send()
+            .await;
+        assert!(matches!(put, Err(OtsError::ValidationFailed(msg)) if msg.contains("user_id")));
+
+        let get = client
+            .get_row(GetRowRequest::new("users").primary_key_column_integer("user_id", 1))
+            .send()
+            .await;
+        assert!(matches!(get, Err(OtsError::ValidationFailed(_))));
+
+        let delete = client
+            .delete_row(DeleteRowRequest::new("users").primary_key_column_string("uid", "1"))
+            .send()
+            .await;
+        assert!(matches!(delete, Err(OtsError::ValidationFailed(_))));
+    }
+
+    #[test]
+    fn test_put_row_auto_increment_value() {
+        let row = Row::new()
+            .primary_key_column_string("school_id", "a")
+            .primary_key_column_integer("id", 1742373697699000);
+        let response = PutRowResponse::try_from(crate::protos::PutRowResponse {
+            consumed: Default::default(),
+            row: Some(row.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM)),
+        })
+        .unwrap();
+
+        assert_eq!(Some(1742373697699000), response.auto_increment_value("id"));
+        assert_eq!(None, response.auto_increment_value("school_id"));
+        assert_eq!(None, response.auto_increment_value("not_exist"));
+        assert_eq!(None, PutRowResponse::default().auto_increment_value("id"));
+    }
+
+    #[test]
+    fn test_update_row_return_columns() {
+        let row = Row::new()
+            .primary_key(PrimaryKey::new().column_string("user_id", "0005358A-DCAF-665E-EECF-D9935E821B87"))
+            .column_to_increse("counter", 1)
+            .column_string("name", "tablestore");
+
+        let request = UpdateRowRequest::new("users")
+            .row(row.clone())
+            .return_type(ReturnType::RtAfterModify)
+            .return_columns(["counter"]);
+        assert!(request.validate().is_ok());
+
+        let request = UpdateRowRequest::new("users")
+            .row(row.clone())
+            .return_type(ReturnType::RtAfterModify)
+            .return_columns(["counter", "score"]);
+        let err = request.validate();
+        assert!(matches!(err, Err(OtsError::ValidationFailed(msg)) if msg.contains("score")));
+
+        let msg = crate::protos::UpdateRowRequest::from(
+            UpdateRowRequest::new("users")
+                .row(row)
+                .return_type(ReturnType::RtAfterModify)
+                .return_column("counter"),
+        );
+        assert_eq!(Some(vec!["counter".to_string()]), msg.return_content.map(|c| c.return_column_names));
+
+        // 服务端返回的修改后的列解码到响应的行中
+        let returned = Row::new()
+            .primary_key(PrimaryKey::new().column_string("user_id", "0005358A-DCAF-665E-EECF-D9935E821B87"))
+            .column_integer("counter", 8);
+        let response = UpdateRowResponse::try_from(crate::protos::UpdateRowResponse {
+            consumed: Default::default(),
+            row: Some(returned.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM)),
+        })
+        .unwrap();
+        assert_eq!(Some(8), response.row.and_then(|r| r.get_column_value("counter").and_then(|v| v.as_i64())));
+    }
+
+    #[test]
+    fn test_get_range_progress() {
+        let request = GetRangeRequest::new("users")
+            .start_primary_key_column_string("user_id_part", "a")
+            .start_primary_key_column_integer("id", 1000)
+            .end_primary_key_column_string("user_id_part", "a")
+            .end_primary_key_column_integer("id", 2000);
+
+        let mut last_progress = 0.0;
+
+        for next_id in [1100, 1500, 1900] {
+            let resp = GetRangeResponse {
+                next_start_primary_key: Some(vec![
+                    PrimaryKeyColumn::from_string("user_id_part", "a"),
+                    PrimaryKeyColumn::from_integer("id", next_id),
+                ]),
+                ..Default::default()
+            };
+
+            assert!(resp.has_more());
+
+            let progress = resp.progress(&request).unwrap();
+            assert!(progress > last_progress);
+            last_progress = progress;
+        }
+
+        assert!((last_progress - 0.9).abs() < 1e-9);
+
+        let resp = GetRangeResponse::default();
+        assert!(!resp.has_more());
+        assert_eq!(Some(1.0), resp.progress(&request));
+
+        // 不是整数的范围无法估算进度
+        let request = GetRangeRequest::new("users")
+            .start_primary_key_column_inf_min("user_id_part")
+            .end_primary_key_column_inf_max("user_id_part");
+
+        let resp = GetRangeResponse {
+            next_start_primary_key: Some(vec![PrimaryKeyColumn::from_string("user_id_part", "b")]),
+            ..Default::default()
+        };
+
+        assert_eq!(None, resp.progress(&request));
+    }
 }
@@ -7,7 +7,7 @@ use crate::OtsRequestOptions;
 use crate::{
     add_per_request_options,
     error::OtsError,
-    model::{Filter, Row},
+    model::{Filter, PrimaryKeyValue, Row},
     protos::{
         plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM},
         {Condition, ConsumedCapacity, ReturnContent, ReturnType, RowExistenceExpectation},
@@ -60,7 +60,9 @@ impl PutRowRequest {
         self
     }
 
-    /// 设置行存在性检查
+    /// 设置行存在性检查。
+    ///
+    /// 设置为 `ExpectNotExist` 可以实现“不存在时才插入”：行已经存在时，服务端返回 `OTSConditionCheckFail` 错误，不会覆盖已有的数据
     pub fn row_condition(mut self, row_condition: RowExistenceExpectation) -> Self {
         self.row_condition = row_condition;
 
@@ -123,6 +125,10 @@ impl PutRowRequest {
             }
         }
 
+        if let Some(f) = &self.column_condition {
+            f.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -168,6 +174,18 @@ pub struct PutRowResponse {
     pub row: Option<Row>,
 }
 
+impl PutRowResponse {
+    /// 获取服务端为自增主键列生成的值。
+    ///
+    /// 需要在请求中设置 `return_type(ReturnType::RtPk)`，否则响应中没有主键，返回 `None`
+    pub fn auto_increment_value(&self, pk_name: &str) -> Option<i64> {
+        match self.row.as_ref()?.get_primary_key_value(pk_name)? {
+            PrimaryKeyValue::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<crate::protos::PutRowResponse> for PutRowResponse {
     type Error = OtsError;
 
@@ -211,7 +229,9 @@ impl PutRowOperation {
     pub async fn send(self) -> OtsResult<PutRowResponse> {
         self.request.validate()?;
 
-        let Self { client, request, options } = self;
+        let Self { client, mut request, options } = self;
+
+        client.prepare_primary_keys(&request.table_name, [&mut request.row.primary_key]).await?;
 
         let msg: crate::protos::PutRowRequest = request.into();
 
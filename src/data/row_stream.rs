@@ -0,0 +1,211 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use tokio::{
+    sync::mpsc::{Receiver, Sender},
+    task::{JoinHandle, JoinSet},
+};
+
+use crate::{error::OtsError, model::Row, util::join_next_or_resume, OtsResult};
+
+/// 后台任务已经读取但还没有被消费的行的最大数量，超过之后读取任务等待
+const CHANNEL_CAPACITY: usize = 5000;
+
+/// 可以分页读取行的操作。每次读取一页，同时返回读取下一页使用的操作，没有更多数据时返回 `None`。
+///
+/// 行的类型默认为宽表的 [`Row`]，时序表的行、时间线元数据等其他类型可以通过 `T` 指定
+pub(crate) trait PagedRowOperation<T = Row>: Sized + Send + 'static {
+    fn next_page(self) -> impl Future<Output = OtsResult<(Vec<T>, Option<Self>)>> + Send;
+}
+
+/// 内部状态，在读取过程中被移动到 future 中
+struct RowStreamState<O, T> {
+    /// 读取下一页使用的操作。为 `None` 表示已经读取完毕或者出错
+    next: Option<O>,
+
+    /// 已经读取但还没有返回的行
+    buffer: VecDeque<T>,
+}
+
+impl<T: Send + 'static, O: PagedRowOperation<T>> RowStreamState<O, T> {
+    async fn advance(mut self) -> (Self, Option<OtsResult<T>>) {
+        loop {
+            if let Some(row) = self.buffer.pop_front() {
+                return (self, Some(Ok(row)));
+            }
+
+            let op = match self.next.take() {
+                Some(op) => op,
+                None => return (self, None),
+            };
+
+            match op.next_page().await {
+                Ok((rows, next)) => {
+                    self.buffer.extend(rows);
+                    self.next = next;
+                }
+
+                // 出错之后不再继续读取
+                Err(e) => return (self, Some(Err(e))),
+            }
+        }
+    }
+}
+
+type AdvanceFuture<O, T> = Pin<Box<dyn Future<Output = (RowStreamState<O, T>, Option<OtsResult<T>>)> + Send>>;
+
+/// 自动翻页的行读取流
+pub(crate) struct RowStream<O, T = Row> {
+    state: Option<RowStreamState<O, T>>,
+    pending: Option<AdvanceFuture<O, T>>,
+}
+
+impl<T: Send + 'static, O: PagedRowOperation<T>> RowStream<O, T> {
+    pub(crate) fn new(op: O) -> Self {
+        Self {
+            state: Some(RowStreamState {
+                next: Some(op),
+                buffer: VecDeque::new(),
+            }),
+            pending: None,
+        }
+    }
+}
+
+impl<T, O> Unpin for RowStream<O, T> {}
+
+impl<T: Send + 'static, O: PagedRowOperation<T>> Stream for RowStream<O, T> {
+    type Item = OtsResult<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut fut = match self.pending.take() {
+            Some(fut) => fut,
+            None => match self.state.take() {
+                Some(state) => Box::pin(state.advance()),
+                None => return Poll::Ready(None),
+            },
+        };
+
+        match fut.as_mut().poll(cx) {
+            Poll::Ready((state, item)) => {
+                self.state = Some(state);
+                Poll::Ready(item)
+            }
+
+            Poll::Pending => {
+                self.pending = Some(fut);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// 从通道中读取行的流，行由后台任务写入通道。流被丢弃之后，后台任务写入失败并停止读取
+pub(crate) struct ChannelRowStream {
+    rx: Receiver<OtsResult<Row>>,
+
+    /// 写入通道的后台任务。通道关闭之后等待它结束，以便把后台任务中的 panic 传播到读取流的任务中
+    task: Option<JoinHandle<()>>,
+}
+
+impl Stream for ChannelRowStream {
+    type Item = OtsResult<Row>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(item) = ready!(self.rx.poll_recv(cx)) {
+            return Poll::Ready(Some(item));
+        }
+
+        if let Some(task) = self.task.as_mut() {
+            let res = ready!(Pin::new(task).poll(cx));
+            self.task = None;
+
+            if let Err(e) = res {
+                if e.is_panic() {
+                    std::panic::resume_unwind(e.into_panic());
+                }
+            }
+        }
+
+        Poll::Ready(None)
+    }
+}
+
+/// 读取一个分片的全部数据并写入通道。出错或者通道已经关闭的时候返回 `false`
+async fn read_split<O: PagedRowOperation>(op: O, tx: Sender<OtsResult<Row>>) -> bool {
+    let stream = RowStream::new(op);
+    tokio::pin!(stream);
+
+    while let Some(item) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        let failed = item.is_err();
+
+        if tx.send(item).await.is_err() || failed {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// 获取所有分片，然后最多同时读取 `concurrency` 个分片，把读取到的行写入通道
+async fn read_splits<O, F>(concurrency: usize, splits: F, tx: Sender<OtsResult<Row>>)
+where
+    O: PagedRowOperation,
+    F: Future<Output = OtsResult<Vec<O>>>,
+{
+    if concurrency == 0 {
+        let _ = tx
+            .send(Err(OtsError::ValidationFailed("invalid concurrency: must be greater than 0".to_string())))
+            .await;
+        return;
+    }
+
+    let ops = match splits.await {
+        Ok(ops) => ops,
+        Err(e) => {
+            let _ = tx.send(Err(e)).await;
+            return;
+        }
+    };
+
+    let mut tasks = JoinSet::new();
+
+    for op in ops {
+        while tasks.len() >= concurrency {
+            // 有分片出错或者流已经被丢弃的时候停止，丢弃 `tasks` 会取消其余的任务
+            if let Some(false) = join_next_or_resume(&mut tasks).await {
+                return;
+            }
+        }
+
+        tasks.spawn(read_split(op, tx.clone()));
+    }
+
+    while let Some(ok) = join_next_or_resume(&mut tasks).await {
+        if !ok {
+            return;
+        }
+    }
+}
+
+/// 在后台任务中最多同时读取 `concurrency` 个分片，每个分片都会自动翻页直到读取完毕。
+/// `splits` 返回每个分片读取第一页使用的操作。
+///
+/// 不同分片的行可能交错返回，同一个分片中的行按照顺序返回。出错之后返回这个错误，然后结束。
+/// 读取分片的任务 panic 的时候，在读取流的任务中继续 panic
+pub(crate) fn concurrent_row_stream<O, F>(concurrency: usize, splits: F) -> ChannelRowStream
+where
+    O: PagedRowOperation,
+    F: Future<Output = OtsResult<Vec<O>>> + Send + 'static,
+{
+    let (tx, rx) = tokio::sync::mpsc::channel(CHANNEL_CAPACITY);
+
+    let task = tokio::spawn(read_splits(concurrency, splits, tx));
+
+    ChannelRowStream { rx, task: Some(task) }
+}
@@ -0,0 +1,30 @@
+use crate::{table::ComputeSplitPointsBySizeRequest, OtsClient};
+
+use super::{
+    row_stream::{concurrent_row_stream, ChannelRowStream},
+    GetRangeRequest,
+};
+
+/// 计算分割点时每个分片的近似大小，以百兆为单位
+const SCAN_SPLIT_SIZE: u64 = 1;
+
+/// 并发读取全表数据。使用 `ComputeSplitPointsBySize` 把表划分成多个分片，在后台任务中最多同时读取 `concurrency` 个分片，
+/// 每个分片都会自动翻页直到读取完毕。
+///
+/// 不同分片的行可能交错返回，同一个分片中的行按照主键顺序返回。出错之后返回这个错误，然后结束
+pub(crate) fn scan_table(client: OtsClient, table_name: &str, concurrency: usize) -> ChannelRowStream {
+    let table_name = table_name.to_string();
+
+    concurrent_row_stream(concurrency, async move {
+        let resp = client
+            .compute_split_points_by_size(ComputeSplitPointsBySizeRequest::new(&table_name, SCAN_SPLIT_SIZE))
+            .send()
+            .await?;
+
+        Ok(resp
+            .into_primary_key_ranges()
+            .into_iter()
+            .map(|(start, end)| client.get_range(GetRangeRequest::new(&table_name).primary_key_range(start, end)))
+            .collect())
+    })
+}
@@ -39,12 +39,17 @@ pub struct UpdateRowRequest {
     /// 进行行存在性检查的时候，可以附加列过滤器
     pub column_condition: Option<Filter>,
 
-    /// 返回数据设置。目前仅支持返回主键，主要用于主键列自增功能。
+    /// 返回数据设置。
+    ///
+    /// - `ReturnType::RtPk`：返回主键，主要用于主键列自增功能
+    /// - `ReturnType::RtAfterModify`：返回 `return_columns` 中的列修改后的值，例如递增之后的值。返回的列解码到响应的 `row` 中
     ///
     /// 见 [`ReturnType`](`crate::protos::ReturnType`)
     pub return_type: Option<ReturnType>,
 
-    /// 如果需要返回数据，可以指定要返回的列
+    /// 如果需要返回数据，可以指定要返回的列。
+    ///
+    /// 和 `ReturnType::RtAfterModify` 一起使用时，只返回这些列修改后的值，这些列必须是本次请求中修改的列
     pub return_columns: HashSet<String>,
 
     /// 局部事务ID。当使用局部事务功能写入数据时必须设置此参数。
@@ -109,7 +114,7 @@ impl UpdateRowRequest {
     }
 
     /// 验证请求设置
-    fn validate(&self) -> OtsResult<()> {
+    pub(crate) fn validate(&self) -> OtsResult<()> {
         if !validate_table_name(&self.table_name) {
             return Err(OtsError::ValidationFailed(format!("invalid table name: {}", self.table_name)));
         }
@@ -130,6 +135,28 @@ impl UpdateRowRequest {
             }
         }
 
+        if let Some(f) = &self.column_condition {
+            f.validate()?;
+        }
+
+        if self.return_type == Some(ReturnType::RtAfterModify) {
+            let mut not_modified = self
+                .return_columns
+                .iter()
+                .filter(|name| !self.row.columns.iter().any(|col| &col.name == *name))
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>();
+
+            if !not_modified.is_empty() {
+                not_modified.sort();
+
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid return columns: columns are not modified in this request: {}",
+                    not_modified.join(", ")
+                )));
+            }
+        }
+
         Ok(())
     }
 }
@@ -218,7 +245,9 @@ impl UpdateRowOperation {
     pub async fn send(self) -> OtsResult<UpdateRowResponse> {
         self.request.validate()?;
 
-        let Self { client, request, options } = self;
+        let Self { client, mut request, options } = self;
+
+        client.prepare_primary_keys(&request.table_name, [&mut request.row.primary_key]).await?;
 
         let msg: crate::protos::UpdateRowRequest = request.into();
 
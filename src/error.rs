@@ -15,10 +15,207 @@ impl Display for protos::Error {
             "API response error. code: {}, message: {}",
             self.code,
             self.message.as_ref().unwrap_or(&"".to_string())
+        )?;
+
+        if let Some(detail) = &self.access_denied_detail {
+            write!(f, ", {}", AccessDeniedDetail::from(detail.clone()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// 访问被拒绝（例如 RAM 策略不允许当前操作）时，服务端返回的详细信息
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessDeniedDetail {
+    /// 被拒绝的操作，例如 `ots:PutRow`
+    pub auth_action: String,
+
+    /// 访问者的身份类型，例如 `SubUser`、`AssumedRoleUser`
+    pub auth_principal_type: String,
+
+    /// 访问者所属的阿里云账号 ID
+    pub auth_principal_owner_id: String,
+
+    /// 访问者的名称
+    pub auth_principal_display_name: String,
+
+    /// 拒绝访问的策略类型，例如 `AccountLevelIdentityBasedPolicy`、`ResourceGroupLevelIdentityBasedPolicy`
+    pub policy_type: String,
+
+    /// 无权限的类型，例如 `ImplicitDeny` 表示没有授权，`ExplicitDeny` 表示被策略显式拒绝
+    pub no_permission_type: String,
+
+    /// 编码后的诊断信息，可以通过 RAM 的 `DecodeDiagnosticMessage` 接口解码
+    pub encoded_diagnostic_message: Option<String>,
+}
+
+impl From<protos::AccessDeniedDetail> for AccessDeniedDetail {
+    fn from(value: protos::AccessDeniedDetail) -> Self {
+        let protos::AccessDeniedDetail {
+            auth_action,
+            auth_principal_type,
+            auth_principal_owner_id,
+            auth_principal_display_name,
+            policy_type,
+            no_permission_type,
+            encoded_diagnostic_message,
+        } = value;
+
+        Self {
+            auth_action,
+            auth_principal_type,
+            auth_principal_owner_id,
+            auth_principal_display_name,
+            policy_type,
+            no_permission_type,
+            encoded_diagnostic_message,
+        }
+    }
+}
+
+impl Display for AccessDeniedDetail {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "access denied: action {} is denied for {} {} (owner: {}) by {} policy ({})",
+            self.auth_action,
+            self.auth_principal_type,
+            self.auth_principal_display_name,
+            self.auth_principal_owner_id,
+            self.policy_type,
+            self.no_permission_type
         )
     }
 }
 
+/// 服务端返回的常见错误码。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/error-codes>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiErrorCode {
+    /// `OTSAuthFailed`：鉴权失败，例如 AccessKey 不正确或者没有权限
+    AuthFailed,
+
+    /// `OTSConditionCheckFail`：行存在性条件或者列条件检查失败
+    ConditionCheckFail,
+
+    /// `OTSInternalServerError`：服务端内部错误
+    InternalServerError,
+
+    /// `OTSNotEnoughCapacityUnit`：预留读写吞吐量不足
+    NotEnoughCapacityUnit,
+
+    /// `OTSObjectAlreadyExist`：要创建的对象（例如表、索引）已经存在
+    ObjectAlreadyExist,
+
+    /// `OTSObjectNotExist`：操作的对象（例如表、索引、通道）不存在
+    ObjectNotExist,
+
+    /// `OTSOutOfColumnCountLimit`：列的数量超过限制
+    OutOfColumnCountLimit,
+
+    /// `OTSOutOfRowSizeLimit`：行的大小超过限制
+    OutOfRowSizeLimit,
+
+    /// `OTSParameterInvalid`：请求参数不合法
+    ParameterInvalid,
+
+    /// `OTSPartitionUnavailable`：分区暂时不可用
+    PartitionUnavailable,
+
+    /// `OTSQuotaExhausted`：超过配额，例如表操作太频繁
+    QuotaExhausted,
+
+    /// `OTSRequestBodyTooLarge`：请求体太大
+    RequestBodyTooLarge,
+
+    /// `OTSRequestTimeout`：请求超时
+    RequestTimeout,
+
+    /// `OTSRowOperationConflict`：同时操作同一行的请求太多
+    RowOperationConflict,
+
+    /// `OTSServerBusy`：服务端繁忙
+    ServerBusy,
+
+    /// `OTSServerUnavailable`：服务端暂时不可用
+    ServerUnavailable,
+
+    /// `OTSTableNotReady`：表刚创建，还没有准备好
+    TableNotReady,
+
+    /// `OTSTimeout`：服务端处理超时
+    Timeout,
+
+    /// `OTSTunnelServerUnavailable`：通道服务暂时不可用
+    TunnelServerUnavailable,
+
+    /// 其他错误码，保存原始的错误码字符串
+    Other(String),
+}
+
+impl ApiErrorCode {
+    /// 服务端返回的错误码字符串
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::AuthFailed => "OTSAuthFailed",
+            Self::ConditionCheckFail => "OTSConditionCheckFail",
+            Self::InternalServerError => "OTSInternalServerError",
+            Self::NotEnoughCapacityUnit => "OTSNotEnoughCapacityUnit",
+            Self::ObjectAlreadyExist => "OTSObjectAlreadyExist",
+            Self::ObjectNotExist => "OTSObjectNotExist",
+            Self::OutOfColumnCountLimit => "OTSOutOfColumnCountLimit",
+            Self::OutOfRowSizeLimit => "OTSOutOfRowSizeLimit",
+            Self::ParameterInvalid => "OTSParameterInvalid",
+            Self::PartitionUnavailable => "OTSPartitionUnavailable",
+            Self::QuotaExhausted => "OTSQuotaExhausted",
+            Self::RequestBodyTooLarge => "OTSRequestBodyTooLarge",
+            Self::RequestTimeout => "OTSRequestTimeout",
+            Self::RowOperationConflict => "OTSRowOperationConflict",
+            Self::ServerBusy => "OTSServerBusy",
+            Self::ServerUnavailable => "OTSServerUnavailable",
+            Self::TableNotReady => "OTSTableNotReady",
+            Self::Timeout => "OTSTimeout",
+            Self::TunnelServerUnavailable => "OTSTunnelServerUnavailable",
+            Self::Other(s) => s,
+        }
+    }
+}
+
+impl From<&str> for ApiErrorCode {
+    fn from(value: &str) -> Self {
+        match value {
+            "OTSAuthFailed" => Self::AuthFailed,
+            "OTSConditionCheckFail" => Self::ConditionCheckFail,
+            "OTSInternalServerError" => Self::InternalServerError,
+            "OTSNotEnoughCapacityUnit" => Self::NotEnoughCapacityUnit,
+            "OTSObjectAlreadyExist" => Self::ObjectAlreadyExist,
+            "OTSObjectNotExist" => Self::ObjectNotExist,
+            "OTSOutOfColumnCountLimit" => Self::OutOfColumnCountLimit,
+            "OTSOutOfRowSizeLimit" => Self::OutOfRowSizeLimit,
+            "OTSParameterInvalid" => Self::ParameterInvalid,
+            "OTSPartitionUnavailable" => Self::PartitionUnavailable,
+            "OTSQuotaExhausted" => Self::QuotaExhausted,
+            "OTSRequestBodyTooLarge" => Self::RequestBodyTooLarge,
+            "OTSRequestTimeout" => Self::RequestTimeout,
+            "OTSRowOperationConflict" => Self::RowOperationConflict,
+            "OTSServerBusy" => Self::ServerBusy,
+            "OTSServerUnavailable" => Self::ServerUnavailable,
+            "OTSTableNotReady" => Self::TableNotReady,
+            "OTSTimeout" => Self::Timeout,
+            "OTSTunnelServerUnavailable" => Self::TunnelServerUnavailable,
+            s => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl Display for ApiErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum OtsError {
     #[error("{0}")]
@@ -33,9 +230,9 @@ pub enum OtsError {
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
 
-    /// This is error for OTS API response.
+    /// This is error for OTS API response. 第二个值是响应头 `x-ots-requestid` 中的请求 ID
     #[error("{0}")]
-    ApiError(Box<protos::Error>),
+    ApiError(Box<protos::Error>, Option<String>),
 
     #[error("{0}")]
     FromUtf8Error(#[from] FromUtf8Error),
@@ -43,9 +240,169 @@ pub enum OtsError {
     #[error("{0}")]
     ReadError(#[from] std::io::Error),
 
+    /// 第三个值是响应头 `x-ots-requestid` 中的请求 ID
     #[error("Aliyun ots api response with non-successful code: {0}. response message is: {1}")]
-    StatusError(StatusCode, String),
+    StatusError(StatusCode, String, Option<String>),
 
     #[error("{0}")]
     PlainBufferError(String),
+
+    /// plain buffer 中单元格或者行的 CRC8 校验码和根据数据计算出来的不一致，说明响应数据被截断或者损坏
+    #[error("Plain buffer checksum mismatch. calculated: {calculated}, received: {received}")]
+    ChecksumMismatch { calculated: u8, received: u8 },
+
+    /// 把列值转换成 Rust 类型时，列值的类型和目标类型不一致。类型名称见 [`ColumnValue::type_name`](crate::model::ColumnValue::type_name)
+    #[error("Column value type mismatch. expected: {expected}, actual: {actual}")]
+    ColumnValueTypeMismatch { expected: &'static str, actual: &'static str },
+
+    /// 编码之后的请求体超过了服务端允许的大小，请求没有发送。
+    /// 限制见 [`MAX_WRITE_REQUEST_SIZE`](crate::model::MAX_WRITE_REQUEST_SIZE) 和 [`MAX_REQUEST_SIZE`](crate::model::MAX_REQUEST_SIZE)
+    #[error("Request body is too large: {size} bytes, limit: {limit} bytes")]
+    RequestTooLarge { size: usize, limit: usize },
+
+    /// 要删除的通道不存在（服务端返回 `OTSObjectNotExist`）。如果只需要保证通道不存在，可以把这个错误当成成功处理
+    #[error("Tunnel {tunnel_name} of table {table_name} does not exist")]
+    TunnelNotExist {
+        table_name: String,
+        tunnel_name: String,
+        request_id: Option<String>,
+    },
+
+    /// 使用 serde 在行和结构体之间转换失败。只有开启 `serde` 特性时才会返回这个错误，
+    /// 但是这个变体始终存在，所以匹配错误的代码不需要根据特性区分
+    #[error("Serde error: {0}")]
+    SerdeError(String),
+}
+
+impl OtsError {
+    /// 服务端返回的请求 ID，提交工单时需要提供。只有服务端返回了错误响应时才有
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::ApiError(_, request_id) | Self::StatusError(_, _, request_id) | Self::TunnelNotExist { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// 如果是服务端返回的错误，返回错误码
+    pub fn code(&self) -> Option<ApiErrorCode> {
+        match self {
+            Self::ApiError(e, _) => Some(ApiErrorCode::from(e.code.as_str())),
+            _ => None,
+        }
+    }
+
+    /// 如果是服务端返回的访问被拒绝的错误，返回拒绝访问的详细信息
+    pub fn access_denied_detail(&self) -> Option<AccessDeniedDetail> {
+        match self {
+            Self::ApiError(e, _) => e.access_denied_detail.clone().map(AccessDeniedDetail::from),
+            _ => None,
+        }
+    }
+
+    /// 批量操作中单行失败时的错误。服务端没有返回错误详情时，使用 `action` 说明失败的操作
+    pub(crate) fn from_row_error(error: Option<protos::Error>, action: impl std::fmt::Display) -> Self {
+        match error {
+            Some(e) => Self::ApiError(Box::new(e), None),
+            None => Self::ValidationFailed(format!("{} failed without error detail", action)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_error {
+    use crate::protos;
+
+    use super::{AccessDeniedDetail, ApiErrorCode, OtsError};
+
+    #[test]
+    fn test_access_denied_detail() {
+        let err = OtsError::ApiError(
+            Box::new(protos::Error {
+                code: "OTSAuthFailed".to_string(),
+                message: Some("The user is not authorized to perform the action.".to_string()),
+                access_denied_detail: Some(protos::AccessDeniedDetail {
+                    auth_action: "ots:PutRow".to_string(),
+                    auth_principal_type: "SubUser".to_string(),
+                    auth_principal_owner_id: "1234567890".to_string(),
+                    auth_principal_display_name: "test-user".to_string(),
+                    policy_type: "AccountLevelIdentityBasedPolicy".to_string(),
+                    no_permission_type: "ImplicitDeny".to_string(),
+                    encoded_diagnostic_message: Some("AQEAAAAA".to_string()),
+                }),
+            }),
+            None,
+        );
+
+        let detail = err.access_denied_detail();
+        assert!(detail.is_some());
+
+        let detail = detail.unwrap();
+        assert_eq!("ots:PutRow", detail.auth_action);
+        assert_eq!("AccountLevelIdentityBasedPolicy", detail.policy_type);
+        assert_eq!(Some("AQEAAAAA".to_string()), detail.encoded_diagnostic_message);
+
+        let msg = err.to_string();
+        assert!(msg.contains("OTSAuthFailed"));
+        assert!(msg.contains("ots:PutRow"));
+        assert!(msg.contains("ImplicitDeny"));
+
+        let err = OtsError::ApiError(
+            Box::new(protos::Error {
+                code: "OTSObjectNotExist".to_string(),
+                message: None,
+                access_denied_detail: None,
+            }),
+            None,
+        );
+        assert_eq!(None, err.access_denied_detail());
+        assert_eq!(None, OtsError::ValidationFailed("invalid".to_string()).access_denied_detail());
+        assert_eq!(AccessDeniedDetail::default(), AccessDeniedDetail::from(protos::AccessDeniedDetail::default()));
+    }
+
+    #[test]
+    fn test_api_error_code() {
+        let err = OtsError::ApiError(
+            Box::new(protos::Error {
+                code: "OTSConditionCheckFail".to_string(),
+                message: Some("Condition check failed.".to_string()),
+                access_denied_detail: None,
+            }),
+            None,
+        );
+        assert_eq!(Some(ApiErrorCode::ConditionCheckFail), err.code());
+
+        let err = OtsError::ApiError(
+            Box::new(protos::Error {
+                code: "OTSSomethingNew".to_string(),
+                message: None,
+                access_denied_detail: None,
+            }),
+            None,
+        );
+        assert_eq!(Some(ApiErrorCode::Other("OTSSomethingNew".to_string())), err.code());
+        assert_eq!(None, OtsError::ValidationFailed("invalid".to_string()).code());
+
+        for code in [ApiErrorCode::ObjectNotExist, ApiErrorCode::QuotaExhausted, ApiErrorCode::RowOperationConflict] {
+            assert_eq!(code, ApiErrorCode::from(code.as_str()));
+        }
+
+        assert_eq!("OTSRowOperationConflict", ApiErrorCode::RowOperationConflict.to_string());
+    }
+
+    #[test]
+    fn test_request_id() {
+        let err = OtsError::ApiError(
+            Box::new(protos::Error {
+                code: "OTSServerBusy".to_string(),
+                message: None,
+                access_denied_detail: None,
+            }),
+            Some("0005f1a2-b3c4".to_string()),
+        );
+        assert_eq!(Some("0005f1a2-b3c4"), err.request_id());
+
+        let err = OtsError::StatusError(reqwest::StatusCode::BAD_GATEWAY, "".to_string(), Some("0005f1a2-b3c5".to_string()));
+        assert_eq!(Some("0005f1a2-b3c5"), err.request_id());
+        assert_eq!(None, OtsError::ValidationFailed("invalid".to_string()).request_id());
+    }
 }
@@ -0,0 +1,38 @@
+use crate::{add_per_request_options, OtsClient, OtsRequestOptions, OtsResult};
+
+/// 列出时序表关联的 lastpoint 索引的名称。
+///
+/// 服务端没有单独列出 lastpoint 索引的接口，这里通过 `DescribeTimeseriesTable` 接口获取。
+/// 服务端也不返回 lastpoint 索引的构建状态，创建之后就可以在这里列出
+#[derive(Clone)]
+pub struct ListTimeseriesLastpointIndexOperation {
+    client: OtsClient,
+    table_name: String,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(ListTimeseriesLastpointIndexOperation);
+
+impl ListTimeseriesLastpointIndexOperation {
+    pub(crate) fn new(client: OtsClient, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    pub async fn send(self) -> OtsResult<Vec<String>> {
+        let Self { client, table_name, options } = self;
+
+        let mut op = client.describe_timeseries_table(&table_name);
+
+        if let Some(ms) = options.timeout_ms {
+            op = op.timeout_ms(ms);
+        }
+
+        let resp = op.send().await?;
+
+        Ok(resp.lastpoint_index_names().into_iter().map(|s| s.to_string()).collect())
+    }
+}
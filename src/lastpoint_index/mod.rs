@@ -2,13 +2,15 @@
 
 mod create_lastpoint_index;
 mod delete_lastpoint_index;
+mod list_lastpoint_index;
 
 pub use create_lastpoint_index::*;
 pub use delete_lastpoint_index::*;
+pub use list_lastpoint_index::*;
 
 #[cfg(test)]
 mod test {
-    use crate::{lastpoint_index::CreateTimeseriesLastpointIndexRequest, test_util::setup, OtsClient};
+    use crate::{lastpoint_index::CreateTimeseriesLastpointIndexRequest, test_util::setup, util::current_time_ms, OtsClient};
 
     async fn test_create_lastpoint_index_impl() {
         setup();
@@ -25,6 +27,34 @@ mod test {
         test_create_lastpoint_index_impl().await;
     }
 
+    async fn test_list_lastpoint_index_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let index_name = format!("lpi_{}", current_time_ms());
+        let req = CreateTimeseriesLastpointIndexRequest::new("timeseries_demo_with_data", &index_name);
+        client.create_timeseries_lastpoint_index(req).send().await.unwrap();
+
+        let names = client.list_timeseries_lastpoint_index("timeseries_demo_with_data").send().await.unwrap();
+        log::debug!("{:?}", names);
+        assert!(names.contains(&index_name));
+
+        client
+            .delete_timeseries_lastpoint_index("timeseries_demo_with_data", &index_name)
+            .send()
+            .await
+            .unwrap();
+
+        let names = client.list_timeseries_lastpoint_index("timeseries_demo_with_data").send().await.unwrap();
+        assert!(!names.contains(&index_name));
+    }
+
+    #[tokio::test]
+    async fn test_list_lastpoint_index() {
+        test_list_lastpoint_index_impl().await;
+    }
+
     async fn test_delete_lastpoint_index_impl() {
         setup();
 
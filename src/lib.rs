@@ -1,11 +1,21 @@
-use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 use base64::{prelude::BASE64_STANDARD, Engine};
 use bytes::Bytes;
 use defined_column::{AddDefinedColumnOperation, AddDefinedColumnRequest, DeleteDefinedColumnOperation, DeleteDefinedColumnRequest};
-use error::OtsError;
+use error::{ApiErrorCode, OtsError};
 use index::{CreateIndexOperation, CreateIndexRequest, DropIndexOperation};
-use lastpoint_index::{CreateTimeseriesLastpointIndexOperation, CreateTimeseriesLastpointIndexRequest, DeleteTimeseriesLastpointIndexOperation};
+use lastpoint_index::{
+    CreateTimeseriesLastpointIndexOperation, CreateTimeseriesLastpointIndexRequest, DeleteTimeseriesLastpointIndexOperation,
+    ListTimeseriesLastpointIndexOperation,
+};
+use model::{ColumnValue, PrimaryKey, Row};
 use prost::Message;
 use protos::search::{CreateSearchIndexRequest, UpdateSearchIndexRequest};
 use reqwest::{
@@ -19,28 +29,34 @@ use analytical_store::{
     UpdateTimeseriesAnalyticalStoreRequest,
 };
 use data::{
-    BatchGetRowOperation, BatchGetRowRequest, BatchWriteRowOperation, BatchWriteRowRequest, BulkExportOperation, BulkExportRequest, BulkImportOperation,
-    BulkImportRequest, DeleteRowOperation, DeleteRowRequest, GetRangeOperation, GetRangeRequest, GetRowOperation, GetRowRequest, PutRowOperation,
-    PutRowRequest, UpdateRowOperation, UpdateRowRequest,
+    BatchGetRowOperation, BatchGetRowRequest, BatchWriteRowChunkedOperation, BatchWriteRowOperation, BatchWriteRowRequest, BatchWriteRowResponse,
+    BulkExportOperation, BulkExportRequest, BulkImportAllResponse, BulkImportOperation, BulkImportRequest, DeleteRangeOperation, DeleteRowOperation,
+    DeleteRowRequest, GetRangeOperation, GetRangeRequest, GetRowOperation, GetRowRequest, PutRowOperation, PutRowRequest, UpdateRowOperation, UpdateRowRequest,
 };
 use search::{
     ComputeSplitsOperation, CreateSearchIndexOperation, DeleteSearchIndexOperation, DescribeSearchIndexOperation, ListSearchIndexOperation,
-    ParallelScanOperation, ParallelScanRequest, SearchOperation, SearchRequest, UpdateSearchIndexOperation,
+    ParallelScanOperation, ParallelScanRequest, ScanQuery, SearchOperation, SearchRequest, UpdateSearchIndexOperation,
 };
 use sql::{SqlQueryOperation, SqlQueryRequest};
+use stream::{
+    DescribeStreamOperation, GetShardIteratorOperation, GetStreamRecordOperation, GetStreamRecordRequest, ListStreamOperation, ListStreamRequest, StreamReader,
+    StreamRecord,
+};
 use table::{
     ComputeSplitPointsBySizeOperation, ComputeSplitPointsBySizeRequest, CreateTableOperation, CreateTableRequest, DeleteTableOperation, DescribeTableOperation,
     ListTableOperation, UpdateTableOperation, UpdateTableRequest,
 };
 use timeseries_data::{
-    DeleteTimeseriesMetaOperation, DeleteTimeseriesMetaRequest, GetTimeseriesDataOperation, GetTimeseriesDataRequest, PutTimeseriesDataOperation,
-    PutTimeseriesDataRequest, QueryTimeseriesMetaOperation, QueryTimeseriesMetaRequest, ScanTimeseriesDataOperation, ScanTimeseriesDataRequest,
-    SplitTimeseriesScanTaskOperation, SplitTimeseriesScanTaskRequest, UpdateTimeseriesMetaOperation, UpdateTimeseriesMetaRequest,
+    BatchGetTimeseriesDataOperation, BatchGetTimeseriesDataRequest, DeleteTimeseriesMetaOperation, DeleteTimeseriesMetaRequest, GetTimeseriesDataOperation,
+    GetTimeseriesDataRequest, PutTimeseriesDataOperation, PutTimeseriesDataRequest, QueryTimeseriesMetaOperation, QueryTimeseriesMetaRequest,
+    ScanTimeseriesDataOperation, ScanTimeseriesDataRequest, SplitTimeseriesScanTaskOperation, SplitTimeseriesScanTaskRequest, UpdateTimeseriesMetaOperation,
+    UpdateTimeseriesMetaRequest,
 };
 use timeseries_table::{
     CreateTimeseriesTableOperation, CreateTimeseriesTableRequest, DeleteTimeseriesTableOperation, DescribeTimeseriesTableOperation,
     ListTimeseriesTableOperation, UpdateTimeseriesTableOperation, UpdateTimeseriesTableRequest,
 };
+use tunnel::{CreateTunnelOperation, CreateTunnelRequest, DeleteTunnelOperation, DescribeTunnelOperation, ListTunnelOperation, TunnelWorker};
 use url::Url;
 use util::{get_iso8601_date_time_string, hmac_sha256};
 
@@ -56,10 +72,12 @@ pub mod model;
 pub mod protos;
 pub mod search;
 pub mod sql;
+pub mod stream;
 pub mod table;
 pub mod timeseries_data;
 pub mod timeseries_model;
 pub mod timeseries_table;
+pub mod tunnel;
 pub mod util;
 
 #[cfg(test)]
@@ -68,6 +86,7 @@ pub mod test_util;
 const USER_AGENT: &str = "aliyun-tablestore-rs/0.1.2";
 const HEADER_API_VERSION: &str = "x-ots-apiversion";
 const HEADER_ACCESS_KEY_ID: &str = "x-ots-accesskeyid";
+const HEADER_REQUEST_ID: &str = "x-ots-requestid";
 const HEADER_CONTENT_MD5: &str = "x-ots-contentmd5";
 const HEADER_SIGNATURE: &str = "x-ots-signature";
 const HEADER_DATE: &str = "x-ots-date";
@@ -76,12 +95,14 @@ const HEADER_SIGN_REGION: &str = "x-ots-signregion";
 const HEADER_SIGN_DATE: &str = "x-ots-signdate";
 const HEADER_INSTANCE_NAME: &str = "x-ots-instancename";
 const HEADER_SIGNATURE_V4: &str = "x-ots-signaturev4";
+const HEADER_SIGNATURE_METHOD: &str = "x-ots-signaturemethod";
+const HEADER_ACCEPT_ENCODING: &str = "accept-encoding";
 
 const API_VERSION: &str = "2015-12-31";
 
 pub type OtsResult<T> = Result<T, OtsError>;
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum OtsOp {
     #[default]
     Undefined,
@@ -160,6 +181,12 @@ pub enum OtsOp {
     ListTunnel,
     DescribeTunnel,
     DeleteTunnel,
+    ConnectTunnel,
+    Heartbeat,
+    ShutdownTunnel,
+    GetCheckpoint,
+    ReadRecords,
+    Checkpoint,
 
     SQLQuery,
 }
@@ -199,6 +226,12 @@ impl Display for OtsOp {
             OtsOp::ListTunnel => "ListTunnel",
             OtsOp::DescribeTunnel => "DescribeTunnel",
             OtsOp::DeleteTunnel => "DeleteTunnel",
+            OtsOp::ConnectTunnel => "ConnectTunnel",
+            OtsOp::Heartbeat => "Heartbeat",
+            OtsOp::ShutdownTunnel => "ShutdownTunnel",
+            OtsOp::GetCheckpoint => "GetCheckpoint",
+            OtsOp::ReadRecords => "ReadRecords",
+            OtsOp::Checkpoint => "Checkpoint",
 
             OtsOp::ListStream => "ListStream",
             OtsOp::DescribeStream => "DescribeStream",
@@ -247,6 +280,23 @@ impl Display for OtsOp {
 }
 
 impl OtsOp {
+    /// 请求路径。通道服务的接口使用单独的路径，其余接口的路径就是操作名称
+    pub(crate) fn path(&self) -> String {
+        match self {
+            Self::CreateTunnel => "tunnel/create".to_string(),
+            Self::ListTunnel => "tunnel/list".to_string(),
+            Self::DescribeTunnel => "tunnel/describe".to_string(),
+            Self::DeleteTunnel => "tunnel/delete".to_string(),
+            Self::ConnectTunnel => "tunnel/connect".to_string(),
+            Self::Heartbeat => "tunnel/heartbeat".to_string(),
+            Self::ShutdownTunnel => "tunnel/shutdown".to_string(),
+            Self::GetCheckpoint => "tunnel/getcheckpoint".to_string(),
+            Self::ReadRecords => "tunnel/readrecords".to_string(),
+            Self::Checkpoint => "tunnel/checkpoint".to_string(),
+            _ => self.to_string(),
+        }
+    }
+
     /// 检测一个操作是否是幂等的
     pub fn is_idempotent(&self) -> bool {
         matches!(
@@ -271,6 +321,8 @@ impl OtsOp {
                 | Self::ComputeSplits
                 | Self::ListTunnel
                 | Self::DescribeTunnel
+                | Self::GetCheckpoint
+                | Self::ReadRecords
                 | Self::SQLQuery
         )
     }
@@ -281,6 +333,131 @@ pub struct OtsRequestOptions {
     pub timeout_ms: Option<u64>,
 }
 
+/// V2 签名使用的算法
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+    /// HMAC-SHA1，不发送 `x-ots-signaturemethod` 请求头，和以前的版本保持一致
+    #[default]
+    HmacSha1,
+
+    /// HMAC-SHA256，请求头 `x-ots-signaturemethod` 为 `HmacSHA256`
+    HmacSha256,
+}
+
+impl SignatureAlgorithm {
+    /// 放在 `x-ots-signaturemethod` 请求头中的算法名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HmacSha1 => "HmacSHA1",
+            Self::HmacSha256 => "HmacSHA256",
+        }
+    }
+
+    /// 对待签名字符串签名，返回 base64 编码的签名
+    pub(crate) fn sign(&self, key: &[u8], string_to_sign: &[u8]) -> String {
+        let sig = match self {
+            Self::HmacSha1 => util::hmac_sha1(key, string_to_sign),
+            Self::HmacSha256 => util::hmac_sha256(key, string_to_sign),
+        };
+
+        BASE64_STANDARD.encode(sig)
+    }
+}
+
+/// 客户端级别的配置项
+#[derive(Debug, Default, Clone)]
+pub struct OtsClientOptions {
+    /// 是否在 debug 日志中输出请求体和出错时的响应体的字节内容，默认为 `false`。
+    ///
+    /// 请求体中包含完整的行数据，可能含有敏感信息，并且逐字节输出日志的开销很大，
+    /// 所以仅建议在排查问题时临时打开。操作名称、状态码以及重试相关的日志不受这个选项影响。
+    pub log_bodies: bool,
+
+    /// 是否在客户端缓存表结构，默认为 `false`。
+    ///
+    /// 开启之后，`GetRow` 和 `GetRange` 在发送请求之前会检查 `columns_to_get` 中的列是否为主键列或者预定义列，
+    /// 如果不是，会输出一条 warn 级别的日志，用来发现拼写错误的列名。检查不会阻止请求发送。
+    /// 同时，读写单行、批量读写以及范围读取的请求在发送之前会按照表结构中声明的顺序重新排列主键列，
+    /// 如果缺少主键列或者包含不存在的主键列，直接返回错误。
+    /// 表结构在第一次用到时通过 `DescribeTable` 获取，表结构变更后可以调用 [`OtsClient::invalidate_table_schema`] 清除缓存。
+    pub cache_schema: bool,
+
+    /// 是否在发送之前按照表结构校验主键，默认为 `false`。
+    ///
+    /// 开启之后，读写单行、批量读写以及范围读取的请求在发送之前会检查主键列的个数、名称、顺序以及值的类型是否和表结构一致，
+    /// 不一致时直接返回 [`OtsError::ValidationFailed`]，不会发送请求。和 `cache_schema` 一样，表结构在第一次用到时通过 `DescribeTable` 获取并缓存。
+    /// 同时开启 `cache_schema` 的时候，先按照表结构重新排列主键列，再进行校验
+    pub validate_against_schema: bool,
+
+    /// 全局的请求超时时间，单位为毫秒。默认为 `None`，即使用 HTTP 客户端自身的超时设置
+    pub timeout_ms: Option<u64>,
+
+    /// 针对某个操作的超时时间，单位为毫秒。例如可以给 `CreateSearchIndex` 设置较长的超时时间，同时让 `GetRow` 保持较短的超时时间。
+    ///
+    /// 超时时间的优先级为：单次请求通过操作的 `timeout_ms` 方法设置的超时时间 > 这里针对操作设置的超时时间 > 全局的 `timeout_ms`
+    pub operation_timeouts_ms: HashMap<OtsOp, u64>,
+
+    /// 停机信号。默认为 `None`。
+    ///
+    /// 发送端的值变成 `true` 之后，正在等待重试的请求会立即结束等待，返回最后一次请求的错误，不再重试，
+    /// 避免进程退出时被较长的重试间隔阻塞。发送端被丢弃时视为不会停机。已经发出的 HTTP 请求不受影响。
+    ///
+    /// ```ignore
+    /// let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    /// let client = OtsClient::builder(ak_id, ak_sec).endpoint(endpoint).shutdown_signal(shutdown_rx).build()?;
+    ///
+    /// // 收到退出信号的时候
+    /// shutdown_tx.send(true).unwrap();
+    /// ```
+    pub shutdown_signal: Option<tokio::sync::watch::Receiver<bool>>,
+
+    /// 签名算法，默认为 [`SignatureAlgorithm::HmacSha1`]
+    pub signature_algorithm: SignatureAlgorithm,
+
+    /// 一次请求（包括全部重试和重试之间的等待）总共允许花费的时间，单位为毫秒。默认为 `None`，即只受重试策略的限制。
+    ///
+    /// 如果等待下一次重试之后会超过这个时间，不再重试，直接返回最后一次请求的错误
+    pub max_total_elapsed_ms: Option<u64>,
+
+    /// 是否请求服务端压缩响应体，默认为 `false`。
+    ///
+    /// 开启之后请求头中带上 `Accept-Encoding: gzip, deflate`，响应体在解码 protobuf 之前自动解压，适合通过较慢的网络读取大量数据。
+    /// 使用自定义的 HTTP 客户端时，需要保证客户端没有关闭 gzip 和 deflate 解压。
+    /// 签名中的 `x-ots-contentmd5` 是根据请求体计算的，不受这个选项影响
+    pub accept_compression: bool,
+
+    /// 是否完整校验响应中 plain buffer 数据的 CRC8 校验码，默认为 `false`。
+    ///
+    /// 解码单元格时总是会校验单元格的校验码，但是单行的数据读取到行校验码标记时就结束了，不会校验行校验码。
+    /// 开启之后，`GetRow`、`BatchGetRow` 和 `Search` 返回的每一行在解码的同时也会校验行校验码，
+    /// 不一致时返回 [`OtsError::ChecksumMismatch`]，可以尽早发现被截断或者损坏的响应。
+    /// `GetRange` 和 `BulkExport` 逐行解码时总是校验行校验码，不受这个选项影响
+    pub verify_checksums: bool,
+}
+
+impl OtsClientOptions {
+    /// 等待重试间隔。如果在等待期间收到停机信号，立即返回 `true`
+    pub(crate) async fn sleep_or_shutdown(&self, delay_ms: u64) -> bool {
+        util::sleep_or_shutdown(self.shutdown_signal.as_ref(), delay_ms).await
+    }
+
+    /// 请求头 `Accept-Encoding` 的值。不压缩时明确要求 `identity`，避免 HTTP 客户端自动请求压缩
+    pub(crate) fn accept_encoding(&self) -> &'static str {
+        if self.accept_compression {
+            "gzip, deflate"
+        } else {
+            "identity"
+        }
+    }
+
+    /// 计算一次请求最终使用的超时时间
+    pub(crate) fn effective_timeout_ms(&self, operation: OtsOp, request_timeout_ms: Option<u64>) -> Option<u64> {
+        request_timeout_ms
+            .or_else(|| self.operation_timeouts_ms.get(&operation).copied())
+            .or(self.timeout_ms)
+    }
+}
+
 /// OTS API 请求结构体
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -311,8 +488,9 @@ pub trait RetryPolicy: std::fmt::Debug + Send + Sync {
     /// 是否需要重试。参数分别表示重试次数、操作和发生的错误
     fn should_retry(&self, retried: u32, op: OtsOp, ots_error: &OtsError) -> bool;
 
-    /// 如果需要重试，重试之前让线程等待的时间
-    fn delay_ms(&self) -> u32;
+    /// 如果需要重试，重试之前让线程等待的时间。参数分别表示已经重试的次数（第一次重试之前为 `0`）、操作和发生的错误，
+    /// 可以根据错误码调整等待时间，例如遇到 `OTSServerBusy` 的时候等待更长的时间
+    fn delay_ms(&self, retried: u32, op: OtsOp, ots_error: &OtsError) -> u32;
 
     /// 需要自行实现克隆逻辑。一般来说就是需要重置一些记录参数，为下一次全新的请求做准备
     fn clone_box(&self) -> Box<dyn RetryPolicy>;
@@ -339,19 +517,28 @@ impl Default for DefaultRetryPolicy {
 
 impl DefaultRetryPolicy {
     /// 无论是什么操作，只要是这些错误码，就重试
-    const RETRY_NO_MATTER_ACTIONS_ERR_CODES: &[&'static str] = &[
-        "OTSRowOperationConflict",
-        "OTSNotEnoughCapacityUnit",
-        "OTSTableNotReady",
-        "OTSPartitionUnavailable",
-        "OTSServerBusy",
+    const RETRY_NO_MATTER_ACTIONS_ERR_CODES: &[ApiErrorCode] = &[
+        ApiErrorCode::RowOperationConflict,
+        ApiErrorCode::NotEnoughCapacityUnit,
+        ApiErrorCode::TableNotReady,
+        ApiErrorCode::PartitionUnavailable,
+        ApiErrorCode::ServerBusy,
     ];
 
     const ERR_OTS_QUOTA_EXHAUSTED_MSG: &str = "Too frequent table operations.";
 
     // 仅针对幂等的操作，如果遇到这些错误码，重试
-    const RETRY_FOR_IDEMPOTENT_ACTIONS_ERR_CODES: &[&'static str] =
-        &["OTSTimeout", "OTSInternalServerError", "OTSServerUnavailable", "OTSTunnelServerUnavailable"];
+    const RETRY_FOR_IDEMPOTENT_ACTIONS_ERR_CODES: &[ApiErrorCode] = &[
+        ApiErrorCode::Timeout,
+        ApiErrorCode::InternalServerError,
+        ApiErrorCode::ServerUnavailable,
+        ApiErrorCode::TunnelServerUnavailable,
+    ];
+
+    /// 无论是什么操作都重试的错误码
+    pub(crate) fn is_retriable_code(code: &ApiErrorCode) -> bool {
+        Self::RETRY_NO_MATTER_ACTIONS_ERR_CODES.contains(code)
+    }
 
     fn should_retry_inner(&self, retried: u32, op: OtsOp, ots_error: &OtsError) -> bool {
         if retried >= self.max_retry_times {
@@ -364,19 +551,20 @@ impl DefaultRetryPolicy {
             OtsError::ReqwestError(_) => true,
 
             // 5xx 的状态码 + 幂等操作，重试
-            OtsError::StatusError(code, _) => code.is_server_error() && op.is_idempotent(),
+            OtsError::StatusError(code, _, _) => code.is_server_error() && op.is_idempotent(),
 
             // API 错误， OTSQuotaExhausted 错误码 + 固定的错误消息，重试
-            OtsError::ApiError(api_error)
-                if api_error.code == "OTSQuotaExhausted" && api_error.message == Some(Self::ERR_OTS_QUOTA_EXHAUSTED_MSG.to_string()) =>
+            OtsError::ApiError(api_error, _)
+                if ots_error.code() == Some(ApiErrorCode::QuotaExhausted) && api_error.message == Some(Self::ERR_OTS_QUOTA_EXHAUSTED_MSG.to_string()) =>
             {
                 true
             }
 
             // 其他的就是无论什么操作都重试的错误，以及幂等操作对应的错误码
-            OtsError::ApiError(api_error) => {
-                (Self::RETRY_NO_MATTER_ACTIONS_ERR_CODES.contains(&api_error.code.as_str()))
-                    || (op.is_idempotent() && Self::RETRY_FOR_IDEMPOTENT_ACTIONS_ERR_CODES.contains(&api_error.code.as_str()))
+            OtsError::ApiError(api_error, _) => {
+                let code = ApiErrorCode::from(api_error.code.as_str());
+
+                Self::is_retriable_code(&code) || (op.is_idempotent() && Self::RETRY_FOR_IDEMPOTENT_ACTIONS_ERR_CODES.contains(&code))
             }
 
             _ => false,
@@ -393,29 +581,179 @@ impl RetryPolicy for DefaultRetryPolicy {
         Box::new(DefaultRetryPolicy::default())
     }
 
-    fn delay_ms(&self) -> u32 {
+    fn delay_ms(&self, _retried: u32, _op: OtsOp, _ots_error: &OtsError) -> u32 {
         10000
     }
 }
 
+/// 指数退避重试机制。重试的条件和 [`DefaultRetryPolicy`] 相同，
+/// 第 `n` 次重试之前（`n` 从 `0` 开始）等待 `base_delay_ms * 2^n` 毫秒，最多等待 `max_delay_ms` 毫秒。
+///
+/// 开启抖动（jitter）之后，实际等待的时间是 `0` 到上面计算出的时间之间的随机值，避免大量客户端同时重试。
+///
+/// # Examples
+///
+/// ```ignore
+/// let policy = ExponentialBackoffRetryPolicy::new().base_delay_ms(200).max_delay_ms(5000).jitter(true);
+/// let client = OtsClient::builder("aid", "asec").endpoint(endpoint).rety_policy(Box::new(policy)).build()?;
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ExponentialBackoffRetryPolicy {
+    /// 最多重试的次数，默认为 `10`
+    pub max_retry_times: u32,
+
+    /// 第一次重试之前等待的时间，默认为 `100` 毫秒
+    pub base_delay_ms: u32,
+
+    /// 最长的等待时间，默认为 `10000` 毫秒
+    pub max_delay_ms: u32,
+
+    /// 是否开启抖动，默认为 `false`
+    pub jitter: bool,
+}
+
+impl Default for ExponentialBackoffRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retry_times: 10,
+            base_delay_ms: 100,
+            max_delay_ms: 10000,
+            jitter: false,
+        }
+    }
+}
+
+impl ExponentialBackoffRetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置最多重试的次数
+    pub fn max_retry_times(mut self, max_retry_times: u32) -> Self {
+        self.max_retry_times = max_retry_times;
+
+        self
+    }
+
+    /// 设置第一次重试之前等待的时间
+    pub fn base_delay_ms(mut self, base_delay_ms: u32) -> Self {
+        self.base_delay_ms = base_delay_ms;
+
+        self
+    }
+
+    /// 设置最长的等待时间
+    pub fn max_delay_ms(mut self, max_delay_ms: u32) -> Self {
+        self.max_delay_ms = max_delay_ms;
+
+        self
+    }
+
+    /// 设置是否开启抖动
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+
+        self
+    }
+
+    /// 不考虑抖动时，第 `retried` 次重试之前等待的时间
+    pub(crate) fn backoff_ms(&self, retried: u32) -> u32 {
+        let factor = 1u32.checked_shl(retried).unwrap_or(u32::MAX);
+
+        self.base_delay_ms.saturating_mul(factor).min(self.max_delay_ms)
+    }
+}
+
+impl RetryPolicy for ExponentialBackoffRetryPolicy {
+    fn should_retry(&self, retried: u32, op: OtsOp, ots_error: &OtsError) -> bool {
+        DefaultRetryPolicy {
+            max_retry_times: self.max_retry_times,
+        }
+        .should_retry_inner(retried, op, ots_error)
+    }
+
+    fn clone_box(&self) -> Box<dyn RetryPolicy> {
+        Box::new(*self)
+    }
+
+    fn delay_ms(&self, retried: u32, _op: OtsOp, _ots_error: &OtsError) -> u32 {
+        let delay = self.backoff_ms(retried);
+
+        if self.jitter {
+            rand::random_range(0..=delay)
+        } else {
+            delay
+        }
+    }
+}
+
+/// 凭证提供者，每次发送请求（包括重试）签名之前都会调用 `credentials` 获取凭证。
+///
+/// 使用 STS 临时凭证的长期运行的客户端可以实现这个 trait，在凭证过期之前自行刷新
+pub trait CredentialProvider: std::fmt::Debug + Send + Sync {
+    /// 返回 Access key id、Access key secret 和可选的 STS Token
+    fn credentials(&self) -> (String, String, Option<String>);
+}
+
+/// 固定凭证，不会刷新。使用 Access key id 和 Access key secret 构建客户端时默认使用这个凭证提供者
+#[derive(Clone)]
+pub struct StaticCredentialProvider {
+    access_key_id: String,
+    access_key_secret: String,
+    sts_token: Option<String>,
+}
+
+impl StaticCredentialProvider {
+    pub fn new(ak_id: impl AsRef<str>, ak_sec: impl AsRef<str>) -> Self {
+        Self {
+            access_key_id: ak_id.as_ref().to_string(),
+            access_key_secret: ak_sec.as_ref().to_string(),
+            sts_token: None,
+        }
+    }
+
+    /// 设置 STS Token
+    pub fn sts_token(mut self, token: impl AsRef<str>) -> Self {
+        self.sts_token = Some(token.as_ref().to_string());
+
+        self
+    }
+}
+
+impl std::fmt::Debug for StaticCredentialProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaticCredentialProvider")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialProvider for StaticCredentialProvider {
+    fn credentials(&self) -> (String, String, Option<String>) {
+        (self.access_key_id.clone(), self.access_key_secret.clone(), self.sts_token.clone())
+    }
+}
+
 /// 客户端构建器
 ///
 /// # Examples
 ///
 /// ```
-/// let builder = OtsClient.builder("aid", "asec").endpoint("").instance_name("");
-/// let client = builder.build();
+/// let builder = OtsClient::builder("aid", "asec").endpoint("https://instance-name.cn-beijing.ots.aliyuncs.com");
+/// let client = builder.build()?;
 /// ```
 #[derive(Clone)]
 pub struct OtsClientBuilder {
     access_key_id: String,
     access_key_secret: String,
     sts_token: Option<String>,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
     retry_policy: Box<dyn RetryPolicy>,
     region: String,
     instance_name: String,
     endpoint: String,
     http_client: Option<reqwest::Client>,
+    options: OtsClientOptions,
 }
 
 impl OtsClientBuilder {
@@ -425,13 +763,29 @@ impl OtsClientBuilder {
             access_key_secret: ak_sec.as_ref().to_string(),
             retry_policy: Box::new(DefaultRetryPolicy::default()),
             sts_token: None,
+            credential_provider: None,
             region: String::new(),
             instance_name: String::new(),
             endpoint: String::new(),
             http_client: None,
+            options: OtsClientOptions::default(),
         }
     }
 
+    /// 设置 Access key id
+    pub fn access_key_id(mut self, ak_id: impl AsRef<str>) -> Self {
+        self.access_key_id = ak_id.as_ref().to_string();
+
+        self
+    }
+
+    /// 设置 Access key secret
+    pub fn access_key_secret(mut self, ak_sec: impl AsRef<str>) -> Self {
+        self.access_key_secret = ak_sec.as_ref().to_string();
+
+        self
+    }
+
     /// 设置 STS Token
     pub fn sts_token(mut self, token: impl AsRef<str>) -> Self {
         self.sts_token = Some(token.as_ref().to_string());
@@ -439,6 +793,13 @@ impl OtsClientBuilder {
         self
     }
 
+    /// 设置凭证提供者。设置之后，忽略通过 `access_key_id`、`access_key_secret` 和 `sts_token` 设置的凭证
+    pub fn credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credential_provider = Some(Arc::new(provider));
+
+        self
+    }
+
     /// 设置重试策略
     pub fn rety_policy(mut self, policy: Box<dyn RetryPolicy>) -> Self {
         self.retry_policy = policy;
@@ -474,33 +835,153 @@ impl OtsClientBuilder {
         self
     }
 
+    /// 设置客户端配置项
+    pub fn options(mut self, options: OtsClientOptions) -> Self {
+        self.options = options;
+
+        self
+    }
+
+    /// 设置是否在 debug 日志中输出请求体和响应体的字节内容
+    pub fn log_bodies(mut self, log_bodies: bool) -> Self {
+        self.options.log_bodies = log_bodies;
+
+        self
+    }
+
+    /// 设置是否在客户端缓存表结构
+    pub fn cache_schema(mut self, cache_schema: bool) -> Self {
+        self.options.cache_schema = cache_schema;
+
+        self
+    }
+
+    /// 设置是否在发送之前按照表结构校验主键，见 [`OtsClientOptions::validate_against_schema`]
+    pub fn validate_against_schema(mut self, validate_against_schema: bool) -> Self {
+        self.options.validate_against_schema = validate_against_schema;
+
+        self
+    }
+
+    /// 设置签名算法
+    pub fn signature_algorithm(mut self, algorithm: SignatureAlgorithm) -> Self {
+        self.options.signature_algorithm = algorithm;
+
+        self
+    }
+
+    /// 设置全局的请求超时时间，单位为毫秒
+    pub fn timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.options.timeout_ms = Some(timeout_ms);
+
+        self
+    }
+
+    /// 设置某个操作的请求超时时间，单位为毫秒
+    pub fn operation_timeout_ms(mut self, operation: OtsOp, timeout_ms: u64) -> Self {
+        self.options.operation_timeouts_ms.insert(operation, timeout_ms);
+
+        self
+    }
+
+    /// 设置是否请求服务端压缩响应体，见 [`OtsClientOptions::accept_compression`]
+    pub fn accept_compression(mut self, accept_compression: bool) -> Self {
+        self.options.accept_compression = accept_compression;
+
+        self
+    }
+
+    /// 设置是否完整校验响应数据的校验码，见 [`OtsClientOptions::verify_checksums`]
+    pub fn verify_checksums(mut self, verify_checksums: bool) -> Self {
+        self.options.verify_checksums = verify_checksums;
+
+        self
+    }
+
+    /// 设置一次请求总共允许花费的时间，单位为毫秒，见 [`OtsClientOptions::max_total_elapsed_ms`]
+    pub fn max_total_elapsed_ms(mut self, ms: u64) -> Self {
+        self.options.max_total_elapsed_ms = Some(ms);
+
+        self
+    }
+
+    /// 设置停机信号，见 [`OtsClientOptions::shutdown_signal`]
+    pub fn shutdown_signal(mut self, shutdown_signal: tokio::sync::watch::Receiver<bool>) -> Self {
+        self.options.shutdown_signal = Some(shutdown_signal);
+
+        self
+    }
+
     /// 获取重试策略的可写引用
     pub fn retry_policy_mut(&mut self) -> &mut Box<dyn RetryPolicy> {
         &mut self.retry_policy
     }
 
-    pub fn build(self) -> OtsClient {
+    /// 构建客户端。
+    ///
+    /// 没有设置地域和实例名称的时候，从 endpoint 中解析。
+    /// 没有设置凭证提供者时 Access key id、Access key secret 为空，或者 endpoint 不是合法的 `http`/`https` 地址的时候，返回错误
+    pub fn build(self) -> OtsResult<OtsClient> {
         let Self {
             access_key_id,
             access_key_secret,
             sts_token,
+            credential_provider,
             retry_policy,
             region,
             instance_name,
             endpoint,
             http_client,
+            options,
         } = self;
 
-        OtsClient {
-            access_key_id,
-            access_key_secret,
-            sts_token,
+        let credential_provider = match credential_provider {
+            Some(provider) => provider,
+            None => {
+                if access_key_id.is_empty() {
+                    return Err(OtsError::ValidationFailed("invalid access key id: can not be empty".to_string()));
+                }
+
+                if access_key_secret.is_empty() {
+                    return Err(OtsError::ValidationFailed("invalid access key secret: can not be empty".to_string()));
+                }
+
+                Arc::new(StaticCredentialProvider {
+                    access_key_id,
+                    access_key_secret,
+                    sts_token,
+                })
+            }
+        };
+
+        let endpoint = endpoint.to_lowercase();
+
+        match Url::parse(&endpoint) {
+            Ok(url) if (url.scheme() == "http" || url.scheme() == "https") && url.host_str().is_some_and(|h| !h.is_empty()) => {}
+            _ => return Err(OtsError::ValidationFailed(format!("invalid endpoint: {}", endpoint))),
+        }
+
+        let (region, instance_name) = if region.is_empty() || instance_name.is_empty() {
+            let (parsed_instance_name, parsed_region) = OtsClient::parse_instance_and_region(&endpoint)?;
+
+            (
+                if region.is_empty() { parsed_region } else { region },
+                if instance_name.is_empty() { parsed_instance_name } else { instance_name },
+            )
+        } else {
+            (region, instance_name)
+        };
+
+        Ok(OtsClient {
+            credential_provider,
             region,
             instance_name,
-            endpoint,
-            http_client: http_client.unwrap_or(reqwest::Client::new()),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            http_client: http_client.unwrap_or_default(),
             retry_policy,
-        }
+            options,
+            schema_cache: Arc::new(RwLock::new(HashMap::new())),
+        })
     }
 }
 
@@ -508,38 +989,72 @@ impl OtsClientBuilder {
 #[allow(dead_code)]
 #[derive(Clone)]
 pub struct OtsClient {
-    access_key_id: String,
-    access_key_secret: String,
-    sts_token: Option<String>,
+    credential_provider: Arc<dyn CredentialProvider>,
     region: String,
     instance_name: String,
     endpoint: String,
     http_client: reqwest::Client,
     retry_policy: Box<dyn RetryPolicy>,
+    options: OtsClientOptions,
+    schema_cache: Arc<RwLock<HashMap<String, protos::TableMeta>>>,
 }
 
 impl std::fmt::Debug for OtsClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OtsClient")
-            .field("access_key_id", &self.access_key_id)
+            .field("credential_provider", &self.credential_provider)
             .field("region", &self.region)
             .field("instance_name", &self.instance_name)
             .field("endpoint", &self.endpoint)
             .field("http_client", &self.http_client)
+            .field("options", &self.options)
             .finish()
     }
 }
 
 impl OtsClient {
-    fn parse_instance_and_region(endpoint: &str) -> (&str, &str) {
-        let s = endpoint.strip_prefix("http://").unwrap_or(endpoint);
-        let s = s.strip_prefix("https://").unwrap_or(s);
-        let parts = s.split(".").collect::<Vec<_>>();
-        if parts.len() < 2 {
-            panic!("can not parse instance name and region from endpoint: {}", endpoint);
+    /// 从服务地址中解析实例名称和地域，服务地址的格式为 `https://${instance-name}.${region}.ots.aliyuncs.com`。
+    ///
+    /// 服务地址的主机是 IP 地址或者只有一级（例如 `localhost`）的时候无法解析，
+    /// 需要使用 [`OtsClientBuilder`] 显式设置实例名称和地域
+    fn parse_instance_and_region(endpoint: &str) -> OtsResult<(String, String)> {
+        let url = match Url::parse(endpoint) {
+            Ok(url) => url,
+            Err(_) => {
+                return Err(OtsError::ValidationFailed(format!(
+                    "can not parse instance name and region from endpoint: {}",
+                    endpoint
+                )))
+            }
+        };
+
+        match url.host() {
+            Some(url::Host::Domain(host)) => {
+                let parts = host.split('.').collect::<Vec<_>>();
+
+                if parts.len() < 2 || parts[0].is_empty() || parts[1].is_empty() {
+                    return Err(OtsError::ValidationFailed(format!(
+                        "can not parse instance name and region from endpoint: {}, set instance name and region explicitly",
+                        endpoint
+                    )));
+                }
+
+                Ok((parts[0].to_string(), parts[1].to_string()))
+            }
+            Some(_) => Err(OtsError::ValidationFailed(format!(
+                "can not parse instance name and region from endpoint: {}, the host is an IP address, set instance name and region explicitly",
+                endpoint
+            ))),
+            None => Err(OtsError::ValidationFailed(format!(
+                "can not parse instance name and region from endpoint: {}",
+                endpoint
+            ))),
         }
+    }
 
-        (parts[0], parts[1])
+    /// 读取一个必须设置的环境变量
+    fn required_env_var(name: &str) -> OtsResult<String> {
+        std::env::var(name).map_err(|e| OtsError::ValidationFailed(format!("env var {} is invalid: {}", name, e)))
     }
 
     /// Build an OtsClient from env values. The following env vars are required:
@@ -547,22 +1062,35 @@ impl OtsClient {
     /// - `ALIYUN_OTS_AK_ID`: The access key id.
     /// - `ALIYUN_OTS_AK_SEC`: The access key secret
     /// - `ALIYUN_OTS_ENDPOINT`: The tablestore instance endpoint. e.g. `https://${instance-name}.cn-beijing.ots.aliyuncs.com`
-    pub fn from_env() -> Self {
-        let access_key_id = std::env::var("ALIYUN_OTS_AK_ID").expect("env var ALI_ACCESS_KEY_ID is missing");
-        let access_key_secret = std::env::var("ALIYUN_OTS_AK_SEC").expect("env var ALI_ACCESS_KEY_SECRET is missing");
-        let endpoint = std::env::var("ALIYUN_OTS_ENDPOINT").expect("env var ALI_OSS_ENDPOINT is missing");
-        let endpoint = endpoint.to_lowercase();
-        let (instance_name, region) = Self::parse_instance_and_region(endpoint.as_str());
-
-        Self {
-            access_key_id,
-            access_key_secret,
-            sts_token: None,
-            region: region.to_string(),
-            instance_name: instance_name.to_string(),
+    ///
+    /// Returns `OtsError::ValidationFailed` if any env var is missing or the endpoint can not be parsed.
+    pub fn try_from_env() -> OtsResult<Self> {
+        let access_key_id = Self::required_env_var("ALIYUN_OTS_AK_ID")?;
+        let access_key_secret = Self::required_env_var("ALIYUN_OTS_AK_SEC")?;
+        let endpoint = Self::required_env_var("ALIYUN_OTS_ENDPOINT")?.to_lowercase();
+        let (instance_name, region) = Self::parse_instance_and_region(endpoint.as_str())?;
+
+        Ok(Self {
+            credential_provider: Arc::new(StaticCredentialProvider::new(access_key_id, access_key_secret)),
+            region,
+            instance_name,
             endpoint,
             http_client: reqwest::Client::new(),
             retry_policy: Box::new(DefaultRetryPolicy::default()),
+            options: OtsClientOptions::default(),
+            schema_cache: Arc::new(RwLock::new(HashMap::new())),
+        })
+    }
+
+    /// Build an OtsClient from env values. See [`OtsClient::try_from_env`] for the required env vars.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any env var is missing or the endpoint can not be parsed.
+    pub fn from_env() -> Self {
+        match Self::try_from_env() {
+            Ok(client) => client,
+            Err(e) => panic!("{}", e),
         }
     }
 
@@ -576,20 +1104,78 @@ impl OtsClient {
     pub fn new(ak_id: impl AsRef<str>, ak_sec: impl AsRef<str>, endpoint: impl AsRef<str>) -> Self {
         let endpoint = endpoint.as_ref().to_lowercase();
 
-        let (instance_name, region) = Self::parse_instance_and_region(endpoint.as_str());
+        let (instance_name, region) = match Self::parse_instance_and_region(endpoint.as_str()) {
+            Ok(v) => v,
+            Err(e) => panic!("{}", e),
+        };
 
         Self {
-            access_key_id: ak_id.as_ref().to_string(),
-            access_key_secret: ak_sec.as_ref().to_string(),
-            region: region.to_string(),
-            instance_name: instance_name.to_string(),
+            credential_provider: Arc::new(StaticCredentialProvider::new(ak_id, ak_sec)),
+            region,
+            instance_name,
             endpoint,
             http_client: reqwest::Client::new(),
-            sts_token: None,
             retry_policy: Box::new(DefaultRetryPolicy::default()),
+            options: OtsClientOptions::default(),
+            schema_cache: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// 获取客户端配置项
+    pub fn options(&self) -> &OtsClientOptions {
+        &self.options
+    }
+
+    /// 清除缓存的表结构。在表结构发生变化（例如添加或者删除了预定义列）之后调用
+    pub fn invalidate_table_schema(&self, table_name: &str) {
+        if let Ok(mut cache) = self.schema_cache.write() {
+            cache.remove(table_name);
+        }
+    }
+
+    /// 在开启了表结构缓存或者按照表结构校验的情况下，获取表结构。都未开启，或者获取表结构失败的时候返回 `None`
+    pub(crate) async fn cached_table_meta(&self, table_name: &str) -> Option<protos::TableMeta> {
+        if !self.options.cache_schema && !self.options.validate_against_schema {
+            return None;
+        }
+
+        if let Some(meta) = self.schema_cache.read().ok().and_then(|cache| cache.get(table_name).cloned()) {
+            return Some(meta);
+        }
+
+        match self.describe_table(table_name).send().await {
+            Ok(resp) => {
+                if let Ok(mut cache) = self.schema_cache.write() {
+                    cache.insert(table_name.to_string(), resp.table_meta.clone());
+                }
+
+                Some(resp.table_meta)
+            }
+            Err(e) => {
+                log::warn!("can not describe table {} to cache its schema: {}", table_name, e);
+                None
+            }
+        }
+    }
+
+    /// 开启了表结构缓存时，按照表结构中声明的顺序重新排列主键列；开启了按照表结构校验时，校验主键列。
+    /// 都没有开启或者获取表结构失败时不做任何处理
+    pub(crate) async fn prepare_primary_keys<'a>(&self, table_name: &str, primary_keys: impl IntoIterator<Item = &'a mut PrimaryKey>) -> OtsResult<()> {
+        if let Some(table_meta) = self.cached_table_meta(table_name).await {
+            for pk in primary_keys {
+                if self.options.cache_schema {
+                    model::rules::reorder_primary_key(&table_meta, pk)?;
+                }
+
+                if self.options.validate_against_schema {
+                    model::rules::validate_primary_key(&table_meta, pk)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// 客户端构建器
     ///
     /// # Arguments
@@ -604,17 +1190,24 @@ impl OtsClient {
     fn fill_signature_v2(&self, operation: &str, headers: &mut HashMap<String, String>) {
         let date_time_string = get_iso8601_date_time_string();
         let date = &date_time_string[..10].replace("-", "");
+        let (access_key_id, access_key_secret, sts_token) = self.credential_provider.credentials();
 
         headers.insert("user-agent".to_string(), USER_AGENT.to_string());
         headers.insert(HEADER_API_VERSION.to_string(), API_VERSION.to_string());
         headers.insert(HEADER_DATE.to_string(), date_time_string.clone());
         headers.insert(HEADER_SIGN_DATE.to_string(), date.to_string());
-        headers.insert(HEADER_ACCESS_KEY_ID.to_string(), self.access_key_id.clone());
+        headers.insert(HEADER_ACCESS_KEY_ID.to_string(), access_key_id);
         headers.insert(HEADER_INSTANCE_NAME.to_string(), self.instance_name.clone());
         headers.insert(HEADER_SIGN_REGION.to_string(), self.region.clone());
 
-        if let Some(s) = &self.sts_token {
-            headers.insert(HEADER_STS_TOKEN.to_string(), s.to_string());
+        if let Some(s) = sts_token {
+            headers.insert(HEADER_STS_TOKEN.to_string(), s);
+        }
+
+        let algorithm = self.options.signature_algorithm;
+
+        if algorithm != SignatureAlgorithm::HmacSha1 {
+            headers.insert(HEADER_SIGNATURE_METHOD.to_string(), algorithm.as_str().to_string());
         }
 
         let mut canonical_headers = headers
@@ -630,8 +1223,7 @@ impl OtsClient {
         let string_to_sign = format!("/{}\nPOST\n\n{}\n", operation, canonical_headers);
 
         log::debug!("string to sign: \n-----\n{}\n-----", string_to_sign);
-        let sig = util::hmac_sha1(self.access_key_secret.as_bytes(), string_to_sign.as_bytes());
-        let sig_string = BASE64_STANDARD.encode(&sig);
+        let sig_string = algorithm.sign(access_key_secret.as_bytes(), string_to_sign.as_bytes());
 
         log::debug!("signature = {}", sig_string);
 
@@ -643,17 +1235,18 @@ impl OtsClient {
     fn fill_signature_v4(&self, operation: &str, headers: &mut HashMap<String, String>) {
         let date_time_string = get_iso8601_date_time_string();
         let date_string = &date_time_string[..10].replace("-", "");
+        let (access_key_id, access_key_secret, sts_token) = self.credential_provider.credentials();
 
         headers.insert("user-agent".to_string(), USER_AGENT.to_string());
         headers.insert(HEADER_API_VERSION.to_string(), API_VERSION.to_string());
         headers.insert(HEADER_DATE.to_string(), date_time_string.clone());
         headers.insert(HEADER_SIGN_DATE.to_string(), date_string.to_string());
-        headers.insert(HEADER_ACCESS_KEY_ID.to_string(), self.access_key_id.clone());
+        headers.insert(HEADER_ACCESS_KEY_ID.to_string(), access_key_id);
         headers.insert(HEADER_INSTANCE_NAME.to_string(), self.instance_name.clone());
         headers.insert(HEADER_SIGN_REGION.to_string(), self.region.clone());
 
-        if let Some(s) = &self.sts_token {
-            headers.insert(HEADER_STS_TOKEN.to_string(), s.to_string());
+        if let Some(s) = sts_token {
+            headers.insert(HEADER_STS_TOKEN.to_string(), s);
         }
 
         let mut canonical_headers = headers
@@ -670,7 +1263,7 @@ impl OtsClient {
 
         log::debug!("string to sign: \n-----\n{}\n----", string_to_sign);
 
-        let sign = hmac_sha256(self.access_key_secret.as_bytes(), string_to_sign.as_bytes());
+        let sign = hmac_sha256(access_key_secret.as_bytes(), string_to_sign.as_bytes());
 
         headers.insert(HEADER_SIGNATURE_V4.to_string(), BASE64_STANDARD.encode(sign));
     }
@@ -686,17 +1279,30 @@ impl OtsClient {
             options,
         } = req;
 
+        let limit = model::rules::max_request_size(operation);
+
+        if body.len() > limit {
+            return Err(OtsError::RequestTooLarge { size: body.len(), limit });
+        }
+
         // 不会发生变化的请求头
         headers.insert("content-lenght".to_string(), format!("{}", body.len()));
         let content_md5_base64 = BASE64_STANDARD.encode(md5::compute(&body).as_slice());
         headers.insert(HEADER_CONTENT_MD5.to_string(), content_md5_base64);
+        headers.insert(HEADER_ACCEPT_ENCODING.to_string(), self.options.accept_encoding().to_string());
+
+        if self.options.log_bodies {
+            log::debug!(">> body of operation {}: {} bytes", operation, body.len());
+            util::debug_bytes(&body);
+        }
 
-        let url = Url::parse(format!("{}/{}", self.endpoint, operation).as_str()).unwrap();
+        let url = Url::parse(format!("{}/{}", self.endpoint, operation.path()).as_str()).unwrap();
         let request_body = Bytes::from_owner(body);
         let mut retried = 0u32;
+        let started = Instant::now();
 
         loop {
-            self.fill_signature_v2(&operation.to_string(), &mut headers);
+            self.fill_signature_v2(&operation.path(), &mut headers);
 
             let mut header_map = HeaderMap::new();
             headers.iter().for_each(|(k, v)| {
@@ -711,7 +1317,7 @@ impl OtsClient {
                 .body(request_body.clone());
 
             // Handle per-request options
-            if let Some(ms) = options.timeout_ms {
+            if let Some(ms) = self.options.effective_timeout_ms(operation, options.timeout_ms) {
                 request_builder = request_builder.timeout(Duration::from_millis(ms));
             }
 
@@ -727,16 +1333,27 @@ impl OtsClient {
 
             if !&response.status().is_success() {
                 let status = response.status();
+                let request_id = response.headers().get(HEADER_REQUEST_ID).and_then(|v| v.to_str().ok()).map(|v| v.to_string());
 
                 let e = match response.bytes().await {
                     Ok(bytes) => {
+                        if self.options.log_bodies {
+                            log::debug!("<< body of operation {}: {} bytes", operation, bytes.len());
+                            util::debug_bytes(&bytes);
+                        }
+
                         let api_error = protos::Error::decode(bytes)?;
-                        OtsError::ApiError(Box::new(api_error))
+                        OtsError::ApiError(Box::new(api_error), request_id)
                     }
-                    Err(_) => OtsError::StatusError(status, "".to_string()),
+                    Err(_) => OtsError::StatusError(status, "".to_string(), request_id),
                 };
 
-                log::error!("api call failed, check retry against retry policy for operation {} and error {}", operation, e);
+                log::error!(
+                    "api call failed with request id {:?}, check retry against retry policy for operation {} and error {}",
+                    e.request_id(),
+                    operation,
+                    e
+                );
                 let should_retry = self.retry_policy.should_retry(retried, operation, &e);
                 log::info!("should retry: {} for operation {} with error {}", should_retry, operation, e);
 
@@ -744,9 +1361,21 @@ impl OtsClient {
                     return Err(e);
                 }
 
-                let next_delay = self.retry_policy.delay_ms();
+                let next_delay = self.retry_policy.delay_ms(retried, operation, &e);
+
+                if let Some(max_ms) = self.options.max_total_elapsed_ms {
+                    if started.elapsed().as_millis() as u64 + next_delay as u64 > max_ms {
+                        log::info!("max total elapsed time {} ms will be exceeded, stop retrying operation {}", max_ms, operation);
+                        return Err(e);
+                    }
+                }
+
                 log::info!("delay for {} ms to retry", next_delay);
-                tokio::time::sleep(tokio::time::Duration::from_millis(next_delay as u64)).await;
+
+                if self.options.sleep_or_shutdown(next_delay as u64).await {
+                    log::info!("shutdown signal received, stop retrying operation {}", operation);
+                    return Err(e);
+                }
 
                 retried += 1;
             }
@@ -897,6 +1526,26 @@ impl OtsClient {
         GetRangeOperation::new(self.clone(), request)
     }
 
+    /// 并发读取全表数据。根据分割点把表划分成多个分片，最多同时读取 `concurrency` 个分片，每个分片都会自动翻页。
+    ///
+    /// 不同分片的行可能交错返回，同一个分片中的行按照主键顺序返回。读取在后台任务中进行，出错之后返回这个错误，然后结束。
+    /// 后台任务 panic 的时候，读取完已经写入通道的行之后，在读取流的任务中继续 panic，不会当成读取完毕
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let stream = client.scan_table("users", 4);
+    /// tokio::pin!(stream);
+    ///
+    /// while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+    ///     let row = row?;
+    ///     println!("{:?}", row);
+    /// }
+    /// ```
+    pub fn scan_table(&self, table_name: &str, concurrency: usize) -> impl futures_core::Stream<Item = OtsResult<Row>> {
+        data::scan_table(self.clone(), table_name, concurrency)
+    }
+
     /// 插入一行数据
     ///
     /// # Examples
@@ -942,6 +1591,33 @@ impl OtsClient {
         UpdateRowOperation::new(self.clone(), request)
     }
 
+    /// 原子地给一个整数列加上 `delta`，返回加完之后的值。常用于计数器。
+    ///
+    /// 发送一个只包含递增操作的 `UpdateRow` 请求，并使用 `ReturnType::RtAfterModify` 返回这个列修改后的值。
+    /// 行或者列不存在的时候，服务端会从 `0` 开始递增。列已经存在但是不是整数的时候，服务端会返回错误
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let pk = PrimaryKey::new().column_string("counter_id", "page_views");
+    /// let views = client.increment_column("counters", pk, "views", 1).await?;
+    /// ```
+    pub async fn increment_column(&self, table_name: &str, primary_key: PrimaryKey, column_name: &str, delta: i64) -> OtsResult<i64> {
+        let request = UpdateRowRequest::new(table_name)
+            .row(Row::new().primary_key(primary_key).column_to_increse(column_name, delta))
+            .return_type(protos::ReturnType::RtAfterModify)
+            .return_column(column_name);
+
+        let response = self.update_row(request).send().await?;
+        let value = response
+            .row
+            .as_ref()
+            .and_then(|row| row.get_column_value(column_name))
+            .unwrap_or(&ColumnValue::Null);
+
+        i64::try_from(value)
+    }
+
     /// 根据主键删除数据行
     ///
     /// # Examples
@@ -955,6 +1631,26 @@ impl OtsClient {
         DeleteRowOperation::new(self.clone(), request)
     }
 
+    /// 删除指定主键范围 `[start_pk, end_pk)` 内的全部行，返回删除的行数。
+    /// 内部通过 `GetRange` 读取主键后使用 `BatchWriteRow` 分批删除，并不是原子操作。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let response = client
+    ///     .delete_range(
+    ///         "data_types",
+    ///         PrimaryKey::new().column_inf_min("str_id"),
+    ///         PrimaryKey::new().column_info_max("str_id"),
+    ///     )
+    ///     .concurrency(4)
+    ///     .send()
+    ///     .await;
+    /// ```
+    pub fn delete_range(&self, table_name: &str, start_pk: PrimaryKey, end_pk: PrimaryKey) -> DeleteRangeOperation {
+        DeleteRangeOperation::new(self.clone(), table_name, start_pk, end_pk)
+    }
+
     /// 批量读取一个表或多个表中的若干行数据
     ///
     /// # Examples
@@ -1022,6 +1718,24 @@ impl OtsClient {
         BatchWriteRowOperation::new(self.clone(), request)
     }
 
+    /// 批量写入数据，并重试因为行操作冲突等临时性原因失败的行，最多重试 `max_retries` 次。
+    /// 见 [`BatchWriteRowOperation::send_with_retry`]
+    pub async fn batch_write_row_with_retry(&self, request: BatchWriteRowRequest, max_retries: u32) -> OtsResult<BatchWriteRowResponse> {
+        self.batch_write_row(request).send_with_retry(max_retries).await
+    }
+
+    /// 批量写入任意多行数据。按照 200 行一批自动拆分成多个 `BatchWriteRow` 请求，可以设置并发数量，返回合并之后的响应
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let resp = client.batch_write_row_chunked(request).concurrency(4).send().await?;
+    /// assert!(resp.failed_rows().is_empty());
+    /// ```
+    pub fn batch_write_row_chunked(&self, request: BatchWriteRowRequest) -> BatchWriteRowChunkedOperation {
+        BatchWriteRowChunkedOperation::new(self.clone(), request)
+    }
+
     /// 批量写入数据。写入数据时支持插入一行数据、修改行数据以及删除行数据。最多一次 200 行
     ///
     /// # Examples
@@ -1052,6 +1766,24 @@ impl OtsClient {
         BulkImportOperation::new(self.clone(), request)
     }
 
+    /// 并发批量写入任意多行数据。按照 200 行一批拆分之后最多同时发送 `concurrency` 个 `BulkImport` 请求，
+    /// 返回成功写入的行数、失败的批次和失败的行
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let resp = client.bulk_import_all(req, 4).await?;
+    /// for (batch, e) in &resp.failed_batches {
+    ///     println!("{} rows failed: {}", batch.rows.len(), e);
+    /// }
+    /// for (i, _row, e) in &resp.failed_rows {
+    ///     println!("row {} failed: {}", i, e);
+    /// }
+    /// ```
+    pub async fn bulk_import_all(&self, request: BulkImportRequest, concurrency: usize) -> OtsResult<BulkImportAllResponse> {
+        self.bulk_import(request).send_concurrently(concurrency).await
+    }
+
     /// 接口批量导出数据。
     ///
     /// # Examples
@@ -1124,11 +1856,43 @@ impl OtsClient {
         ParallelScanOperation::new(self.clone(), request)
     }
 
+    /// 使用多元索引并发导出全部符合条件的数据。先计算多元索引的并发度，然后最多同时扫描 `concurrency` 个并发 ID，每个并发 ID 都会自动翻页。
+    ///
+    /// 返回多元索引中的全部列。`scan_query` 中的 `max_parallel`、`current_parallel_id` 和 `token` 会被忽略。
+    /// 不同并发 ID 的行可能交错返回。读取在后台任务中进行，出错之后返回这个错误，然后结束
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let scan_query = ScanQuery::new(Query::MatchAll(MatchAllQuery::new()), 1, 0).limit(100);
+    /// let stream = client.parallel_scan_all("users", "users_index", scan_query, 4);
+    /// tokio::pin!(stream);
+    ///
+    /// while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+    ///     let row = row?;
+    ///     println!("{:?}", row);
+    /// }
+    /// ```
+    pub fn parallel_scan_all(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        scan_query: ScanQuery,
+        concurrency: usize,
+    ) -> impl futures_core::Stream<Item = OtsResult<Row>> {
+        search::parallel_scan_all(self.clone(), table_name, index_name, scan_query, concurrency)
+    }
+
     /// 时序表 - 查询数据
     pub fn get_timeseries_data(&self, request: GetTimeseriesDataRequest) -> GetTimeseriesDataOperation {
         GetTimeseriesDataOperation::new(self.clone(), request)
     }
 
+    /// 时序表 - 一次查询多条时间线的数据。对每条时间线并发发送查询请求
+    pub fn batch_get_timeseries_data(&self, request: BatchGetTimeseriesDataRequest) -> BatchGetTimeseriesDataOperation {
+        BatchGetTimeseriesDataOperation::new(self.clone(), request)
+    }
+
     /// 时序表 - 写入数据
     ///
     /// # Examples
@@ -1199,6 +1963,11 @@ impl OtsClient {
         DeleteTimeseriesLastpointIndexOperation::new(self.clone(), table_name, index_name)
     }
 
+    /// 时序表 - 列出 lastpoint 索引的名称
+    pub fn list_timeseries_lastpoint_index(&self, table_name: &str) -> ListTimeseriesLastpointIndexOperation {
+        ListTimeseriesLastpointIndexOperation::new(self.clone(), table_name)
+    }
+
     /// 时序表 - 创建分析存储
     pub fn create_timeseries_analytical_store(&self, request: CreateTimeseriesAnalyticalStoreRequest) -> CreateTimeseriesAnalyticalStoreOperation {
         CreateTimeseriesAnalyticalStoreOperation::new(self.clone(), request)
@@ -1239,7 +2008,25 @@ impl OtsClient {
         SplitTimeseriesScanTaskOperation::new(self.clone(), request)
     }
 
-    /// 时序表 - 扫描数据
+    /// 时序表 - 扫描数据。
+    ///
+    /// 先使用 [`split_timeseries_scan_task`](`Self::split_timeseries_scan_task`) 把全量导出任务切分成多个 split info，
+    /// 然后对每个 split info 分别扫描，可以并行导出数据。响应中的 `next_token` 不为空的时候，需要带上 token 继续扫描
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let resp = client
+    ///     .split_timeseries_scan_task(SplitTimeseriesScanTaskRequest::new("timeseries_demo_with_data", 2))
+    ///     .send()
+    ///     .await?;
+    ///
+    /// for split_info in resp.split_infos {
+    ///     let req = ScanTimeseriesDataRequest::new("timeseries_demo_with_data").split_info(split_info).limit(1000);
+    ///     let resp = client.scan_timeseries_data(req).send().await?;
+    ///     println!("{} rows, next token: {:?}", resp.rows.len(), resp.next_token);
+    /// }
+    /// ```
     pub fn scan_timeseries_data(&self, request: ScanTimeseriesDataRequest) -> ScanTimeseriesDataOperation {
         ScanTimeseriesDataOperation::new(self.clone(), request)
     }
@@ -1257,4 +2044,375 @@ impl OtsClient {
     pub fn sql_query(&self, request: SqlQueryRequest) -> SqlQueryOperation {
         SqlQueryOperation::new(self.clone(), request)
     }
+
+    /// 列出 Stream。表名为 `None` 的时候列出实例下所有表的 Stream
+    pub fn list_stream(&self, table_name: Option<&str>) -> ListStreamOperation {
+        let request = match table_name {
+            Some(name) => ListStreamRequest::new().table_name(name),
+            None => ListStreamRequest::new(),
+        };
+
+        ListStreamOperation::new(self.clone(), request)
+    }
+
+    /// 获取 Stream 的详细信息和分区信息
+    pub fn describe_stream(&self, stream_id: &str) -> DescribeStreamOperation {
+        DescribeStreamOperation::new(self.clone(), stream_id)
+    }
+
+    /// 获取 Stream 中一个分区的迭代器
+    pub fn get_shard_iterator(&self, stream_id: &str, shard_id: &str) -> GetShardIteratorOperation {
+        GetShardIteratorOperation::new(self.clone(), stream_id, shard_id)
+    }
+
+    /// 使用分区迭代器读取 Stream 记录。需要使用响应中的 `next_shard_iterator` 继续读取，直到它为 `None`
+    pub fn get_stream_record(&self, shard_iterator: &str, limit: Option<u32>) -> GetStreamRecordOperation {
+        let request = match limit {
+            Some(n) => GetStreamRecordRequest::new(shard_iterator).limit(n),
+            None => GetStreamRecordRequest::new(shard_iterator),
+        };
+
+        GetStreamRecordOperation::new(self.clone(), request)
+    }
+
+    /// 持续读取 Stream 中一个分区的记录，自动管理分区迭代器
+    pub fn stream_reader(&self, stream_id: &str, shard_id: &str) -> StreamReader {
+        StreamReader::new(self.clone(), stream_id, shard_id)
+    }
+
+    /// 创建通道
+    pub fn create_tunnel(&self, request: CreateTunnelRequest) -> CreateTunnelOperation {
+        CreateTunnelOperation::new(self.clone(), request)
+    }
+
+    /// 列出表上的通道
+    pub fn list_tunnel(&self, table_name: &str) -> ListTunnelOperation {
+        ListTunnelOperation::new(self.clone(), table_name)
+    }
+
+    /// 查询通道的详细信息和数据通道的消费状态
+    pub fn describe_tunnel(&self, table_name: &str, tunnel_name: &str) -> DescribeTunnelOperation {
+        DescribeTunnelOperation::new(self.clone(), table_name, tunnel_name)
+    }
+
+    /// 删除通道
+    pub fn delete_tunnel(&self, table_name: &str, tunnel_name: &str) -> DeleteTunnelOperation {
+        DeleteTunnelOperation::new(self.clone(), table_name, tunnel_name)
+    }
+
+    /// 创建通道消费者，读取到的行交给回调函数处理，处理成功之后自动记录检查点
+    pub fn tunnel_worker<F>(&self, tunnel_id: &str, mut callback: F) -> TunnelWorker<impl FnMut(Vec<StreamRecord>) -> OtsResult<()>>
+    where
+        F: FnMut(Vec<Row>) -> OtsResult<()>,
+    {
+        TunnelWorker::new(self.clone(), tunnel_id, move |records: Vec<StreamRecord>| {
+            callback(records.into_iter().map(StreamRecord::into_row).collect())
+        })
+    }
+
+    /// 创建通道消费者，和 [`tunnel_worker`](`OtsClient::tunnel_worker`) 一样，
+    /// 只是回调函数收到的是包含操作类型的记录，可以区分写入、更新和删除
+    pub fn tunnel_record_worker<F>(&self, tunnel_id: &str, callback: F) -> TunnelWorker<F>
+    where
+        F: FnMut(Vec<StreamRecord>) -> OtsResult<()>,
+    {
+        TunnelWorker::new(self.clone(), tunnel_id, callback)
+    }
+}
+
+#[cfg(test)]
+mod test_client_options {
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use crate::{
+        error::{ApiErrorCode, OtsError},
+        test_util::{mock_error_server, MOCK_REQUEST_ID_PREFIX},
+        CredentialProvider, ExponentialBackoffRetryPolicy, OtsClient, OtsClientBuilder, OtsClientOptions, OtsOp, RetryPolicy, SignatureAlgorithm,
+    };
+
+    #[test]
+    fn test_client_builder() {
+        let client = OtsClient::builder("aid", "asec")
+            .endpoint("https://My-Instance.cn-hangzhou.ots.aliyuncs.com/")
+            .sts_token("token")
+            .build()
+            .unwrap();
+        assert_eq!("my-instance", client.instance_name);
+        assert_eq!("cn-hangzhou", client.region);
+        assert_eq!("https://my-instance.cn-hangzhou.ots.aliyuncs.com", client.endpoint);
+        assert_eq!(
+            ("aid".to_string(), "asec".to_string(), Some("token".to_string())),
+            client.credential_provider.credentials()
+        );
+
+        // 显式设置的地域和实例名称优先
+        let client = OtsClientBuilder::new("", "")
+            .access_key_id("aid")
+            .access_key_secret("asec")
+            .endpoint("http://10.0.0.1:8080")
+            .region("cn-shanghai")
+            .instance_name("vpc-instance")
+            .build()
+            .unwrap();
+        assert_eq!("vpc-instance", client.instance_name);
+        assert_eq!("cn-shanghai", client.region);
+
+        assert!(OtsClient::builder("", "asec").endpoint("https://a.b.ots.aliyuncs.com").build().is_err());
+        assert!(OtsClient::builder("aid", "").endpoint("https://a.b.ots.aliyuncs.com").build().is_err());
+        assert!(OtsClient::builder("aid", "asec").build().is_err());
+        assert!(OtsClient::builder("aid", "asec").endpoint("a.b.ots.aliyuncs.com").build().is_err());
+        assert!(OtsClient::builder("aid", "asec").endpoint("ftp://a.b.ots.aliyuncs.com").build().is_err());
+        assert!(OtsClient::builder("aid", "asec").endpoint("http://localhost").build().is_err());
+
+        // IP 地址不能解析出实例名称和地域，需要显式设置
+        assert!(OtsClient::builder("aid", "asec").endpoint("http://10.0.0.1").build().is_err());
+        assert!(OtsClient::builder("aid", "asec")
+            .endpoint("http://10.0.0.1")
+            .region("cn-hangzhou")
+            .build()
+            .is_err());
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingCredentialProvider {
+        calls: AtomicU32,
+    }
+
+    impl CredentialProvider for CountingCredentialProvider {
+        fn credentials(&self) -> (String, String, Option<String>) {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+
+            ("aid".to_string(), "asec".to_string(), Some(format!("token-{}", n)))
+        }
+    }
+
+    #[test]
+    fn test_credential_provider() {
+        let client = OtsClient::builder("", "")
+            .endpoint("https://instance.cn-hangzhou.ots.aliyuncs.com")
+            .credential_provider(CountingCredentialProvider::default())
+            .build()
+            .unwrap();
+
+        // 每次签名都重新获取凭证
+        for i in 0..2 {
+            let mut headers = HashMap::new();
+            client.fill_signature_v2("GetRow", &mut headers);
+            assert_eq!(Some(&"aid".to_string()), headers.get("x-ots-accesskeyid"));
+            assert_eq!(Some(&format!("token-{}", i)), headers.get("x-ots-ststoken"));
+        }
+
+        let mut headers = HashMap::new();
+        OtsClient::new("aid", "asec", "https://instance.cn-hangzhou.ots.aliyuncs.com").fill_signature_v2("GetRow", &mut headers);
+        assert_eq!(Some(&"aid".to_string()), headers.get("x-ots-accesskeyid"));
+        assert!(!headers.contains_key("x-ots-ststoken"));
+    }
+
+    #[test]
+    fn test_signature_algorithm() {
+        let string_to_sign = "/GetRow\nPOST\n\nx-ots-accesskeyid:aid\nx-ots-date:2025-01-01T00:00:00.000Z\n";
+
+        assert_eq!(SignatureAlgorithm::HmacSha1, OtsClientOptions::default().signature_algorithm);
+        assert_eq!(
+            "JaSaJRLepdTLBJUql8Qu2rClB5s=",
+            SignatureAlgorithm::HmacSha1.sign(b"asec", string_to_sign.as_bytes())
+        );
+        assert_eq!(
+            "L8/QRZ14B84e64h6r2lWIZzNQRYA2xRf+AaAJZBFtDY=",
+            SignatureAlgorithm::HmacSha256.sign(b"asec", string_to_sign.as_bytes())
+        );
+        assert_eq!("HmacSHA256", SignatureAlgorithm::HmacSha256.as_str());
+    }
+
+    #[test]
+    fn test_parse_instance_and_region() {
+        let parse = |endpoint| OtsClient::parse_instance_and_region(endpoint).unwrap();
+
+        assert_eq!(
+            ("my-instance".to_string(), "cn-beijing".to_string()),
+            parse("https://my-instance.cn-beijing.ots.aliyuncs.com")
+        );
+        assert_eq!(("a".to_string(), "b".to_string()), parse("http://a.b"));
+        assert_eq!(("a".to_string(), "b".to_string()), parse("http://a.b:8080/"));
+
+        for endpoint in [
+            "https://localhost",
+            "",
+            "https://",
+            "https://.cn-beijing.ots.aliyuncs.com",
+            "https://a..b",
+            "http://10.0.0.1",
+            "http://10.0.0.1:8080",
+            "http://[::1]:8080",
+        ] {
+            let e = OtsClient::parse_instance_and_region(endpoint).unwrap_err();
+            assert!(e.to_string().contains(endpoint), "{}", e);
+        }
+    }
+
+    #[test]
+    fn test_exponential_backoff_retry_policy() {
+        let err = OtsError::ValidationFailed("test".to_string());
+        let policy = ExponentialBackoffRetryPolicy::new().base_delay_ms(100).max_delay_ms(1000);
+        let delays = (0..6).map(|n| policy.delay_ms(n, OtsOp::GetRow, &err)).collect::<Vec<_>>();
+        assert_eq!(vec![100, 200, 400, 800, 1000, 1000], delays);
+
+        // 重试次数很大的时候不会溢出
+        assert_eq!(1000, policy.delay_ms(40, OtsOp::GetRow, &err));
+        assert_eq!(
+            u32::MAX,
+            ExponentialBackoffRetryPolicy::new().max_delay_ms(u32::MAX).delay_ms(100, OtsOp::GetRow, &err)
+        );
+
+        let policy = policy.jitter(true);
+        for n in 0..6 {
+            assert!(policy.delay_ms(n, OtsOp::GetRow, &err) <= delays[n as usize]);
+        }
+    }
+
+    type DelayCall = (u32, OtsOp, Option<ApiErrorCode>);
+
+    /// 记录每次计算等待时间时收到的参数
+    #[derive(Debug, Default)]
+    struct RecordingRetryPolicy {
+        calls: Arc<Mutex<Vec<DelayCall>>>,
+    }
+
+    impl RetryPolicy for RecordingRetryPolicy {
+        fn should_retry(&self, retried: u32, _op: OtsOp, _ots_error: &OtsError) -> bool {
+            retried < 2
+        }
+
+        fn delay_ms(&self, retried: u32, op: OtsOp, ots_error: &OtsError) -> u32 {
+            self.calls.lock().unwrap().push((retried, op, ots_error.code()));
+
+            1
+        }
+
+        fn clone_box(&self) -> Box<dyn RetryPolicy> {
+            Box::new(Self { calls: self.calls.clone() })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_delay_context() {
+        let endpoint = mock_error_server(503, "OTSServerBusy").await;
+        let policy = RecordingRetryPolicy::default();
+        let calls = policy.calls.clone();
+
+        let client = OtsClient::builder("aid", "asec")
+            .endpoint(endpoint)
+            .instance_name("instance")
+            .region("cn-hangzhou")
+            .rety_policy(Box::new(policy))
+            .build()
+            .unwrap();
+
+        let res = client.list_table().send().await;
+        assert_eq!(Some(ApiErrorCode::ServerBusy), res.unwrap_err().code());
+
+        assert_eq!(
+            vec![
+                (0, OtsOp::ListTable, Some(ApiErrorCode::ServerBusy)),
+                (1, OtsOp::ListTable, Some(ApiErrorCode::ServerBusy))
+            ],
+            *calls.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_total_elapsed_ms() {
+        let endpoint = mock_error_server(503, "OTSServerBusy").await;
+
+        // 默认的重试策略每次等待 10 秒，超过了允许的总时间，所以不会重试
+        let client = OtsClient::builder("aid", "asec")
+            .endpoint(endpoint)
+            .instance_name("instance")
+            .region("cn-hangzhou")
+            .max_total_elapsed_ms(100)
+            .build()
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        let res = client.list_table().send().await;
+        assert_eq!(Some(ApiErrorCode::ServerBusy), res.unwrap_err().code());
+        assert!(started.elapsed().as_millis() < 5000);
+    }
+
+    #[tokio::test]
+    async fn test_error_request_id() {
+        let endpoint = mock_error_server(403, "OTSAuthFailed").await;
+        let client = OtsClient::builder("aid", "asec")
+            .endpoint(endpoint)
+            .instance_name("instance")
+            .region("cn-hangzhou")
+            .build()
+            .unwrap();
+
+        let e = client.list_table().send().await.unwrap_err();
+        assert_eq!(Some(ApiErrorCode::AuthFailed), e.code());
+        assert!(e.request_id().is_some_and(|id| id.starts_with(MOCK_REQUEST_ID_PREFIX)));
+    }
+
+    #[test]
+    fn test_accept_encoding() {
+        assert_eq!("identity", OtsClientOptions::default().accept_encoding());
+
+        let client = OtsClient::builder("aid", "asec")
+            .endpoint("https://instance.cn-hangzhou.ots.aliyuncs.com")
+            .accept_compression(true)
+            .build()
+            .unwrap();
+        assert_eq!("gzip, deflate", client.options.accept_encoding());
+    }
+
+    #[test]
+    fn test_effective_timeout_ms() {
+        let mut options = OtsClientOptions::default();
+        assert_eq!(None, options.effective_timeout_ms(OtsOp::GetRow, None));
+
+        options.timeout_ms = Some(3000);
+        options.operation_timeouts_ms.insert(OtsOp::CreateSearchIndex, 60000);
+
+        assert_eq!(Some(3000), options.effective_timeout_ms(OtsOp::GetRow, None));
+        assert_eq!(Some(60000), options.effective_timeout_ms(OtsOp::CreateSearchIndex, None));
+        assert_eq!(Some(1000), options.effective_timeout_ms(OtsOp::CreateSearchIndex, Some(1000)));
+        assert_eq!(Some(500), options.effective_timeout_ms(OtsOp::GetRow, Some(500)));
+    }
+
+    #[tokio::test]
+    async fn test_sleep_or_shutdown() {
+        let options = OtsClientOptions::default();
+        assert!(!options.sleep_or_shutdown(1).await);
+
+        let (tx, rx) = tokio::sync::watch::channel(false);
+        let options = OtsClientOptions {
+            shutdown_signal: Some(rx),
+            ..Default::default()
+        };
+        assert!(!options.sleep_or_shutdown(1).await);
+
+        let start = std::time::Instant::now();
+        let handle = tokio::spawn(async move { options.sleep_or_shutdown(60000).await });
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        tx.send(true).unwrap();
+
+        assert!(handle.await.unwrap());
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+        // 发送端被丢弃视为不会停机
+        let (tx, rx) = tokio::sync::watch::channel(false);
+        drop(tx);
+        let options = OtsClientOptions {
+            shutdown_signal: Some(rx),
+            ..Default::default()
+        };
+        assert!(!options.sleep_or_shutdown(1).await);
+    }
 }
@@ -1,4 +1,4 @@
-use std::io::{Cursor, Read, Write};
+use std::io::{Cursor, Write};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
@@ -36,6 +36,19 @@ impl ColumnOp {
     }
 }
 
+impl TryFrom<u8> for ColumnOp {
+    type Error = OtsError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            CELL_OP_DELETE_ONE_VERSION => Ok(ColumnOp::Delete),
+            CELL_OP_DELETE_ALL_VERSION => Ok(ColumnOp::DeleteAll),
+            CELL_OP_INCREMENT => Ok(ColumnOp::Increment),
+            _ => Err(OtsError::PlainBufferError(format!("unknown cell op: {}", value))),
+        }
+    }
+}
+
 impl From<ColumnOp> for u8 {
     fn from(value: ColumnOp) -> Self {
         value.as_u8()
@@ -48,7 +61,7 @@ impl From<&ColumnOp> for u8 {
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default)]
 pub enum ColumnValue {
     /// 这个值是内部使用的，仅仅是用来标记在 UpdateRow 的时候要标记删除某个列的所有版本使用的。
     #[default]
@@ -63,6 +76,57 @@ pub enum ColumnValue {
     InfMax,
 }
 
+/// 比较整数和浮点数。不会先把整数转换成浮点数再比较，避免超过 2^53 的整数丢失精度
+fn compare_integer_double(a: i64, b: f64) -> Option<std::cmp::Ordering> {
+    // 2^63，i64 能表示的范围是 [-2^63, 2^63)
+    const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+
+    if b.is_nan() {
+        return None;
+    }
+
+    if b >= TWO_POW_63 {
+        return Some(std::cmp::Ordering::Less);
+    }
+
+    if b < -TWO_POW_63 {
+        return Some(std::cmp::Ordering::Greater);
+    }
+
+    match a.cmp(&(b.trunc() as i64)) {
+        std::cmp::Ordering::Equal => 0.0f64.partial_cmp(&b.fract()),
+        ord => Some(ord),
+    }
+}
+
+/// 列值的相等规则和 [`PartialOrd`] 一致：
+///
+/// - 同类型的值直接比较，浮点数 `NaN` 和任何值都不相等
+/// - `Integer` 和 `Double` 之间按照数值比较，例如 `Integer(5)` 等于 `Double(5.0)`
+/// - 其他不同类型之间都不相等，例如 `String("5")` 不等于 `Integer(5)`
+impl PartialEq for ColumnValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Null, Self::Null) | (Self::InfMin, Self::InfMin) | (Self::InfMax, Self::InfMax) => true,
+            (Self::Integer(a), Self::Integer(b)) => a == b,
+            (Self::Double(a), Self::Double(b)) => a == b,
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Blob(a), Self::Blob(b)) => a == b,
+            (Self::Integer(a), Self::Double(b)) | (Self::Double(b), Self::Integer(a)) => compare_integer_double(*a, *b) == Some(std::cmp::Ordering::Equal),
+            (_, _) => false,
+        }
+    }
+}
+
+/// 列值的比较规则：
+///
+/// - `Null` 小于其他所有值；`InfMin` 小于除 `Null` 之外的所有值；`InfMax` 大于除 `Null` 之外的所有值
+/// - 同类型的值直接比较。字符串和二进制按字节的字典序比较
+/// - `Integer` 和 `Double` 之间按照数值大小比较，例如 `Integer(5)` 小于 `Double(5.5)`
+/// - 浮点数 `NaN` 和其他值不可比较
+/// - 其他不同类型之间（例如 `Blob` 和 `Boolean`、`String` 和 `Integer`）不可比较，返回 `None`。
+///   此时 `<`、`>`、`<=`、`>=` 都会返回 `false`
 impl PartialOrd for ColumnValue {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
@@ -88,13 +152,123 @@ impl PartialOrd for ColumnValue {
             (Self::String(a), Self::String(b)) => a.partial_cmp(b),
             (Self::Blob(a), Self::Blob(b)) => a.partial_cmp(b),
 
+            // Compare numeric values
+            (Self::Integer(a), Self::Double(b)) => compare_integer_double(*a, *b),
+            (Self::Double(a), Self::Integer(b)) => compare_integer_double(*b, *a).map(|ord| ord.reverse()),
+
             // Compare different types
             (_, _) => None,
         }
     }
 }
 
+impl TryFrom<&ColumnValue> for i64 {
+    type Error = OtsError;
+
+    fn try_from(value: &ColumnValue) -> Result<Self, Self::Error> {
+        value.as_i64().ok_or_else(|| value.type_mismatch("Integer"))
+    }
+}
+
+impl TryFrom<&ColumnValue> for f64 {
+    type Error = OtsError;
+
+    fn try_from(value: &ColumnValue) -> Result<Self, Self::Error> {
+        value.as_f64().ok_or_else(|| value.type_mismatch("Double"))
+    }
+}
+
+impl TryFrom<&ColumnValue> for bool {
+    type Error = OtsError;
+
+    fn try_from(value: &ColumnValue) -> Result<Self, Self::Error> {
+        value.as_bool().ok_or_else(|| value.type_mismatch("Boolean"))
+    }
+}
+
+impl TryFrom<&ColumnValue> for String {
+    type Error = OtsError;
+
+    fn try_from(value: &ColumnValue) -> Result<Self, Self::Error> {
+        value.as_str().map(|s| s.to_string()).ok_or_else(|| value.type_mismatch("String"))
+    }
+}
+
+impl TryFrom<&ColumnValue> for Vec<u8> {
+    type Error = OtsError;
+
+    fn try_from(value: &ColumnValue) -> Result<Self, Self::Error> {
+        value.as_bytes().map(|b| b.to_vec()).ok_or_else(|| value.type_mismatch("Blob"))
+    }
+}
+
 impl ColumnValue {
+    /// 列值类型的名称，例如 `"Integer"`、`"String"`
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "Null",
+            Self::Integer(_) => "Integer",
+            Self::Double(_) => "Double",
+            Self::Boolean(_) => "Boolean",
+            Self::String(_) => "String",
+            Self::Blob(_) => "Blob",
+            Self::InfMin => "InfMin",
+            Self::InfMax => "InfMax",
+        }
+    }
+
+    /// 是否为空值
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// 如果是整数，返回整数值
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// 如果是浮点数，返回浮点数值。整数不会被转换成浮点数
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Double(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    /// 如果是布尔值，返回布尔值
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// 如果是字符串，返回字符串的引用
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// 如果是二进制数据，返回字节的引用
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Blob(buf) => Some(buf.as_slice()),
+            _ => None,
+        }
+    }
+
+    fn type_mismatch(&self, expected: &'static str) -> OtsError {
+        OtsError::ColumnValueTypeMismatch {
+            expected,
+            actual: self.type_name(),
+        }
+    }
+
     /// 返回的长度包含：4 字节前缀 + 1 字节类型 + 4 字节值的长度（仅针对 String 和 Binary）+ 值的实际数据长度
     pub(crate) fn compute_size(&self) -> u32 {
         // 4 bytes for total length,
@@ -151,16 +325,12 @@ impl ColumnValue {
             }
 
             VT_STRING => {
-                let len = cursor.read_u32::<LittleEndian>()?;
-                let mut buf = vec![0u8; len as usize];
-                cursor.read_exact(&mut buf)?;
+                let buf = plain_buffer::read_len_prefixed_bytes(&mut cursor)?;
                 Ok(Self::String(String::from_utf8(buf)?))
             }
 
             VT_BLOB => {
-                let len = cursor.read_u32::<LittleEndian>()?;
-                let mut buf = vec![0u8; len as usize];
-                cursor.read_exact(&mut buf)?;
+                let buf = plain_buffer::read_len_prefixed_bytes(&mut cursor)?;
                 Ok(Self::Blob(buf))
             }
 
@@ -328,9 +498,11 @@ impl Column {
 
     pub(crate) fn read_plain_buffer(cursor: &mut Cursor<Vec<u8>>) -> OtsResult<Self> {
         let mut name = String::new();
-        let mut value = ColumnValue::Integer(0);
+        // 删除列的操作（例如 Stream 中的更新记录）没有值
+        let mut value = ColumnValue::Null;
         let mut checksum = 0u8;
         let mut ts: Option<u64> = None;
+        let mut op: Option<ColumnOp> = None;
 
         loop {
             if cursor.position() >= (cursor.get_ref().len() - 1) as u64 {
@@ -341,10 +513,7 @@ impl Column {
 
             match tag {
                 plain_buffer::TAG_CELL_NAME => {
-                    let len = cursor.read_u32::<LittleEndian>()? as usize;
-                    let mut buf: Vec<u8> = vec![0u8; len];
-
-                    cursor.read_exact(&mut buf)?;
+                    let buf = plain_buffer::read_len_prefixed_bytes(cursor)?;
                     name = String::from_utf8(buf)?;
                 }
 
@@ -363,16 +532,12 @@ impl Column {
                         }
 
                         plain_buffer::VT_STRING => {
-                            let len = cursor.read_u32::<LittleEndian>()? as usize;
-                            let mut buf: Vec<u8> = vec![0u8; len];
-                            cursor.read_exact(&mut buf)?;
+                            let buf = plain_buffer::read_len_prefixed_bytes(cursor)?;
                             ColumnValue::String(String::from_utf8(buf)?)
                         }
 
                         plain_buffer::VT_BLOB => {
-                            let len = cursor.read_u32::<LittleEndian>()? as usize;
-                            let mut buf: Vec<u8> = vec![0u8; len];
-                            cursor.read_exact(&mut buf)?;
+                            let buf = plain_buffer::read_len_prefixed_bytes(cursor)?;
                             ColumnValue::Blob(buf)
                         }
 
@@ -384,6 +549,10 @@ impl Column {
                     ts = Some(cursor.read_u64::<LittleEndian>()?);
                 }
 
+                plain_buffer::TAG_CELL_OP => {
+                    op = Some(ColumnOp::try_from(cursor.read_u8()?)?);
+                }
+
                 plain_buffer::TAG_CELL_CHECKSUM => {
                     checksum = cursor.read_u8()?;
                     break;
@@ -396,8 +565,8 @@ impl Column {
         let col = Self {
             name,
             value,
+            op,
             timestamp: ts,
-            ..Default::default()
         };
 
         let cell_checksum = col.crc8_checksum();
@@ -405,10 +574,10 @@ impl Column {
         // log::debug!("cell {}, calculated checksum {}, received checksum {}", col.name, cell_checksum, checksum);
 
         if cell_checksum != checksum {
-            return Err(OtsError::PlainBufferError(format!(
-                "data data cell checksum validation failed. calculated: {}, received: {}",
-                cell_checksum, checksum
-            )));
+            return Err(OtsError::ChecksumMismatch {
+                calculated: cell_checksum,
+                received: checksum,
+            });
         }
 
         Ok(col)
@@ -503,3 +672,217 @@ impl Column {
         }
     }
 }
+
+#[cfg(test)]
+mod test_column {
+    use std::cmp::Ordering;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{
+        error::OtsError,
+        model::{decode_plainbuf_rows, Column, PrimaryKey, Row},
+        protos::plain_buffer::{MASK_HEADER, VT_BLOB, VT_BOOLEAN, VT_DOUBLE, VT_INF_MAX, VT_INF_MIN, VT_INTEGER, VT_STRING},
+        OtsResult,
+    };
+
+    use super::ColumnValue;
+
+    #[test]
+    fn test_compare_numeric_values() {
+        assert!(ColumnValue::Integer(5) < ColumnValue::Double(5.5));
+        assert!(ColumnValue::Double(5.5) > ColumnValue::Integer(5));
+        assert!(ColumnValue::Integer(-6) < ColumnValue::Double(-5.5));
+        assert!(ColumnValue::Double(-5.5) > ColumnValue::Integer(-6));
+        assert!(ColumnValue::Integer(0) >= ColumnValue::Double(0.0));
+        assert!(ColumnValue::Integer(0) <= ColumnValue::Double(0.0));
+        assert_eq!(Some(Ordering::Equal), ColumnValue::Integer(5).partial_cmp(&ColumnValue::Double(5.0)));
+
+        // 超过 f64 精度的整数
+        assert!(ColumnValue::Integer(i64::MAX) < ColumnValue::Double(9_223_372_036_854_775_808.0));
+        assert!(ColumnValue::Integer(i64::MIN) > ColumnValue::Double(-1e19));
+        assert!(ColumnValue::Integer((1 << 53) + 1) > ColumnValue::Double((1u64 << 53) as f64));
+
+        assert!(ColumnValue::Double(f64::INFINITY) > ColumnValue::Integer(i64::MAX));
+        assert!(ColumnValue::Double(f64::NEG_INFINITY) < ColumnValue::Integer(i64::MIN));
+        assert_eq!(None, ColumnValue::Integer(1).partial_cmp(&ColumnValue::Double(f64::NAN)));
+    }
+
+    #[test]
+    fn test_try_from_column_value() {
+        assert_eq!(42i64, i64::try_from(&ColumnValue::Integer(42)).unwrap());
+        assert_eq!(1.5f64, f64::try_from(&ColumnValue::Double(1.5)).unwrap());
+        assert!(bool::try_from(&ColumnValue::Boolean(true)).unwrap());
+        assert_eq!("abc", String::try_from(&ColumnValue::String("abc".to_string())).unwrap());
+        assert_eq!(vec![1u8, 2], Vec::<u8>::try_from(&ColumnValue::Blob(vec![1, 2])).unwrap());
+
+        let v: i64 = (&ColumnValue::Integer(7)).try_into().unwrap();
+        assert_eq!(7, v);
+
+        let mismatches: Vec<(OtsResult<()>, &str, &str)> = vec![
+            (i64::try_from(&ColumnValue::Double(1.0)).map(|_| ()), "Integer", "Double"),
+            (f64::try_from(&ColumnValue::Integer(1)).map(|_| ()), "Double", "Integer"),
+            (bool::try_from(&ColumnValue::String("true".to_string())).map(|_| ()), "Boolean", "String"),
+            (String::try_from(&ColumnValue::Blob(vec![b'a'])).map(|_| ()), "String", "Blob"),
+            (Vec::<u8>::try_from(&ColumnValue::Null).map(|_| ()), "Blob", "Null"),
+        ];
+
+        for (result, expected_type, actual_type) in mismatches {
+            match result {
+                Err(OtsError::ColumnValueTypeMismatch { expected, actual }) => {
+                    assert_eq!(expected_type, expected);
+                    assert_eq!(actual_type, actual);
+                }
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_column_value_as() {
+        assert_eq!(Some(42), ColumnValue::Integer(42).as_i64());
+        assert_eq!(None, ColumnValue::Double(42.0).as_i64());
+        assert_eq!(Some(1.5), ColumnValue::Double(1.5).as_f64());
+        assert_eq!(None, ColumnValue::Integer(1).as_f64());
+        assert_eq!(Some(false), ColumnValue::Boolean(false).as_bool());
+        assert_eq!(None, ColumnValue::Integer(0).as_bool());
+        assert_eq!(Some("abc"), ColumnValue::String("abc".to_string()).as_str());
+        assert_eq!(None, ColumnValue::Blob(b"abc".to_vec()).as_str());
+        assert_eq!(Some(&[1u8, 2][..]), ColumnValue::Blob(vec![1, 2]).as_bytes());
+        assert_eq!(None, ColumnValue::String("ab".to_string()).as_bytes());
+        assert!(ColumnValue::Null.is_null());
+        assert!(!ColumnValue::InfMin.is_null());
+    }
+
+    #[test]
+    fn test_numeric_equality() {
+        assert_eq!(ColumnValue::Integer(5), ColumnValue::Double(5.0));
+        assert_eq!(ColumnValue::Double(5.0), ColumnValue::Integer(5));
+        assert_eq!(ColumnValue::Integer(0), ColumnValue::Double(-0.0));
+        assert_ne!(ColumnValue::Integer(5), ColumnValue::Double(5.5));
+        assert_ne!(ColumnValue::Integer(i64::MAX), ColumnValue::Double(9_223_372_036_854_775_808.0));
+        assert_ne!(ColumnValue::Integer((1 << 53) + 1), ColumnValue::Double((1u64 << 53) as f64));
+        assert_ne!(ColumnValue::Integer(0), ColumnValue::Double(f64::NAN));
+        assert_ne!(ColumnValue::Double(f64::NAN), ColumnValue::Double(f64::NAN));
+
+        // 相等和大小比较的结果保持一致
+        let a = ColumnValue::Integer(3);
+        let b = ColumnValue::Double(3.0);
+        assert_eq!(Some(Ordering::Equal), a.partial_cmp(&b));
+        assert_eq!(a, b);
+
+        // 字符串、二进制和数值之间不相等，也不可比较
+        assert_ne!(ColumnValue::String("5".to_string()), ColumnValue::Integer(5));
+        assert_ne!(ColumnValue::Blob(vec![5]), ColumnValue::Double(5.0));
+        assert_eq!(None, ColumnValue::Blob(vec![5]).partial_cmp(&ColumnValue::Double(5.0)));
+        assert_eq!(None, ColumnValue::Double(5.0).partial_cmp(&ColumnValue::Blob(vec![5])));
+        assert_eq!(None, ColumnValue::String("1".to_string()).partial_cmp(&ColumnValue::Integer(100)));
+        assert_eq!(None, ColumnValue::Integer(100).partial_cmp(&ColumnValue::String("1".to_string())));
+        assert_ne!(ColumnValue::Boolean(true), ColumnValue::Integer(1));
+        assert_eq!(ColumnValue::Null, ColumnValue::Null);
+    }
+
+    #[test]
+    fn test_compare_special_values() {
+        assert!(ColumnValue::Null < ColumnValue::InfMin);
+        assert!(ColumnValue::InfMin < ColumnValue::Integer(i64::MIN));
+        assert!(ColumnValue::InfMax > ColumnValue::Blob(vec![0xFF]));
+        assert!(ColumnValue::InfMax > ColumnValue::Double(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_compare_incomparable_values() {
+        assert!(ColumnValue::Blob(vec![1]).partial_cmp(&ColumnValue::Boolean(true)).is_none());
+        assert!(ColumnValue::Boolean(true).partial_cmp(&ColumnValue::Blob(vec![1])).is_none());
+        assert!(ColumnValue::Double(1e300).partial_cmp(&ColumnValue::Boolean(false)).is_none());
+        assert!(ColumnValue::String("z".to_string()).partial_cmp(&ColumnValue::Blob(vec![])).is_none());
+
+        // 特殊值和其他类型之间仍然可以比较
+        assert!(ColumnValue::Null < ColumnValue::Boolean(false));
+        assert!(ColumnValue::InfMin < ColumnValue::String("a".to_string()));
+        assert!(ColumnValue::InfMax > ColumnValue::Boolean(true));
+    }
+
+    #[test]
+    fn test_decode_plain_buffer_round_trip() {
+        let values = vec![
+            ColumnValue::InfMin,
+            ColumnValue::InfMax,
+            ColumnValue::Integer(-42),
+            ColumnValue::Double(3.5),
+            ColumnValue::Boolean(true),
+            ColumnValue::String("hello".to_string()),
+            ColumnValue::Blob(vec![1, 2, 3]),
+        ];
+
+        for value in values {
+            let bytes = value.encode_plain_buffer();
+            assert_eq!(value, ColumnValue::decode_plain_buffer(bytes.clone()).unwrap());
+
+            // 截断的数据都应该返回错误
+            for n in 0..bytes.len() {
+                if bytes[0] == VT_INF_MIN || bytes[0] == VT_INF_MAX {
+                    continue;
+                }
+
+                assert!(ColumnValue::decode_plain_buffer(&bytes[..n]).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_plain_buffer_invalid_length() {
+        // 长度远大于实际数据
+        assert!(ColumnValue::decode_plain_buffer(vec![VT_STRING, 0xFF, 0xFF, 0xFF, 0xFF, b'a']).is_err());
+        assert!(ColumnValue::decode_plain_buffer(vec![VT_BLOB, 0xFF, 0xFF, 0xFF, 0xFF]).is_err());
+        assert!(ColumnValue::decode_plain_buffer(vec![VT_STRING, 0x02, 0x00, 0x00, 0x00, 0xC3, 0x28]).is_err());
+        assert!(ColumnValue::decode_plain_buffer(vec![0xEE]).is_err());
+        assert!(ColumnValue::decode_plain_buffer(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_decode_plain_buffer_random_bytes() {
+        let mut rng = StdRng::seed_from_u64(20250401);
+        let value_types = [VT_INTEGER, VT_DOUBLE, VT_BOOLEAN, VT_STRING, VT_BLOB, VT_INF_MIN, VT_INF_MAX];
+
+        for _ in 0..10000 {
+            let len = rng.random_range(0..64);
+            let mut bytes = (0..len).map(|_| rng.random::<u8>()).collect::<Vec<_>>();
+
+            // 大部分用例使用合法的类型字节，尽量覆盖到后续的解析逻辑
+            if !bytes.is_empty() && rng.random_bool(0.8) {
+                bytes[0] = value_types[rng.random_range(0..value_types.len())];
+            }
+
+            // 只要求不 panic
+            let _ = ColumnValue::decode_plain_buffer(bytes.clone());
+            let _ = Row::decode_plain_buffer(bytes.clone(), 0);
+
+            let mut with_header = vec![0x75, 0x00, 0x00, 0x00];
+            with_header.extend(bytes);
+            let _ = Row::decode_plain_buffer(with_header, MASK_HEADER);
+        }
+
+        // 在合法的行数据上随机修改一些字节
+        let row = Row::new()
+            .primary_key(PrimaryKey::new().column_string("pk", "id").column_integer("seq", 1))
+            .column(Column::from_string("name", "tablestore"))
+            .column(Column::from_blob("data", vec![1, 2, 3]))
+            .column(Column::from_double("score", 1.5));
+        let bytes = row.encode_plain_buffer(MASK_HEADER);
+
+        for _ in 0..10000 {
+            let mut mutated = bytes.clone();
+
+            for _ in 0..rng.random_range(1..4) {
+                let i = rng.random_range(0..mutated.len());
+                mutated[i] = rng.random::<u8>();
+            }
+
+            mutated.truncate(rng.random_range(0..=mutated.len()));
+
+            let _ = Row::decode_plain_buffer(mutated.clone(), MASK_HEADER);
+            let _ = decode_plainbuf_rows(mutated, MASK_HEADER);
+        }
+    }
+}
@@ -2,9 +2,16 @@ use std::io::Cursor;
 
 use prost::Message;
 
-use crate::protos::filter::{ComparatorType, FilterType, LogicalOperator, ValueTransferRule};
+use crate::{
+    error::OtsError,
+    protos::filter::{ComparatorType, FilterType, LogicalOperator, ValueTransferRule},
+    OtsResult,
+};
 
-use super::Column;
+use super::{
+    rules::{MAX_FILTER_COUNT, MAX_FILTER_DEPTH},
+    Column,
+};
 
 /// 单条件过滤器
 ///
@@ -282,6 +289,61 @@ impl From<Filter> for crate::protos::filter::Filter {
 }
 
 impl Filter {
+    /// 过滤器的嵌套深度。单条件过滤器和分页过滤器的深度为 `1`
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::Single(_) | Self::Pagination(_) => 1,
+            Self::Composite(f) => 1 + f.sub_filters.iter().map(|sub| sub.depth()).max().unwrap_or(0),
+        }
+    }
+
+    /// 过滤器中包含的过滤器总数（包含自身）
+    pub fn count(&self) -> usize {
+        match self {
+            Self::Single(_) | Self::Pagination(_) => 1,
+            Self::Composite(f) => 1 + f.sub_filters.iter().map(|sub| sub.count()).sum::<usize>(),
+        }
+    }
+
+    /// 校验过滤器的嵌套深度和包含的过滤器总数，避免服务端拒绝过于复杂的过滤器。
+    /// 最大嵌套深度为 `10`，最多包含 `32` 个过滤器
+    pub fn validate(&self) -> OtsResult<()> {
+        self.validate_composite()?;
+
+        let depth = self.depth();
+        if depth > MAX_FILTER_DEPTH {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid filter: nesting depth {} exceeds the limit {}",
+                depth, MAX_FILTER_DEPTH
+            )));
+        }
+
+        let count = self.count();
+        if count > MAX_FILTER_COUNT {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid filter: total filter count {} exceeds the limit {}",
+                count, MAX_FILTER_COUNT
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 组合过滤器至少要有一个子过滤器
+    fn validate_composite(&self) -> OtsResult<()> {
+        if let Self::Composite(f) = self {
+            if f.sub_filters.is_empty() {
+                return Err(OtsError::ValidationFailed("invalid filter: composite filter must have sub filters".to_string()));
+            }
+
+            for sub in &f.sub_filters {
+                sub.validate_composite()?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// 编码到 protobuf 字节
     pub fn into_protobuf_bytes(self) -> Vec<u8> {
         let msg: crate::protos::filter::Filter = self.into();
@@ -289,3 +351,58 @@ impl Filter {
         msg.encode_to_vec()
     }
 }
+
+#[cfg(test)]
+mod test_filter {
+    use crate::{
+        model::{rules::MAX_FILTER_DEPTH, Column},
+        protos::filter::LogicalOperator,
+    };
+
+    use super::{CompositeColumnValueFilter, Filter, SingleColumnValueFilter};
+
+    fn single() -> Filter {
+        Filter::Single(SingleColumnValueFilter::new().equal_column(Column::from_integer("int_col", 1)))
+    }
+
+    #[test]
+    fn test_filter_depth_and_count() {
+        let filter = Filter::Composite(
+            CompositeColumnValueFilter::new(LogicalOperator::LoAnd)
+                .sub_filter(single())
+                .sub_filter(Filter::Composite(
+                    CompositeColumnValueFilter::new(LogicalOperator::LoOr).sub_filters([single(), single()]),
+                )),
+        );
+
+        assert_eq!(3, filter.depth());
+        assert_eq!(5, filter.count());
+        assert!(filter.validate().is_ok());
+    }
+
+    #[test]
+    fn test_filter_too_deep() {
+        let mut filter = single();
+
+        for _ in 0..MAX_FILTER_DEPTH {
+            filter = Filter::Composite(CompositeColumnValueFilter::new(LogicalOperator::LoNot).sub_filter(filter));
+        }
+
+        assert_eq!(MAX_FILTER_DEPTH + 1, filter.depth());
+        assert!(filter.validate().is_err());
+    }
+
+    #[test]
+    fn test_filter_too_many_sub_filters() {
+        let filter = Filter::Composite(CompositeColumnValueFilter::new(LogicalOperator::LoOr).sub_filters((0..100).map(|_| single())));
+
+        assert!(filter.validate().is_err());
+    }
+
+    #[test]
+    fn test_empty_composite_filter() {
+        let filter = Filter::Composite(CompositeColumnValueFilter::new(LogicalOperator::LoAnd));
+
+        assert!(filter.validate().is_err());
+    }
+}
@@ -3,22 +3,32 @@ mod column;
 mod filter;
 mod primary_key;
 mod row;
+#[cfg(feature = "serde")]
+mod row_serde;
 pub(crate) mod rules;
 
 pub use column::*;
 pub use filter::*;
 pub use primary_key::*;
 pub use row::*;
+pub use rules::{MAX_REQUEST_SIZE, MAX_WRITE_REQUEST_SIZE};
 
 #[cfg(test)]
 mod test_model {
     use byteorder::{LittleEndian, ReadBytesExt};
 
-    use crate::protos::plain_buffer;
+    use crate::{
+        error::OtsError,
+        protos::{plain_buffer, DefinedColumnSchema, DefinedColumnType, PrimaryKeyOption, PrimaryKeySchema, PrimaryKeyType, TableMeta},
+        OtsOp,
+    };
 
-    use super::Row;
+    use super::{
+        rules::{find_undefined_columns, max_request_size, reorder_primary_key, validate_primary_key},
+        PrimaryKey, Row, MAX_REQUEST_SIZE, MAX_WRITE_REQUEST_SIZE,
+    };
 
-    use std::{io::Cursor, sync::Once};
+    use std::{collections::HashSet, io::Cursor, sync::Once};
 
     static INIT: Once = Once::new();
 
@@ -29,6 +39,124 @@ mod test_model {
         });
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_row_deserialize() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct DataTypes {
+            str_id: String,
+            int_col: i32,
+            double_col: f64,
+            bool_col: bool,
+            blob_col: Vec<u8>,
+            str_col: Option<String>,
+        }
+
+        let row = Row::new()
+            .primary_key_column_string("str_id", "1")
+            .column_integer("int_col", 100)
+            .column_double("double_col", 1.5)
+            .column_bool("bool_col", true)
+            .column_blob("blob_col", [1u8, 2, 3])
+            .column_string("not_in_struct", "ignored");
+
+        assert_eq!(
+            DataTypes {
+                str_id: "1".to_string(),
+                int_col: 100,
+                double_col: 1.5,
+                bool_col: true,
+                blob_col: vec![1, 2, 3],
+                str_col: None,
+            },
+            row.deserialize::<DataTypes>().unwrap()
+        );
+
+        let err = Row::new().primary_key_column_string("str_id", "1").deserialize::<DataTypes>().unwrap_err();
+        assert!(err.to_string().contains("int_col"), "{}", err);
+
+        let err = Row::new()
+            .primary_key_column_string("str_id", "1")
+            .column_string("int_col", "abc")
+            .deserialize::<DataTypes>()
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid type"), "{}", err);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_row_from_serialize() {
+        use crate::model::{ColumnValue, PrimaryKeyValue};
+
+        #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Order {
+            user_id: String,
+            order_id: i64,
+            amount: f64,
+            paid: bool,
+            payload: Vec<u8>,
+            remark: Option<String>,
+        }
+
+        let order = Order {
+            user_id: "u1".to_string(),
+            order_id: 10,
+            amount: 9.5,
+            paid: false,
+            payload: vec![0, 255],
+            remark: None,
+        };
+
+        let row = Row::from_serialize(&order, &["order_id", "user_id"]).unwrap();
+        assert_eq!(
+            vec![
+                ("order_id", PrimaryKeyValue::Integer(10)),
+                ("user_id", PrimaryKeyValue::String("u1".to_string()))
+            ],
+            row.primary_key.columns.iter().map(|c| (c.name.as_str(), c.value.clone())).collect::<Vec<_>>()
+        );
+        assert_eq!(3, row.columns.len());
+        assert_eq!(Some(&ColumnValue::Blob(vec![0, 255])), row.get_column_value("payload"));
+        assert!(row.get_column_value("remark").is_none());
+        assert_eq!(order, row.deserialize::<Order>().unwrap());
+
+        assert!(Row::from_serialize(&order, &["not_exist"]).is_err());
+        assert!(Row::from_serialize(&order, &["amount"]).is_err());
+        assert!(Row::from_serialize(&1, &[]).is_err());
+
+        // 通过 `serialize_bytes` 序列化的值也是二进制数据，其他类型的序列返回错误
+        struct Bytes(Vec<u8>);
+
+        impl serde::Serialize for Bytes {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        #[derive(serde::Serialize)]
+        struct Raw {
+            id: i64,
+            raw: Bytes,
+        }
+
+        #[derive(serde::Serialize)]
+        struct Tags {
+            id: i64,
+            tags: Vec<String>,
+        }
+
+        let row = Row::from_serialize(&Raw { id: 1, raw: Bytes(vec![1, 2]) }, &["id"]).unwrap();
+        assert_eq!(Some(&ColumnValue::Blob(vec![1, 2])), row.get_column_value("raw"));
+        assert!(Row::from_serialize(
+            &Tags {
+                id: 1,
+                tags: vec!["a".to_string()]
+            },
+            &["id"]
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_decode_plain_buffer() {
         setup();
@@ -41,4 +169,129 @@ mod test_model {
         let row = Row::read_plain_buffer(&mut cursor).unwrap();
         log::debug!("{:#?}", row);
     }
+
+    #[test]
+    fn test_max_request_size() {
+        assert_eq!(MAX_WRITE_REQUEST_SIZE, max_request_size(OtsOp::BatchWriteRow));
+        assert_eq!(MAX_WRITE_REQUEST_SIZE, max_request_size(OtsOp::PutRow));
+        assert_eq!(MAX_REQUEST_SIZE, max_request_size(OtsOp::GetRange));
+    }
+
+    #[test]
+    fn test_find_undefined_columns() {
+        let table_meta = TableMeta {
+            table_name: "users".to_string(),
+            primary_key: vec![PrimaryKeySchema {
+                name: "user_id".to_string(),
+                r#type: PrimaryKeyType::String as i32,
+                option: None,
+            }],
+            defined_column: vec![DefinedColumnSchema {
+                name: "full_name".to_string(),
+                r#type: DefinedColumnType::DctString as i32,
+            }],
+        };
+
+        let columns = ["user_id", "full_name", "phnoe_number", "agee"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<HashSet<_>>();
+
+        assert_eq!(vec!["agee", "phnoe_number"], find_undefined_columns(&table_meta, &columns));
+    }
+
+    #[test]
+    fn test_reorder_primary_key() {
+        let table_meta = TableMeta {
+            table_name: "schools".to_string(),
+            primary_key: vec![
+                PrimaryKeySchema {
+                    name: "school_id".to_string(),
+                    r#type: PrimaryKeyType::String as i32,
+                    option: None,
+                },
+                PrimaryKeySchema {
+                    name: "id".to_string(),
+                    r#type: PrimaryKeyType::Integer as i32,
+                    option: None,
+                },
+            ],
+            defined_column: vec![],
+        };
+
+        let mut pk = PrimaryKey::new().column_integer("id", 1).column_string("school_id", "2");
+        assert!(reorder_primary_key(&table_meta, &mut pk).is_ok());
+        assert_eq!(vec!["school_id", "id"], pk.columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>());
+        assert_eq!(PrimaryKey::new().column_string("school_id", "2").column_integer("id", 1), pk);
+
+        let mut pk = PrimaryKey::new().column_integer("id", 1);
+        assert!(reorder_primary_key(&table_meta, &mut pk).is_err());
+
+        let mut pk = PrimaryKey::new()
+            .column_integer("id", 1)
+            .column_string("school_id", "2")
+            .column_string("name", "a");
+        assert!(reorder_primary_key(&table_meta, &mut pk).is_err());
+
+        let mut pk = PrimaryKey::new()
+            .column_integer("id", 1)
+            .column_string("school_id", "2")
+            .column_integer("id", 2);
+        assert!(reorder_primary_key(&table_meta, &mut pk).is_err());
+    }
+
+    #[test]
+    fn test_validate_primary_key() {
+        let table_meta = TableMeta {
+            table_name: "schools".to_string(),
+            primary_key: vec![
+                PrimaryKeySchema {
+                    name: "school_id".to_string(),
+                    r#type: PrimaryKeyType::String as i32,
+                    option: None,
+                },
+                PrimaryKeySchema {
+                    name: "id".to_string(),
+                    r#type: PrimaryKeyType::Integer as i32,
+                    option: Some(PrimaryKeyOption::AutoIncrement as i32),
+                },
+            ],
+            defined_column: vec![],
+        };
+
+        let valid = [
+            PrimaryKey::new().column_string("school_id", "1").column_integer("id", 1),
+            PrimaryKey::new().column_string("school_id", "1").column_auto_increment("id"),
+            PrimaryKey::new().column_inf_min("school_id").column_info_max("id"),
+        ];
+
+        for pk in &valid {
+            assert!(validate_primary_key(&table_meta, pk).is_ok(), "{:?}", pk);
+        }
+
+        let invalid = [
+            // 类型不匹配
+            PrimaryKey::new().column_integer("school_id", 1).column_integer("id", 1),
+            PrimaryKey::new().column_string("school_id", "1").column_string("id", "1"),
+            PrimaryKey::new().column_binary("school_id", vec![1]).column_integer("id", 1),
+            // 顺序不对
+            PrimaryKey::new().column_integer("id", 1).column_string("school_id", "1"),
+            // 个数不对
+            PrimaryKey::new().column_string("school_id", "1"),
+            PrimaryKey::new()
+                .column_string("school_id", "1")
+                .column_integer("id", 1)
+                .column_integer("id2", 1),
+        ];
+
+        for pk in &invalid {
+            assert!(matches!(validate_primary_key(&table_meta, pk), Err(OtsError::ValidationFailed(_))), "{:?}", pk);
+        }
+
+        // 非自增列不能使用自增值
+        let mut table_meta = table_meta;
+        table_meta.primary_key[1].option = None;
+        let pk = PrimaryKey::new().column_string("school_id", "1").column_auto_increment("id");
+        assert!(validate_primary_key(&table_meta, &pk).is_err());
+    }
 }
@@ -1,5 +1,6 @@
-use std::io::{Cursor, Read, Write};
+use std::io::{Cursor, Write};
 
+use base64::{prelude::BASE64_STANDARD, Engine};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use crate::{
@@ -12,8 +13,10 @@ use crate::{
     OtsResult,
 };
 
+use super::Row;
+
 /// 主键容器
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PrimaryKey {
     pub columns: Vec<PrimaryKeyColumn>,
 }
@@ -82,6 +85,35 @@ impl PrimaryKey {
         c
     }
 
+    /// 将主键编码为一个紧凑的字符串（plain buffer 编码后的 base64），用来持久化 `GetRange` 或者 `BulkExport` 的断点。
+    /// 可以通过 [`from_checkpoint`](`Self::from_checkpoint`) 还原。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aliyun_tablestore_rs::model::PrimaryKey;
+    ///
+    /// // 实际使用时是响应中的 `next_start_primary_key`
+    /// let next_start_pk = PrimaryKey::new().column_string("str_id", "a").column_integer("id", 10);
+    /// let token = next_start_pk.to_checkpoint();
+    ///
+    /// // 保存 token，之后从断点继续读取
+    /// let start_pk = PrimaryKey::from_checkpoint(&token).unwrap();
+    /// assert_eq!(next_start_pk, start_pk);
+    /// ```
+    pub fn to_checkpoint(&self) -> String {
+        BASE64_STANDARD.encode(self.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM))
+    }
+
+    /// 从 [`to_checkpoint`](`Self::to_checkpoint`) 生成的字符串还原主键
+    pub fn from_checkpoint(checkpoint: &str) -> OtsResult<Self> {
+        let bytes = BASE64_STANDARD
+            .decode(checkpoint)
+            .map_err(|e| OtsError::ValidationFailed(format!("invalid primary key checkpoint: {}", e)))?;
+
+        Ok(Row::decode_plain_buffer(bytes, MASK_HEADER | MASK_ROW_CHECKSUM)?.primary_key)
+    }
+
     pub fn new() -> Self {
         Self { columns: vec![] }
     }
@@ -266,7 +298,7 @@ impl PrimaryKeyValue {
 }
 
 /// 主键列
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PrimaryKeyColumn {
     /// 列名
     pub name: String,
@@ -343,9 +375,7 @@ impl PrimaryKeyColumn {
 
             match tag {
                 plain_buffer::TAG_CELL_NAME => {
-                    let len = cursor.read_u32::<LittleEndian>()? as usize;
-                    let mut buf: Vec<u8> = vec![0u8; len];
-                    cursor.read_exact(&mut buf)?;
+                    let buf = plain_buffer::read_len_prefixed_bytes(cursor)?;
                     name = String::from_utf8(buf)?;
                 }
 
@@ -357,21 +387,19 @@ impl PrimaryKeyColumn {
                         plain_buffer::VT_INTEGER => PrimaryKeyValue::Integer(cursor.read_i64::<LittleEndian>()?),
 
                         plain_buffer::VT_STRING => {
-                            let len = cursor.read_u32::<LittleEndian>()? as usize;
-                            let mut buf: Vec<u8> = vec![0u8; len];
-
-                            cursor.read_exact(&mut buf)?;
+                            let buf = plain_buffer::read_len_prefixed_bytes(cursor)?;
                             PrimaryKeyValue::String(String::from_utf8(buf)?)
                         }
 
                         plain_buffer::VT_BLOB => {
-                            let len = cursor.read_u32::<LittleEndian>()? as usize;
-                            let mut buf: Vec<u8> = vec![0u8; len];
-
-                            cursor.read_exact(&mut buf)?;
+                            let buf = plain_buffer::read_len_prefixed_bytes(cursor)?;
                             PrimaryKeyValue::Binary(buf)
                         }
 
+                        plain_buffer::VT_INF_MIN => PrimaryKeyValue::InfMin,
+                        plain_buffer::VT_INF_MAX => PrimaryKeyValue::InfMax,
+                        plain_buffer::VT_AUTO_INCREMENT => PrimaryKeyValue::AutoIncrement,
+
                         _ => return Err(OtsError::PlainBufferError(format!("unknown primary key cell value type: {}", cell_value_type))),
                     };
                 }
@@ -390,10 +418,10 @@ impl PrimaryKeyColumn {
         let cell_checksum = pk_col.crc8_checksum();
 
         if cell_checksum != checksum {
-            return Err(OtsError::PlainBufferError(format!(
-                "primary key cell checksum validation failed. calculated: {}, received: {}",
-                cell_checksum, checksum
-            )));
+            return Err(OtsError::ChecksumMismatch {
+                calculated: cell_checksum,
+                received: checksum,
+            });
         }
 
         Ok(pk_col)
@@ -464,4 +492,24 @@ mod test_primary_key {
         assert_eq!(bytes_from_java_sdk, &buf[..]);
         println!("{:?}", buf);
     }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let pk = PrimaryKey::new()
+            .column_string("school_id", "00020FFB-BB14-CCAD-0181-A929E71C7312")
+            .column_integer("id", 1742203524276000)
+            .column_binary("bin_id", [0x01u8, 0x02, 0xFF])
+            .column_inf_min("tail");
+
+        let checkpoint = pk.to_checkpoint();
+        let restored = PrimaryKey::from_checkpoint(&checkpoint).unwrap();
+
+        assert_eq!(pk, restored);
+    }
+
+    #[test]
+    fn test_invalid_checkpoint() {
+        assert!(PrimaryKey::from_checkpoint("not a checkpoint!").is_err());
+        assert!(PrimaryKey::from_checkpoint("AAAA").is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
@@ -6,20 +6,62 @@ use crate::{
     crc8::crc_u8,
     error::OtsError,
     protos::plain_buffer::{
-        self, HEADER, LITTLE_ENDIAN_32_SIZE, MASK_HEADER, MASK_ROW_CHECKSUM, TAG_DELETE_ROW_MARKER, TAG_ROW_CHECKSUM, TAG_ROW_DATA, TAG_ROW_PK,
+        self, HEADER, LITTLE_ENDIAN_32_SIZE, MASK_HEADER, MASK_ROW_CHECKSUM, TAG_CELL, TAG_CELL_CHECKSUM, TAG_CELL_NAME, TAG_CELL_OP, TAG_CELL_TIMESTAMP,
+        TAG_CELL_VALUE, TAG_DELETE_ROW_MARKER, TAG_ROW_CHECKSUM, TAG_ROW_DATA, TAG_ROW_PK,
     },
     OtsResult,
 };
 
 use super::{Column, ColumnOp, ColumnValue, PrimaryKey, PrimaryKeyColumn, PrimaryKeyValue};
 
+/// 二进制数据在 [`Row::display_pretty`] 中最多输出的字节数
+const PRETTY_BYTES_LIMIT: usize = 8;
+
+fn abbreviate_bytes(bytes: &[u8]) -> String {
+    let hex = bytes
+        .iter()
+        .take(PRETTY_BYTES_LIMIT)
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if bytes.len() > PRETTY_BYTES_LIMIT {
+        format!("<{} bytes: {} ...>", bytes.len(), hex)
+    } else {
+        format!("<{} bytes: {}>", bytes.len(), hex)
+    }
+}
+
+/// 根据解码出来的主键列、数据列和删除标记计算行校验码，和数据中的校验码比较
+fn verify_row_checksum(pk_columns: &[PrimaryKeyColumn], columns: &[Column], deleted: bool, received: u8) -> OtsResult<()> {
+    let mut calculated = 0u8;
+    for key_col in pk_columns {
+        calculated = crc_u8(calculated, key_col.crc8_checksum());
+    }
+
+    for col in columns {
+        calculated = crc_u8(calculated, col.crc8_checksum());
+    }
+
+    calculated = crc_u8(calculated, if deleted { 1u8 } else { 0u8 });
+
+    if calculated != received {
+        return Err(OtsError::ChecksumMismatch { calculated, received });
+    }
+
+    Ok(())
+}
+
 /// 宽表模型的行
 #[derive(Debug, Clone, Default)]
 pub struct Row {
     /// 主键列
     pub primary_key: PrimaryKey,
 
-    /// 数据列
+    /// 数据列。
+    ///
+    /// 从响应中解码的行，数据列的顺序和服务端返回的顺序相同（按照列名排序，同名的列按照时间戳从新到旧排列），
+    /// 同一份数据每次解码得到的顺序都是一样的
     pub columns: Vec<Column>,
 
     /// 是否要删除行
@@ -51,6 +93,78 @@ impl Row {
         self.columns.iter().find(|c| c.name.as_str() == name).map(|c| &c.value)
     }
 
+    /// 将行格式化为便于阅读的多行文本，主要用于调试和日志输出。
+    ///
+    /// 每一列占一行，包含类型、列名和值，数据列如果有时间戳会以 `@ timestamp` 的形式输出，
+    /// 二进制的值只输出前 8 个字节。例如：
+    ///
+    /// ```text
+    /// primary keys:
+    ///   [String ] school_id = "1"
+    ///   [Integer] id        = 1742373697699000
+    /// columns:
+    ///   [String ] name      = "School-A" @ 1742378007415
+    ///   [Blob   ] avatar    = <16 bytes: 01 02 03 04 05 06 07 08 ...>
+    /// ```
+    pub fn display_pretty(&self) -> String {
+        let name_width = self
+            .primary_key
+            .columns
+            .iter()
+            .map(|pk| pk.name.len())
+            .chain(self.columns.iter().map(|c| c.name.len()))
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = vec!["primary keys:".to_string()];
+
+        for pk in &self.primary_key.columns {
+            let (type_name, value) = match &pk.value {
+                PrimaryKeyValue::Integer(n) => ("Integer", n.to_string()),
+                PrimaryKeyValue::String(s) => ("String", format!("{:?}", s)),
+                PrimaryKeyValue::Binary(bytes) => ("Binary", abbreviate_bytes(bytes)),
+                PrimaryKeyValue::InfMax => ("InfMax", "INF_MAX".to_string()),
+                PrimaryKeyValue::InfMin => ("InfMin", "INF_MIN".to_string()),
+                PrimaryKeyValue::AutoIncrement => ("AutoIncrement", "AUTO_INCREMENT".to_string()),
+            };
+
+            lines.push(format!("  [{:<7}] {:<name_width$} = {}", type_name, pk.name, value));
+        }
+
+        lines.push("columns:".to_string());
+
+        for col in &self.columns {
+            let value = match &col.value {
+                ColumnValue::Null => "NULL".to_string(),
+                ColumnValue::Integer(n) => n.to_string(),
+                ColumnValue::Double(d) => d.to_string(),
+                ColumnValue::Boolean(b) => b.to_string(),
+                ColumnValue::String(s) => format!("{:?}", s),
+                ColumnValue::Blob(bytes) => abbreviate_bytes(bytes),
+                ColumnValue::InfMin => "INF_MIN".to_string(),
+                ColumnValue::InfMax => "INF_MAX".to_string(),
+            };
+
+            let mut line = format!("  [{:<7}] {:<name_width$} = {}", col.value.type_name(), col.name, value);
+
+            if let Some(ts) = col.timestamp {
+                line.push_str(&format!(" @ {}", ts));
+            }
+
+            if let Some(op) = &col.op {
+                line.push_str(&format!(" ({:?})", op));
+            }
+
+            lines.push(line);
+        }
+
+        if self.deleted {
+            lines.push("(delete marker)".to_string());
+        }
+
+        lines.join("\n")
+    }
+
     /// 计算一个行的 plain buffer
     pub(crate) fn compute_size(&self, masks: u32) -> u32 {
         let mut size = if masks & MASK_HEADER == MASK_HEADER { LITTLE_ENDIAN_32_SIZE } else { 0u32 };
@@ -78,6 +192,11 @@ impl Row {
 
     /// 解码 plain buffer
     pub(crate) fn decode_plain_buffer(bytes: Vec<u8>, masks: u32) -> OtsResult<Self> {
+        Self::decode_plain_buffer_checked(bytes, masks, false)
+    }
+
+    /// 解码 plain buffer。`verify_checksum` 为 `true` 的时候在解码的同时校验行校验码，见 [`Row::read_plain_buffer_checked`]
+    pub(crate) fn decode_plain_buffer_checked(bytes: Vec<u8>, masks: u32, verify_checksum: bool) -> OtsResult<Self> {
         let mut cursor = Cursor::new(bytes);
 
         if masks & MASK_HEADER == MASK_HEADER {
@@ -88,7 +207,7 @@ impl Row {
             }
         }
 
-        Row::read_plain_buffer(&mut cursor)
+        Row::read_plain_buffer_checked(&mut cursor, verify_checksum)
     }
 
     pub(crate) fn write_plain_buffer(&self, cursor: &mut Cursor<Vec<u8>>, _masks: u32) {
@@ -117,15 +236,28 @@ impl Row {
 
     /// 从 cursor 构建行
     pub(crate) fn read_plain_buffer(cursor: &mut Cursor<Vec<u8>>) -> OtsResult<Self> {
+        Self::read_plain_buffer_checked(cursor, false)
+    }
+
+    /// 从 cursor 构建行。
+    ///
+    /// 单元格的校验码总是会校验。数据中的最后一行读取到行校验码标记时就结束了，
+    /// `verify_checksum` 为 `true` 的时候也会校验最后一行的行校验码
+    pub(crate) fn read_plain_buffer_checked(cursor: &mut Cursor<Vec<u8>>, verify_checksum: bool) -> OtsResult<Self> {
         let mut row_type: RowType = RowType::PrimaryKey;
         let mut pk_columns = vec![];
         let mut columns = vec![];
+        let mut deleted = false;
 
         loop {
             let tag = cursor.read_u8()?;
             // log::debug!("tag = 0x{:02X}, pos = 0x{:02X}, len = 0x{:02X}", tag, cursor.position(), cursor.get_ref().len());
             if cursor.position() as usize >= cursor.get_ref().len() - 1 {
                 // log::debug!("read to stream end");
+                if verify_checksum && tag == plain_buffer::TAG_ROW_CHECKSUM {
+                    verify_row_checksum(&pk_columns, &columns, deleted, cursor.read_u8()?)?;
+                }
+
                 break;
             }
 
@@ -154,29 +286,14 @@ impl Row {
                     }
                 },
 
+                plain_buffer::TAG_DELETE_ROW_MARKER => {
+                    deleted = true;
+                }
+
                 plain_buffer::TAG_ROW_CHECKSUM => {
                     // log::debug!("TAG_ROW_CHECKSUM read");
                     let checksum = cursor.read_u8()?;
-
-                    let mut row_checksum = 0u8;
-                    for key_col in &pk_columns {
-                        // log::debug!("primary key: {:#?}", key_col);
-                        row_checksum = crc_u8(row_checksum, key_col.crc8_checksum());
-                    }
-
-                    for col in &columns {
-                        // log::debug!("column: {:#?}", col);
-                        row_checksum = crc_u8(row_checksum, col.crc8_checksum());
-                    }
-
-                    row_checksum = crc_u8(row_checksum, 0u8);
-
-                    if row_checksum != checksum {
-                        return Err(OtsError::PlainBufferError(format!(
-                            "data data checksum validation failed. calculated: {}, received: {}",
-                            row_checksum, checksum
-                        )));
-                    }
+                    verify_row_checksum(&pk_columns, &columns, deleted, checksum)?;
                     break;
                 }
 
@@ -187,7 +304,7 @@ impl Row {
         Ok(Self {
             primary_key: PrimaryKey { columns: pk_columns },
             columns,
-            deleted: false,
+            deleted,
         })
     }
 
@@ -324,7 +441,7 @@ impl Row {
         self
     }
 
-    /// 添加要删除指定版本值的列
+    /// 添加要删除指定版本值的列。`timestamp` 是要删除的版本的时间戳，单位为毫秒
     pub fn column_to_delete(mut self, name: &str, timestamp: u64) -> Self {
         self.columns.push(Column {
             name: name.to_string(),
@@ -336,6 +453,11 @@ impl Row {
         self
     }
 
+    /// 添加要删除指定版本值的列，同 [`Row::column_to_delete`]。只删除时间戳为 `timestamp_ms` 的版本，其他版本不受影响
+    pub fn column_to_delete_version(self, name: &str, timestamp_ms: u64) -> Self {
+        self.column_to_delete(name, timestamp_ms)
+    }
+
     /// 添加要删除全部版本的列
     pub fn column_to_delete_all_versions(mut self, name: &str) -> Self {
         self.columns.push(Column {
@@ -422,17 +544,185 @@ pub(crate) fn decode_plainbuf_rows(bytes: Vec<u8>, masks: u32) -> OtsResult<Vec<
     Ok(rows)
 }
 
+/// 从 plain buffer 数据流中逐行读取。
+///
+/// 每次只把一行的字节读入内存并解码，不需要一次性持有所有行。读取到流的末尾时结束，
+/// 出错之后不再继续读取
+pub(crate) struct RowReader<R> {
+    reader: R,
+    masks: u32,
+
+    /// 是否已经读取并校验过头部
+    header_read: bool,
+
+    /// 是否已经读取完毕或者出错
+    finished: bool,
+}
+
+impl<R: Read> RowReader<R> {
+    /// 设置了 `MASK_HEADER` 的时候，第一次读取时校验消息头
+    pub(crate) fn new(reader: R, masks: u32) -> Self {
+        Self {
+            reader,
+            masks,
+            header_read: false,
+            finished: false,
+        }
+    }
+
+    /// 读取一个字节。流已经结束时返回 `None`
+    fn read_u8_or_eof(&mut self) -> OtsResult<Option<u8>> {
+        let mut buf = [0u8; 1];
+
+        loop {
+            match self.reader.read(&mut buf) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(buf[0])),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// 从流中复制 `n` 个字节到行数据中
+    fn copy_bytes(&mut self, buf: &mut Vec<u8>, n: u64) -> OtsResult<()> {
+        let copied = (&mut self.reader).take(n).read_to_end(buf)? as u64;
+
+        if copied != n {
+            return Err(OtsError::PlainBufferError(format!(
+                "unexpected end of stream: expect {} bytes, got {}",
+                n, copied
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 复制 4 字节的长度，以及后面对应长度的数据
+    fn copy_len_prefixed_bytes(&mut self, buf: &mut Vec<u8>) -> OtsResult<()> {
+        let len = self.reader.read_u32::<LittleEndian>()?;
+        buf.write_u32::<LittleEndian>(len)?;
+
+        self.copy_bytes(buf, len as u64)
+    }
+
+    /// 读取一行的原始字节，包括末尾的行校验码。流已经结束时返回 `None`
+    fn read_row_bytes(&mut self) -> OtsResult<Option<Vec<u8>>> {
+        if !self.header_read {
+            self.header_read = true;
+
+            if self.masks & MASK_HEADER == MASK_HEADER {
+                let mut buf = [0u8; 4];
+                let n = (&mut self.reader).take(4).read(&mut buf)?;
+
+                // 空的数据没有消息头
+                if n == 0 {
+                    return Ok(None);
+                }
+
+                self.reader.read_exact(&mut buf[n..])?;
+                let header = u32::from_le_bytes(buf);
+
+                if header != HEADER {
+                    return Err(OtsError::PlainBufferError(format!("invalid message header: {}", header)));
+                }
+            }
+        }
+
+        let mut buf = match self.read_u8_or_eof()? {
+            Some(tag) => vec![tag],
+            None => return Ok(None),
+        };
+
+        loop {
+            let tag = buf[buf.len() - 1];
+
+            match tag {
+                TAG_ROW_PK | TAG_ROW_DATA | TAG_CELL | TAG_DELETE_ROW_MARKER => {}
+                TAG_CELL_NAME | TAG_CELL_VALUE => self.copy_len_prefixed_bytes(&mut buf)?,
+                TAG_CELL_OP | TAG_CELL_CHECKSUM => self.copy_bytes(&mut buf, 1)?,
+                TAG_CELL_TIMESTAMP => self.copy_bytes(&mut buf, 8)?,
+
+                TAG_ROW_CHECKSUM => {
+                    self.copy_bytes(&mut buf, 1)?;
+                    return Ok(Some(buf));
+                }
+
+                _ => return Err(OtsError::PlainBufferError(format!("invalid tag: {}", tag))),
+            }
+
+            match self.read_u8_or_eof()? {
+                Some(tag) => buf.push(tag),
+                None => return Err(OtsError::PlainBufferError("unexpected end of stream: row checksum is missing".to_string())),
+            }
+        }
+    }
+
+    fn read_row(&mut self) -> OtsResult<Option<Row>> {
+        let bytes = match self.read_row_bytes()? {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+
+        let checksum = bytes[bytes.len() - 1];
+        let row = Row::read_plain_buffer(&mut Cursor::new(bytes))?;
+
+        // 单独一行的数据在读取到行校验码标记的时候就结束了，所以要在这里校验
+        let row_checksum = row.crc8_checksum();
+        if row_checksum != checksum {
+            return Err(OtsError::ChecksumMismatch {
+                calculated: row_checksum,
+                received: checksum,
+            });
+        }
+
+        Ok(Some(row))
+    }
+}
+
+impl<R: Read> Iterator for RowReader<R> {
+    type Item = OtsResult<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let item = self.read_row().transpose();
+
+        if !matches!(item, Some(Ok(_))) {
+            self.finished = true;
+        }
+
+        item
+    }
+}
+
 #[cfg(test)]
 mod test_row {
+    use std::io::{Cursor, Read};
+
     use base64::{prelude::BASE64_STANDARD, Engine};
 
     use crate::{
-        model::{Column, ColumnValue, PrimaryKey, PrimaryKeyColumn},
+        error::OtsError,
+        model::{Column, ColumnOp, ColumnValue, PrimaryKey, PrimaryKeyColumn},
         protos::plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM},
         test_util::setup,
+        OtsResult,
     };
 
-    use super::Row;
+    use super::{decode_plainbuf_rows, encode_plainbuf_rows, Row, RowReader};
+
+    /// 每次只返回一个字节，模拟分多次到达的数据
+    struct OneByteReader<R>(R);
+
+    impl<R: Read> Read for OneByteReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(1);
+            self.0.read(&mut buf[..n])
+        }
+    }
 
     // #[tokio::test]
     // async fn get_row_no_col() {
@@ -574,4 +864,169 @@ mod test_row {
 
         assert_eq!(md5_expected, md5_calc);
     }
+
+    #[test]
+    fn test_decode_preserves_column_order() {
+        let row = Row::new()
+            .primary_key(PrimaryKey::new().column_string("pk", "id"))
+            .column(Column {
+                timestamp: Some(2000),
+                ..Column::from_integer("b", 2)
+            })
+            .column(Column {
+                timestamp: Some(1000),
+                ..Column::from_integer("b", 1)
+            })
+            .column(Column::from_string("a", "first"))
+            .column(Column::from_bool("c", true));
+
+        let bytes = row.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM);
+
+        let decoded_1 = Row::decode_plain_buffer(bytes.clone(), MASK_HEADER).unwrap();
+        let decoded_2 = Row::decode_plain_buffer(bytes, MASK_HEADER).unwrap();
+
+        let names_1 = decoded_1.columns.iter().map(|c| (c.name.as_str(), c.timestamp)).collect::<Vec<_>>();
+        let names_2 = decoded_2.columns.iter().map(|c| (c.name.as_str(), c.timestamp)).collect::<Vec<_>>();
+
+        assert_eq!(names_1, names_2);
+        assert_eq!(vec![("b", Some(2000)), ("b", Some(1000)), ("a", None), ("c", None)], names_1);
+    }
+
+    #[test]
+    fn test_row_reader() {
+        let rows = vec![
+            Row::new().primary_key(PrimaryKey::new().column_string("pk", "only-pk")),
+            Row::new()
+                .primary_key(PrimaryKey::new().column_string("pk", "a").column_integer("id", 1))
+                .column(Column {
+                    timestamp: Some(1742378007415),
+                    ..Column::from_string("name", "School-A")
+                })
+                .column(Column::from_double("score", 98.5))
+                .column(Column::from_bool("active", true))
+                .column_blob("avatar", (1u8..=16).collect::<Vec<_>>()),
+            Row::new()
+                .primary_key(PrimaryKey::new().column_string("pk", "b").column_integer("id", 2))
+                .column(Column::from_integer("age", 30)),
+        ];
+
+        let bytes = encode_plainbuf_rows(rows, MASK_HEADER);
+        let path = std::env::temp_dir().join(format!("aliyun-tablestore-row-reader-{}.data", std::process::id()));
+        std::fs::write(&path, &bytes).unwrap();
+
+        let batch = decode_plainbuf_rows(bytes, MASK_HEADER).unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let streamed = RowReader::new(OneByteReader(std::io::BufReader::new(file)), MASK_HEADER)
+            .collect::<OtsResult<Vec<_>>>()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(3, streamed.len());
+        assert_eq!(format!("{:?}", batch), format!("{:?}", streamed));
+    }
+
+    #[test]
+    fn test_row_reader_invalid_data() {
+        assert_eq!(0, RowReader::new(Cursor::new(vec![]), MASK_HEADER).count());
+
+        let row = Row::new()
+            .primary_key(PrimaryKey::new().column_string("pk", "a"))
+            .column(Column::from_integer("age", 30));
+        let mut bytes = encode_plainbuf_rows(vec![row.clone(), row], MASK_HEADER);
+
+        // 损坏最后一行的行校验码
+        let last = bytes.len() - 1;
+        bytes[last] = bytes[last].wrapping_add(1);
+
+        let mut reader = RowReader::new(Cursor::new(bytes.clone()), MASK_HEADER);
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        // 数据被截断
+        bytes.truncate(last);
+        let result = RowReader::new(Cursor::new(bytes.clone()), MASK_HEADER).collect::<OtsResult<Vec<_>>>();
+        assert!(result.is_err());
+
+        // 消息头不正确
+        bytes[0] = 0;
+        assert!(RowReader::new(Cursor::new(bytes), MASK_HEADER).next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_encode_delete_version() {
+        let row = Row::new()
+            .primary_key(PrimaryKey::new().column_string("pk", "a"))
+            .column_to_delete_version("col", 2000)
+            .column_to_delete_all_versions("other");
+
+        let bytes = row.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM);
+        let decoded = Row::decode_plain_buffer(bytes, MASK_HEADER).unwrap();
+
+        assert_eq!(2, decoded.columns.len());
+        assert_eq!(Some(ColumnOp::Delete), decoded.columns[0].op);
+        assert_eq!(Some(2000), decoded.columns[0].timestamp);
+        assert_eq!(ColumnValue::Null, decoded.columns[0].value);
+        assert_eq!(Some(ColumnOp::DeleteAll), decoded.columns[1].op);
+        assert_eq!(None, decoded.columns[1].timestamp);
+    }
+
+    #[test]
+    fn test_verify_checksums() {
+        let row = Row::new()
+            .primary_key(PrimaryKey::new().column_string("pk", "a"))
+            .column(Column::from_integer("age", 30));
+        let bytes = row.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM);
+
+        assert!(Row::decode_plain_buffer_checked(bytes.clone(), MASK_HEADER, true).is_ok());
+
+        // 整数值的最低位字节在单元格校验码、行校验码标记和行校验码之前
+        let mut corrupted = bytes.clone();
+        let value_pos = corrupted.len() - 2 - 2 - 8;
+        corrupted[value_pos] ^= 0x01;
+        assert!(matches!(
+            Row::decode_plain_buffer(corrupted.clone(), MASK_HEADER),
+            Err(OtsError::ChecksumMismatch { .. })
+        ));
+        assert!(matches!(
+            Row::decode_plain_buffer_checked(corrupted, MASK_HEADER, true),
+            Err(OtsError::ChecksumMismatch { .. })
+        ));
+
+        // 只有行校验码损坏的时候，不开启校验的单行解码不会发现
+        let mut corrupted = bytes;
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 0x01;
+        assert!(Row::decode_plain_buffer(corrupted.clone(), MASK_HEADER).is_ok());
+        assert!(matches!(
+            Row::decode_plain_buffer_checked(corrupted, MASK_HEADER, true),
+            Err(OtsError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_display_pretty() {
+        let row = Row::new()
+            .primary_key_column_string("school_id", "1")
+            .primary_key_column_integer("id", 1742373697699000)
+            .column(Column {
+                name: "name".to_string(),
+                value: ColumnValue::String("School-A".to_string()),
+                timestamp: Some(1742378007415),
+                ..Default::default()
+            })
+            .column_blob("avatar", (1u8..=16).collect::<Vec<_>>());
+
+        let expected = [
+            "primary keys:",
+            "  [String ] school_id = \"1\"",
+            "  [Integer] id        = 1742373697699000",
+            "columns:",
+            "  [String ] name      = \"School-A\" @ 1742378007415",
+            "  [Blob   ] avatar    = <16 bytes: 01 02 03 04 05 06 07 08 ...>",
+        ]
+        .join("\n");
+
+        assert_eq!(expected, row.display_pretty());
+    }
 }
@@ -0,0 +1,567 @@
+//! 使用 serde 在行和结构体之间转换。需要开启 `serde` 特性
+use std::collections::HashSet;
+
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor},
+    ser::{self, Impossible, Serialize},
+};
+
+use crate::{error::OtsError, OtsResult};
+
+use super::{Column, ColumnValue, PrimaryKeyColumn, PrimaryKeyValue, Row};
+
+impl de::Error for OtsError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        OtsError::SerdeError(msg.to_string())
+    }
+}
+
+impl ser::Error for OtsError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        OtsError::SerdeError(msg.to_string())
+    }
+}
+
+/// 行中的一个值，可能来自主键列或者数据列
+#[derive(Clone, Copy)]
+enum FieldValue<'a> {
+    PrimaryKey(&'a PrimaryKeyValue),
+    Column(&'a ColumnValue),
+}
+
+impl<'de> de::Deserializer<'de> for FieldValue<'_> {
+    type Error = OtsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::PrimaryKey(PrimaryKeyValue::Integer(n)) | Self::Column(ColumnValue::Integer(n)) => visitor.visit_i64(*n),
+            Self::PrimaryKey(PrimaryKeyValue::String(s)) | Self::Column(ColumnValue::String(s)) => visitor.visit_str(s),
+            // `Vec<u8>` 是按照序列反序列化的
+            Self::PrimaryKey(PrimaryKeyValue::Binary(b)) | Self::Column(ColumnValue::Blob(b)) => {
+                visitor.visit_seq(de::value::SeqDeserializer::<_, OtsError>::new(b.iter().copied()))
+            }
+            Self::Column(ColumnValue::Double(d)) => visitor.visit_f64(*d),
+            Self::Column(ColumnValue::Boolean(b)) => visitor.visit_bool(*b),
+            Self::Column(ColumnValue::Null) => visitor.visit_none(),
+            Self::PrimaryKey(v) => Err(OtsError::SerdeError(format!("can not deserialize primary key value: {:?}", v))),
+            Self::Column(v) => Err(OtsError::SerdeError(format!("can not deserialize column value: {:?}", v))),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::PrimaryKey(PrimaryKeyValue::Binary(b)) | Self::Column(ColumnValue::Blob(b)) => visitor.visit_bytes(b),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Column(ColumnValue::Null) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// 按照名称依次访问行中的主键列和数据列
+struct RowMapAccess<'a> {
+    fields: std::vec::IntoIter<(&'a str, FieldValue<'a>)>,
+    value: Option<FieldValue<'a>>,
+}
+
+impl<'de> MapAccess<'de> for RowMapAccess<'_> {
+    type Error = OtsError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        match self.fields.next() {
+            Some((name, value)) => {
+                self.value = Some(value);
+                seed.deserialize(name.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(OtsError::SerdeError("value is missing".to_string())),
+        }
+    }
+}
+
+struct RowDeserializer<'a> {
+    row: &'a Row,
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'_> {
+    type Error = OtsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let mut names = HashSet::new();
+        let mut fields = vec![];
+
+        for pk in &self.row.primary_key.columns {
+            if names.insert(pk.name.as_str()) {
+                fields.push((pk.name.as_str(), FieldValue::PrimaryKey(&pk.value)));
+            }
+        }
+
+        // 同名的列按照时间戳从新到旧排列，只使用最新的版本
+        for col in &self.row.columns {
+            if names.insert(col.name.as_str()) {
+                fields.push((col.name.as_str(), FieldValue::Column(&col.value)));
+            }
+        }
+
+        visitor.visit_map(RowMapAccess {
+            fields: fields.into_iter(),
+            value: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl Row {
+    /// 按照名称把主键列和数据列映射为结构体的字段。需要开启 `serde` 特性。
+    ///
+    /// - 整数、浮点数、字符串、布尔值分别映射为对应的 Rust 类型，二进制数据映射为 `Vec<u8>`；
+    /// - 同名的列有多个版本的时候，使用最新的版本；
+    /// - 缺少的字段返回包含字段名称的错误，`Option` 类型的字段可以缺少，结构体中没有的列会被忽略。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// #[derive(serde::Deserialize)]
+    /// struct User {
+    ///     user_id: String,
+    ///     age: i64,
+    ///     nick_name: Option<String>,
+    /// }
+    ///
+    /// let user: User = row.deserialize()?;
+    /// ```
+    pub fn deserialize<T: DeserializeOwned>(&self) -> OtsResult<T> {
+        T::deserialize(RowDeserializer { row: self })
+    }
+}
+
+/// 把一个字段的值序列化为列的值。`None` 和 unit 序列化为 `Ok(None)`，表示不写入这个字段
+struct ValueSerializer;
+
+fn unsupported<T>(what: &str) -> OtsResult<T> {
+    Err(OtsError::SerdeError(format!("can not serialize {} as column value", what)))
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Option<ColumnValue>;
+    type Error = OtsError;
+    type SerializeSeq = BytesSerializer;
+    type SerializeTuple = BytesSerializer;
+    type SerializeTupleStruct = Impossible<Self::Ok, OtsError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OtsError>;
+    type SerializeMap = Impossible<Self::Ok, OtsError>;
+    type SerializeStruct = Impossible<Self::Ok, OtsError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OtsError>;
+
+    fn serialize_bool(self, v: bool) -> OtsResult<Self::Ok> {
+        Ok(Some(ColumnValue::Boolean(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> OtsResult<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> OtsResult<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> OtsResult<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> OtsResult<Self::Ok> {
+        Ok(Some(ColumnValue::Integer(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> OtsResult<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u16(self, v: u16) -> OtsResult<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u32(self, v: u32) -> OtsResult<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u64(self, v: u64) -> OtsResult<Self::Ok> {
+        match i64::try_from(v) {
+            Ok(n) => self.serialize_i64(n),
+            Err(_) => Err(OtsError::SerdeError(format!("integer {} is out of range of i64", v))),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> OtsResult<Self::Ok> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> OtsResult<Self::Ok> {
+        Ok(Some(ColumnValue::Double(v)))
+    }
+
+    fn serialize_char(self, v: char) -> OtsResult<Self::Ok> {
+        self.serialize_str(&v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> OtsResult<Self::Ok> {
+        Ok(Some(ColumnValue::String(v.to_string())))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> OtsResult<Self::Ok> {
+        Ok(Some(ColumnValue::Blob(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> OtsResult<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> OtsResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> OtsResult<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> OtsResult<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> OtsResult<Self::Ok> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _name: &'static str, value: &T) -> OtsResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> OtsResult<Self::Ok> {
+        unsupported("newtype variant")
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> OtsResult<Self::SerializeSeq> {
+        Ok(BytesSerializer(Vec::with_capacity(len.unwrap_or_default())))
+    }
+
+    fn serialize_tuple(self, len: usize) -> OtsResult<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> OtsResult<Self::SerializeTupleStruct> {
+        unsupported("tuple struct")
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> OtsResult<Self::SerializeTupleVariant> {
+        unsupported("tuple variant")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> OtsResult<Self::SerializeMap> {
+        unsupported("map")
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> OtsResult<Self::SerializeStruct> {
+        unsupported("struct")
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> OtsResult<Self::SerializeStructVariant> {
+        unsupported("struct variant")
+    }
+}
+
+/// `Vec<u8>` 按照序列序列化，这里把每个元素收集为二进制数据
+struct BytesSerializer(Vec<u8>);
+
+impl BytesSerializer {
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> OtsResult<()> {
+        match value.serialize(ValueSerializer)? {
+            Some(ColumnValue::Integer(n)) if (0..=255).contains(&n) => {
+                self.0.push(n as u8);
+                Ok(())
+            }
+            _ => unsupported("sequence of non-u8 values"),
+        }
+    }
+}
+
+impl ser::SerializeSeq for BytesSerializer {
+    type Ok = Option<ColumnValue>;
+    type Error = OtsError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> OtsResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> OtsResult<Self::Ok> {
+        Ok(Some(ColumnValue::Blob(self.0)))
+    }
+}
+
+impl ser::SerializeTuple for BytesSerializer {
+    type Ok = Option<ColumnValue>;
+    type Error = OtsError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> OtsResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> OtsResult<Self::Ok> {
+        Ok(Some(ColumnValue::Blob(self.0)))
+    }
+}
+
+/// 收集结构体的字段。值为 `None` 的字段不收集
+#[derive(Default)]
+struct FieldsSerializer {
+    fields: Vec<(String, ColumnValue)>,
+}
+
+impl ser::SerializeStruct for FieldsSerializer {
+    type Ok = Vec<(String, ColumnValue)>;
+    type Error = OtsError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> OtsResult<()> {
+        if let Some(v) = value.serialize(ValueSerializer)? {
+            self.fields.push((key.to_string(), v));
+        }
+
+        Ok(())
+    }
+
+    fn end(self) -> OtsResult<Self::Ok> {
+        Ok(self.fields)
+    }
+}
+
+/// 只接受结构体
+struct StructSerializer;
+
+fn expect_struct<T>() -> OtsResult<T> {
+    Err(OtsError::SerdeError("only struct can be serialized into row".to_string()))
+}
+
+impl ser::Serializer for StructSerializer {
+    type Ok = Vec<(String, ColumnValue)>;
+    type Error = OtsError;
+    type SerializeSeq = Impossible<Self::Ok, OtsError>;
+    type SerializeTuple = Impossible<Self::Ok, OtsError>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OtsError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OtsError>;
+    type SerializeMap = Impossible<Self::Ok, OtsError>;
+    type SerializeStruct = FieldsSerializer;
+    type SerializeStructVariant = Impossible<Self::Ok, OtsError>;
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> OtsResult<Self::SerializeStruct> {
+        Ok(FieldsSerializer::default())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _name: &'static str, value: &T) -> OtsResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_i8(self, _v: i8) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_i16(self, _v: i16) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_i32(self, _v: i32) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_i64(self, _v: i64) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_u8(self, _v: u8) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_u16(self, _v: u16) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_u32(self, _v: u32) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_u64(self, _v: u64) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_f32(self, _v: f32) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_f64(self, _v: f64) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_char(self, _v: char) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_str(self, _v: &str) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_none(self) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_unit(self) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> OtsResult<Self::Ok> {
+        expect_struct()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> OtsResult<Self::SerializeSeq> {
+        expect_struct()
+    }
+
+    fn serialize_tuple(self, _len: usize) -> OtsResult<Self::SerializeTuple> {
+        expect_struct()
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> OtsResult<Self::SerializeTupleStruct> {
+        expect_struct()
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> OtsResult<Self::SerializeTupleVariant> {
+        expect_struct()
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> OtsResult<Self::SerializeMap> {
+        expect_struct()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> OtsResult<Self::SerializeStructVariant> {
+        expect_struct()
+    }
+}
+
+impl Row {
+    /// 把结构体转换为行。需要开启 `serde` 特性。
+    ///
+    /// `primary_keys` 中的字段按照给出的顺序作为主键列，其余字段作为数据列。
+    /// 主键列只能是整数、字符串或者二进制数据（`Vec<u8>`）类型，并且不能为 `None`。
+    /// `Vec<u8>` 这样的字节序列和通过 `serialize_bytes` 序列化的值（例如使用 `serde_bytes`）都会转换为二进制数据，其他类型的序列返回错误。
+    /// 数据列中值为 `None` 的字段会被跳过，不会写入空值。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// #[derive(serde::Serialize)]
+    /// struct User {
+    ///     user_id: String,
+    ///     age: i64,
+    ///     nick_name: Option<String>,
+    /// }
+    ///
+    /// let row = Row::from_serialize(&user, &["user_id"])?;
+    /// client.put_row(PutRowRequest::new("users").row(row)).send().await?;
+    /// ```
+    pub fn from_serialize<T: Serialize>(value: &T, primary_keys: &[&str]) -> OtsResult<Row> {
+        let mut fields = value.serialize(StructSerializer)?;
+        let mut row = Row::new();
+
+        for name in primary_keys {
+            let idx = fields
+                .iter()
+                .position(|(n, _)| n == name)
+                .ok_or_else(|| OtsError::SerdeError(format!("primary key field `{}` is missing", name)))?;
+
+            let value = match fields.remove(idx).1 {
+                ColumnValue::Integer(n) => PrimaryKeyValue::Integer(n),
+                ColumnValue::String(s) => PrimaryKeyValue::String(s),
+                ColumnValue::Blob(b) => PrimaryKeyValue::Binary(b),
+                v => return Err(OtsError::SerdeError(format!("invalid primary key value of field `{}`: {:?}", name, v))),
+            };
+
+            row.primary_key.columns.push(PrimaryKeyColumn { name: name.to_string(), value });
+        }
+
+        row.columns = fields
+            .into_iter()
+            .map(|(name, value)| Column {
+                name,
+                value,
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(row)
+    }
+}
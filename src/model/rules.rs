@@ -1,3 +1,10 @@
+use crate::{
+    error::OtsError,
+    model::{PrimaryKey, PrimaryKeyValue},
+    protos::{PrimaryKeyOption, PrimaryKeyType, TableMeta},
+    OtsOp, OtsResult,
+};
+
 /// 一个宽表至少有 1 个主键列
 pub const MIN_PRIMARY_KEY_COUNT: usize = 1;
 
@@ -7,6 +14,35 @@ pub const MAX_PRIMARY_KEY_COUNT: usize = 4;
 /// 读请求一行数据中获取的列的最大个数
 pub const MAX_COLUMNS_TO_GET: usize = 128;
 
+/// 过滤器（包括条件更新中的列条件）允许的最大嵌套深度。单条件过滤器的深度为 `1`
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/compositecolumnvaluefilter>
+pub const MAX_FILTER_DEPTH: usize = 10;
+
+/// 一个过滤器中最多包含的过滤器个数（包括组合过滤器自身和所有的子过滤器）
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/compositecolumnvaluefilter>
+pub const MAX_FILTER_COUNT: usize = 32;
+
+/// 写入数据的请求（`PutRow`、`UpdateRow`、`DeleteRow`、`BatchWriteRow`、`BulkImport`、`PutTimeseriesData`）
+/// 编码之后的请求体最大字节数：4 MB
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/product-overview/general-limits>
+pub const MAX_WRITE_REQUEST_SIZE: usize = 4 * 1024 * 1024;
+
+/// 其他请求编码之后的请求体最大字节数：5 MB
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/product-overview/general-limits>
+pub const MAX_REQUEST_SIZE: usize = 5 * 1024 * 1024;
+
+/// 某个操作的请求体最大字节数
+pub(crate) fn max_request_size(operation: OtsOp) -> usize {
+    match operation {
+        OtsOp::PutRow | OtsOp::UpdateRow | OtsOp::DeleteRow | OtsOp::BatchWriteRow | OtsOp::BulkImport | OtsOp::PutTimeseriesData => MAX_WRITE_REQUEST_SIZE,
+        _ => MAX_REQUEST_SIZE,
+    }
+}
+
 /// 约束条件：
 ///
 /// - 由英文字母、数字或下划线（_）组成，大小写敏感，长度限制为1~255字节。
@@ -28,6 +64,29 @@ pub fn validate_table_name(table_name: &str) -> bool {
     table_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
+/// 验证读取数据时设置的时间范围，单位为毫秒：
+///
+/// - `specific_ms` 和 `start_ms`、`end_ms` 不能同时设置
+/// - 同时设置了 `start_ms` 和 `end_ms` 时，`start_ms` 必须小于 `end_ms`
+pub(crate) fn validate_time_range(start_ms: Option<i64>, end_ms: Option<i64>, specific_ms: Option<i64>) -> OtsResult<()> {
+    if specific_ms.is_some() && (start_ms.is_some() || end_ms.is_some()) {
+        return Err(OtsError::ValidationFailed(
+            "can not set `specific_time_ms` and time range both at the same time".to_string(),
+        ));
+    }
+
+    if let (Some(start), Some(end)) = (start_ms, end_ms) {
+        if start >= end {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid time range: start time {} must be less than end time {}",
+                start, end
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// 和表名的约束条件一样
 pub fn validate_column_name(col_name: &str) -> bool {
     validate_table_name(col_name)
@@ -36,3 +95,100 @@ pub fn validate_column_name(col_name: &str) -> bool {
 pub fn validate_index_name(idx_name: &str) -> bool {
     validate_table_name(idx_name)
 }
+
+/// 按照表结构中声明的顺序重新排列主键列。缺少主键列，或者包含表结构中不存在的主键列时返回错误
+pub(crate) fn reorder_primary_key(table_meta: &TableMeta, primary_key: &mut PrimaryKey) -> OtsResult<()> {
+    if let Some(col) = primary_key
+        .columns
+        .iter()
+        .find(|col| !table_meta.primary_key.iter().any(|pk| pk.name == col.name))
+    {
+        return Err(OtsError::ValidationFailed(format!(
+            "invalid primary key: column {} is not a primary key column of table {}",
+            col.name, table_meta.table_name
+        )));
+    }
+
+    let mut columns = Vec::with_capacity(table_meta.primary_key.len());
+
+    for schema in &table_meta.primary_key {
+        match primary_key.columns.iter().position(|col| col.name == schema.name) {
+            Some(i) => columns.push(primary_key.columns.swap_remove(i)),
+            None => {
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid primary key: missing primary key column {} of table {}",
+                    schema.name, table_meta.table_name
+                )))
+            }
+        }
+    }
+
+    if !primary_key.columns.is_empty() {
+        return Err(OtsError::ValidationFailed(format!(
+            "invalid primary key: duplicated primary key column {} of table {}",
+            primary_key.columns[0].name, table_meta.table_name
+        )));
+    }
+
+    primary_key.columns = columns;
+
+    Ok(())
+}
+
+/// 按照表结构校验主键列的个数、名称、顺序和值的类型。
+///
+/// `InfMin` 和 `InfMax` 可以用在任意类型的主键列上（用于范围读取），自增值只能用在声明为自增列的主键列上
+pub(crate) fn validate_primary_key(table_meta: &TableMeta, primary_key: &PrimaryKey) -> OtsResult<()> {
+    if primary_key.columns.len() != table_meta.primary_key.len() {
+        return Err(OtsError::ValidationFailed(format!(
+            "invalid primary key: table {} has {} primary key columns, got {}",
+            table_meta.table_name,
+            table_meta.primary_key.len(),
+            primary_key.columns.len()
+        )));
+    }
+
+    for (i, (col, schema)) in primary_key.columns.iter().zip(&table_meta.primary_key).enumerate() {
+        if col.name != schema.name {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid primary key: expect column {} at position {} of table {}, got {}",
+                schema.name, i, table_meta.table_name, col.name
+            )));
+        }
+
+        let pk_type = PrimaryKeyType::try_from(schema.r#type).ok();
+
+        let matched = match &col.value {
+            PrimaryKeyValue::InfMin | PrimaryKeyValue::InfMax => true,
+            PrimaryKeyValue::AutoIncrement => schema.option == Some(PrimaryKeyOption::AutoIncrement as i32),
+            PrimaryKeyValue::Integer(_) => pk_type == Some(PrimaryKeyType::Integer),
+            PrimaryKeyValue::String(_) => pk_type == Some(PrimaryKeyType::String),
+            PrimaryKeyValue::Binary(_) => pk_type == Some(PrimaryKeyType::Binary),
+        };
+
+        if !matched {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid primary key: column {} of table {} is {}, got value {:?}",
+                col.name,
+                table_meta.table_name,
+                pk_type.map(|t| t.as_str_name()).unwrap_or("UNKNOWN"),
+                col.value
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// 找出 `column_names` 中既不是主键列、也不是预定义列的列名。返回的列名按照字典序排列
+pub(crate) fn find_undefined_columns<'a>(table_meta: &TableMeta, column_names: impl IntoIterator<Item = &'a String>) -> Vec<&'a str> {
+    let mut names = column_names
+        .into_iter()
+        .map(|s| s.as_str())
+        .filter(|name| !table_meta.primary_key.iter().any(|pk| pk.name == *name) && !table_meta.defined_column.iter().any(|col| col.name == *name))
+        .collect::<Vec<_>>();
+
+    names.sort();
+
+    names
+}
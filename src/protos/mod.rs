@@ -20,16 +20,77 @@ pub mod timeseries {
     include!("./timeseries.rs");
 }
 
+pub mod tunnel {
+    // Mapping to Java SDK: tunnel.proto
+    include!("./tunnel.rs");
+}
+
 pub mod plain_buffer;
 pub mod simple_row_matrix;
 
+/// 一次操作消耗的服务能力单元。
+///
+/// - `capacity_unit` 是读写消耗的服务能力单元（CU），所有表都会返回。对于预留模式（高性能实例）的表，
+///   消耗优先从预留的读写吞吐量中扣除；对于按量模式（容量型实例）的表，按照这里的值计费。
+///   多元索引查询的响应中，按量部分和预留部分会分别通过 `consumed` 和 `reserved_consumed` 返回。
+/// - `capacity_data_size` 是读写的数据量（字节），只有服务端返回的时候才有值，是否返回和实例的计费模式有关。
+impl ConsumedCapacity {
+    /// 消耗的读服务能力单元
+    pub fn read_cu(&self) -> Option<i32> {
+        self.capacity_unit.read
+    }
+
+    /// 消耗的写服务能力单元
+    pub fn write_cu(&self) -> Option<i32> {
+        self.capacity_unit.write
+    }
+
+    /// 读取的数据量，单位为字节
+    pub fn read_size(&self) -> Option<i64> {
+        self.capacity_data_size.as_ref().and_then(|s| s.read_size)
+    }
+
+    /// 写入的数据量，单位为字节
+    pub fn write_size(&self) -> Option<i64> {
+        self.capacity_data_size.as_ref().and_then(|s| s.write_size)
+    }
+}
+
 #[cfg(test)]
 mod test_protos {
     use flatbuffers::FlatBufferBuilder;
 
     use crate::{test_util::setup, util::debug_bytes};
 
-    use super::fbs::timeseries::{DataType, FieldValuesBuilder, FlatBufferRowGroupBuilder, FlatBufferRowInGroupBuilder, FlatBufferRowsBuilder};
+    use super::{
+        fbs::timeseries::{DataType, FieldValuesBuilder, FlatBufferRowGroupBuilder, FlatBufferRowInGroupBuilder, FlatBufferRowsBuilder},
+        CapacityDataSize, CapacityUnit, ConsumedCapacity,
+    };
+
+    #[test]
+    fn test_consumed_capacity() {
+        let consumed = ConsumedCapacity {
+            capacity_unit: CapacityUnit { read: Some(1), write: None },
+            capacity_data_size: None,
+        };
+
+        assert_eq!(Some(1), consumed.read_cu());
+        assert_eq!(None, consumed.write_cu());
+        assert_eq!(None, consumed.read_size());
+        assert_eq!(None, consumed.write_size());
+
+        let consumed = ConsumedCapacity {
+            capacity_unit: CapacityUnit { read: Some(0), write: Some(2) },
+            capacity_data_size: Some(CapacityDataSize {
+                read_size: None,
+                write_size: Some(4096),
+            }),
+        };
+
+        assert_eq!(Some(2), consumed.write_cu());
+        assert_eq!(None, consumed.read_size());
+        assert_eq!(Some(4096), consumed.write_size());
+    }
 
     #[test]
     fn test_flat_buffer() {
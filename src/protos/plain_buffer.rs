@@ -40,6 +40,12 @@
 //! - InfMin, InfMax: 4 字节前缀 + 1 字节类型 = 5 = 0x05
 //!
 
+use std::io::{Cursor, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use crate::{error::OtsError, OtsResult};
+
 pub const LITTLE_ENDIAN_32_SIZE: u32 = 4;
 pub const LITTLE_ENDIAN_64_SIZE: u32 = 8;
 
@@ -96,3 +102,22 @@ pub const VT_BLOB: u8 = 0x07;
 pub const VT_INF_MIN: u8 = 0x09;
 pub const VT_INF_MAX: u8 = 0x0A;
 pub const VT_AUTO_INCREMENT: u8 = 0x0B;
+
+/// 读取 4 字节的长度以及随后对应长度的字节。
+/// 长度超过剩余的字节数时直接返回错误，避免按照损坏数据中的长度分配内存
+pub(crate) fn read_len_prefixed_bytes(cursor: &mut Cursor<Vec<u8>>) -> OtsResult<Vec<u8>> {
+    let len = cursor.read_u32::<LittleEndian>()? as u64;
+    let remaining = (cursor.get_ref().len() as u64).saturating_sub(cursor.position());
+
+    if len > remaining {
+        return Err(OtsError::PlainBufferError(format!(
+            "invalid length: {}, only {} bytes remaining",
+            len, remaining
+        )));
+    }
+
+    let mut buf = vec![0u8; len as usize];
+    cursor.read_exact(&mut buf)?;
+
+    Ok(buf)
+}
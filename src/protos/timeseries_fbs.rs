@@ -1041,7 +1041,7 @@ pub mod fbs {
         /// catch every error, or be maximally performant. For the
         /// previous, unchecked, behavior use
         /// `root_as_flat_buffer_rows_unchecked`.
-        pub fn root_as_flat_buffer_rows(buf: &[u8]) -> Result<FlatBufferRows, flatbuffers::InvalidFlatbuffer> {
+        pub fn root_as_flat_buffer_rows(buf: &[u8]) -> Result<FlatBufferRows<'_>, flatbuffers::InvalidFlatbuffer> {
             flatbuffers::root::<FlatBufferRows>(buf)
         }
         #[inline]
@@ -1051,7 +1051,7 @@ pub mod fbs {
         /// catch every error, or be maximally performant. For the
         /// previous, unchecked, behavior use
         /// `size_prefixed_root_as_flat_buffer_rows_unchecked`.
-        pub fn size_prefixed_root_as_flat_buffer_rows(buf: &[u8]) -> Result<FlatBufferRows, flatbuffers::InvalidFlatbuffer> {
+        pub fn size_prefixed_root_as_flat_buffer_rows(buf: &[u8]) -> Result<FlatBufferRows<'_>, flatbuffers::InvalidFlatbuffer> {
             flatbuffers::size_prefixed_root::<FlatBufferRows>(buf)
         }
         #[inline]
@@ -1084,14 +1084,14 @@ pub mod fbs {
         /// Assumes, without verification, that a buffer of bytes contains a FlatBufferRows and returns it.
         /// # Safety
         /// Callers must trust the given bytes do indeed contain a valid `FlatBufferRows`.
-        pub unsafe fn root_as_flat_buffer_rows_unchecked(buf: &[u8]) -> FlatBufferRows {
+        pub unsafe fn root_as_flat_buffer_rows_unchecked(buf: &[u8]) -> FlatBufferRows<'_> {
             unsafe { flatbuffers::root_unchecked::<FlatBufferRows>(buf) }
         }
         #[inline]
         /// Assumes, without verification, that a buffer of bytes contains a size prefixed FlatBufferRows and returns it.
         /// # Safety
         /// Callers must trust the given bytes do indeed contain a valid size prefixed `FlatBufferRows`.
-        pub unsafe fn size_prefixed_root_as_flat_buffer_rows_unchecked(buf: &[u8]) -> FlatBufferRows {
+        pub unsafe fn size_prefixed_root_as_flat_buffer_rows_unchecked(buf: &[u8]) -> FlatBufferRows<'_> {
             unsafe { flatbuffers::size_prefixed_root_unchecked::<FlatBufferRows>(buf) }
         }
         #[inline]
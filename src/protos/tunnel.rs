@@ -0,0 +1,335 @@
+// This file is @generated by prost-build.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StreamTunnelConfig {
+    #[prost(enumeration = "StartOffsetFlag", optional, tag = "1")]
+    pub flag: ::core::option::Option<i32>,
+    #[prost(uint64, optional, tag = "2")]
+    pub start_offset: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub end_offset: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Tunnel {
+    #[prost(string, required, tag = "1")]
+    pub table_name: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub tunnel_name: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "3")]
+    pub tunnel_type: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub stream_tunnel_config: ::core::option::Option<StreamTunnelConfig>,
+    #[prost(bool, optional, tag = "5")]
+    pub need_all_timeseries_index: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateTunnelRequest {
+    #[prost(message, required, tag = "1")]
+    pub tunnel: Tunnel,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateTunnelResponse {
+    #[prost(string, required, tag = "1")]
+    pub tunnel_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteTunnelRequest {
+    #[prost(string, required, tag = "1")]
+    pub table_name: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub tunnel_name: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub tunnel_id: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteTunnelResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTunnelRequest {
+    #[prost(string, required, tag = "1")]
+    pub table_name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TunnelInfo {
+    #[prost(string, required, tag = "1")]
+    pub tunnel_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub tunnel_type: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "3")]
+    pub table_name: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "4")]
+    pub instance_name: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "5")]
+    pub stream_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "6")]
+    pub stage: ::prost::alloc::string::String,
+    #[prost(bool, optional, tag = "7")]
+    pub expired: ::core::option::Option<bool>,
+    #[prost(string, optional, tag = "8")]
+    pub tunnel_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, optional, tag = "9")]
+    pub public: ::core::option::Option<bool>,
+    #[prost(message, optional, tag = "10")]
+    pub stream_tunnel_config: ::core::option::Option<StreamTunnelConfig>,
+    #[prost(int64, optional, tag = "11")]
+    pub create_time: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTunnelResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub tunnels: ::prost::alloc::vec::Vec<TunnelInfo>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DescribeTunnelRequest {
+    #[prost(string, required, tag = "1")]
+    pub table_name: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub tunnel_name: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub tunnel_id: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChannelInfo {
+    #[prost(string, required, tag = "1")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub channel_type: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub channel_status: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub client_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, optional, tag = "5")]
+    pub channel_rpo: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DescribeTunnelResponse {
+    #[prost(message, required, tag = "1")]
+    pub tunnel: TunnelInfo,
+    #[prost(message, repeated, tag = "2")]
+    pub channels: ::prost::alloc::vec::Vec<ChannelInfo>,
+    #[prost(int64, optional, tag = "3")]
+    pub tunnel_rpo: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientConfig {
+    #[prost(int64, optional, tag = "1")]
+    pub timeout: ::core::option::Option<i64>,
+    #[prost(string, optional, tag = "2")]
+    pub client_tag: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConnectRequest {
+    #[prost(string, required, tag = "1")]
+    pub tunnel_id: ::prost::alloc::string::String,
+    #[prost(message, required, tag = "2")]
+    pub client_config: ClientConfig,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConnectResponse {
+    #[prost(string, required, tag = "1")]
+    pub client_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Channel {
+    #[prost(string, required, tag = "1")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(int64, required, tag = "2")]
+    pub version: i64,
+    #[prost(enumeration = "ChannelStatus", required, tag = "3")]
+    pub status: i32,
+    #[prost(bytes = "vec", optional, tag = "4")]
+    pub detail: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HeartbeatRequest {
+    #[prost(string, required, tag = "1")]
+    pub tunnel_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub client_id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub channels: ::prost::alloc::vec::Vec<Channel>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HeartbeatResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub channels: ::prost::alloc::vec::Vec<Channel>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShutdownRequest {
+    #[prost(string, required, tag = "1")]
+    pub tunnel_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub client_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ShutdownResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCheckpointRequest {
+    #[prost(string, required, tag = "1")]
+    pub tunnel_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub client_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "3")]
+    pub channel_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCheckpointResponse {
+    #[prost(string, required, tag = "1")]
+    pub checkpoint: ::prost::alloc::string::String,
+    #[prost(int64, required, tag = "2")]
+    pub sequence_number: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Record {
+    #[prost(enumeration = "ActionType", required, tag = "1")]
+    pub action_type: i32,
+    #[prost(bytes = "vec", required, tag = "2")]
+    pub record: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadRecordsRequest {
+    #[prost(string, required, tag = "1")]
+    pub tunnel_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub client_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "3")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "4")]
+    pub token: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadRecordsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub records: ::prost::alloc::vec::Vec<Record>,
+    #[prost(string, required, tag = "2")]
+    pub next_token: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckpointRequest {
+    #[prost(string, required, tag = "1")]
+    pub tunnel_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "2")]
+    pub client_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "3")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag = "4")]
+    pub checkpoint: ::prost::alloc::string::String,
+    #[prost(int64, required, tag = "5")]
+    pub sequence_number: i64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CheckpointResponse {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TunnelType {
+    BaseData = 1,
+    Stream = 2,
+    BaseAndStream = 3,
+}
+impl TunnelType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::BaseData => "BaseData",
+            Self::Stream => "Stream",
+            Self::BaseAndStream => "BaseAndStream",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BaseData" => Some(Self::BaseData),
+            "Stream" => Some(Self::Stream),
+            "BaseAndStream" => Some(Self::BaseAndStream),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum StartOffsetFlag {
+    Latest = 0,
+    Earliest = 1,
+}
+impl StartOffsetFlag {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Latest => "LATEST",
+            Self::Earliest => "EARLIEST",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "LATEST" => Some(Self::Latest),
+            "EARLIEST" => Some(Self::Earliest),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChannelStatus {
+    Open = 1,
+    Closing = 2,
+    Close = 3,
+    Terminated = 4,
+}
+impl ChannelStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Open => "OPEN",
+            Self::Closing => "CLOSING",
+            Self::Close => "CLOSE",
+            Self::Terminated => "TERMINATED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OPEN" => Some(Self::Open),
+            "CLOSING" => Some(Self::Closing),
+            "CLOSE" => Some(Self::Close),
+            "TERMINATED" => Some(Self::Terminated),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ActionType {
+    PutRow = 1,
+    UpdateRow = 2,
+    DeleteRow = 3,
+}
+impl ActionType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::PutRow => "PUT_ROW",
+            Self::UpdateRow => "UPDATE_ROW",
+            Self::DeleteRow => "DELETE_ROW",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PUT_ROW" => Some(Self::PutRow),
+            "UPDATE_ROW" => Some(Self::UpdateRow),
+            "DELETE_ROW" => Some(Self::DeleteRow),
+            _ => None,
+        }
+    }
+}
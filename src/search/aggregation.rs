@@ -735,6 +735,20 @@ impl From<Aggregation> for crate::protos::search::Aggregation {
 }
 
 impl Aggregation {
+    /// 聚合名称
+    pub fn name(&self) -> &str {
+        match self {
+            Aggregation::Min(a) => &a.name,
+            Aggregation::Max(a) => &a.name,
+            Aggregation::Avg(a) => &a.name,
+            Aggregation::Count(a) => &a.name,
+            Aggregation::DistinctCount(a) => &a.name,
+            Aggregation::Sum(a) => &a.name,
+            Aggregation::TopRows(a) => &a.name,
+            Aggregation::Percentiles(a) => &a.name,
+        }
+    }
+
     pub(crate) fn validate(&self) -> OtsResult<()> {
         match self {
             Aggregation::Min(a) => a.validate(),
@@ -834,6 +848,25 @@ pub enum AggregationResult {
     Percentiles(Vec<PercentilesAggregationItem>),
 }
 
+impl AggregationResult {
+    /// 返回数值类型的聚合结果。`Count` 和 `DistinctCount` 的行数转换为 `f64` 返回，`TopRows` 和 `Percentiles` 返回 `None`
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Min(v) | Self::Max(v) | Self::Avg(v) | Self::Sum(v) => Some(*v),
+            Self::Count(n) | Self::DistinctCount(n) => Some(*n as f64),
+            Self::TopRows(_) | Self::Percentiles(_) => None,
+        }
+    }
+
+    /// 返回 `TopRows` 聚合的行。不是 `TopRows` 聚合时返回 `None`
+    pub fn as_rows(&self) -> Option<&Vec<Row>> {
+        match self {
+            Self::TopRows(rows) => Some(rows),
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<crate::protos::search::AggregationResult> for AggregationResult {
     type Error = OtsError;
 
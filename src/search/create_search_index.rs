@@ -1,6 +1,435 @@
 use prost::Message;
 
-use crate::{add_per_request_options, protos::search::CreateSearchIndexRequest, OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult};
+use super::Sort;
+use crate::{
+    add_per_request_options,
+    error::OtsError,
+    protos::search::{
+        CreateSearchIndexRequest, FieldSchema, FieldType, FuzzyAnalyzerParameter, IndexSchema, IndexSetting, SingleWordAnalyzerParameter,
+        SplitAnalyzerParameter, VectorDataType, VectorMetricType, VectorOptions,
+    },
+    OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
+};
+
+/// `Text` 类型字段的分词器及其参数
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/tokenization>
+#[derive(Debug, Clone)]
+pub enum Analyzer {
+    /// 单字分词。`case_sensitive` 设置是否大小写敏感，`delimit_word` 设置是否分割英文和数字
+    SingleWord { case_sensitive: Option<bool>, delimit_word: Option<bool> },
+
+    /// 分隔符分词。`delimiter` 默认为空白字符
+    Split { delimiter: Option<String>, case_sensitive: Option<bool> },
+
+    /// 最少词数量语义分词
+    MinWord,
+
+    /// 最多词数量语义分词
+    MaxWord,
+
+    /// 模糊分词。`min_chars` 和 `max_chars` 设置最短和最长的字符切分单元长度
+    Fuzzy {
+        min_chars: Option<u32>,
+        max_chars: Option<u32>,
+        case_sensitive: Option<bool>,
+    },
+}
+
+impl Analyzer {
+    /// 服务端使用的分词器名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SingleWord { .. } => "single_word",
+            Self::Split { .. } => "split",
+            Self::MinWord => "min_word",
+            Self::MaxWord => "max_word",
+            Self::Fuzzy { .. } => "fuzzy",
+        }
+    }
+
+    /// 编码之后的分词器参数。没有参数的分词器返回 `None`
+    fn encode_parameter(&self) -> Option<Vec<u8>> {
+        match self {
+            Self::SingleWord { case_sensitive, delimit_word } => Some(
+                SingleWordAnalyzerParameter {
+                    case_sensitive: *case_sensitive,
+                    delimit_word: *delimit_word,
+                }
+                .encode_to_vec(),
+            ),
+
+            Self::Split { delimiter, case_sensitive } => Some(
+                SplitAnalyzerParameter {
+                    delimiter: delimiter.clone(),
+                    case_sensitive: *case_sensitive,
+                }
+                .encode_to_vec(),
+            ),
+
+            Self::MinWord | Self::MaxWord => None,
+
+            Self::Fuzzy {
+                min_chars,
+                max_chars,
+                case_sensitive,
+            } => Some(
+                FuzzyAnalyzerParameter {
+                    min_chars: min_chars.map(|n| n as i32),
+                    max_chars: max_chars.map(|n| n as i32),
+                    case_sensitive: *case_sensitive,
+                }
+                .encode_to_vec(),
+            ),
+        }
+    }
+}
+
+/// 构建多元索引中一个字段的配置
+#[derive(Debug, Clone, Default)]
+pub struct FieldSchemaBuilder {
+    schema: FieldSchema,
+}
+
+impl FieldSchemaBuilder {
+    pub fn new(field_name: &str, field_type: FieldType) -> Self {
+        Self {
+            schema: FieldSchema {
+                field_name: Some(field_name.to_string()),
+                field_type: Some(field_type as i32),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// `Text` 类型的字段
+    pub fn text(field_name: &str) -> Self {
+        Self::new(field_name, FieldType::Text)
+    }
+
+    /// `Keyword` 类型的字段
+    pub fn keyword(field_name: &str) -> Self {
+        Self::new(field_name, FieldType::Keyword)
+    }
+
+    /// `Long` 类型的字段
+    pub fn long(field_name: &str) -> Self {
+        Self::new(field_name, FieldType::Long)
+    }
+
+    /// `Double` 类型的字段
+    pub fn double(field_name: &str) -> Self {
+        Self::new(field_name, FieldType::Double)
+    }
+
+    /// `Boolean` 类型的字段
+    pub fn boolean(field_name: &str) -> Self {
+        Self::new(field_name, FieldType::Boolean)
+    }
+
+    /// `Date` 类型的字段。通常还需要使用 `date_format` 设置日期格式
+    pub fn date(field_name: &str) -> Self {
+        Self::new(field_name, FieldType::Date)
+    }
+
+    /// `GeoPoint` 类型的字段
+    pub fn geo_point(field_name: &str) -> Self {
+        Self::new(field_name, FieldType::GeoPoint)
+    }
+
+    /// `Vector` 类型的字段。向量的数据类型为 `float32`，`dimension` 是向量的维度，`metric_type` 是计算向量距离的方式。
+    ///
+    /// 使用 [`KnnVectorQuery::validate_with_schema`](`crate::search::KnnVectorQuery::validate_with_schema`)
+    /// 可以检查查询向量的长度和这里设置的维度是否一致
+    pub fn vector(field_name: &str, dimension: u32, metric_type: VectorMetricType) -> Self {
+        let mut builder = Self::new(field_name, FieldType::Vector);
+        builder.schema.vector_options = Some(VectorOptions {
+            data_type: Some(VectorDataType::VdFloat32 as i32),
+            dimension: Some(dimension as i32),
+            metric_type: Some(metric_type as i32),
+        });
+
+        builder
+    }
+
+    /// 嵌套类型的字段。`sub_fields` 是嵌套对象中各个子字段的配置
+    pub fn nested(field_name: &str, sub_fields: impl IntoIterator<Item = FieldSchema>) -> Self {
+        Self::new(field_name, FieldType::Nested).sub_fields(sub_fields)
+    }
+
+    /// 虚拟列。虚拟列的值来自数据表中的 `source_field_name` 列，按照 `field_type` 重新建立索引，
+    /// 例如把字符串类型的列作为 `Date` 类型的虚拟列，这时候通常还需要设置 `date_format`
+    pub fn virtual_field(field_name: &str, field_type: FieldType, source_field_name: &str) -> Self {
+        let mut builder = Self::new(field_name, field_type);
+        builder.schema.is_virtual_field = Some(true);
+        builder.schema.source_field_names = vec![source_field_name.to_string()];
+
+        builder
+    }
+
+    /// 设置是否开启索引
+    pub fn index(mut self, index: bool) -> Self {
+        self.schema.index = Some(index);
+
+        self
+    }
+
+    /// 设置是否开启排序与统计聚合功能
+    pub fn sort_and_agg(mut self, sort_and_agg: bool) -> Self {
+        self.schema.sort_and_agg = Some(sort_and_agg);
+
+        self
+    }
+
+    /// 设置是否在多元索引中附加存储该字段的值
+    pub fn store(mut self, store: bool) -> Self {
+        self.schema.store = Some(store);
+
+        self
+    }
+
+    /// 设置是否为数组
+    pub fn is_array(mut self, is_array: bool) -> Self {
+        self.schema.is_array = Some(is_array);
+
+        self
+    }
+
+    /// 设置分词器类型，仅适用于 `Text` 类型的字段，例如 `single_word`、`split`、`min_word`、`max_word`、`fuzzy`
+    pub fn analyzer(mut self, analyzer: &str) -> Self {
+        self.schema.analyzer = Some(analyzer.to_string());
+
+        self
+    }
+
+    /// 设置分词器及其参数，仅适用于 `Text` 类型的字段
+    pub fn text_analyzer(mut self, analyzer: Analyzer) -> Self {
+        self.schema.analyzer = Some(analyzer.name().to_string());
+        self.schema.analyzer_parameter = analyzer.encode_parameter();
+
+        self
+    }
+
+    /// 添加一个日期格式，仅适用于 `Date` 类型的字段
+    pub fn date_format(mut self, date_format: &str) -> Self {
+        self.schema.date_formats.push(date_format.to_string());
+
+        self
+    }
+
+    /// 设置是否开启查询高亮，仅适用于 `Text` 类型的字段
+    pub fn enable_highlighting(mut self, enable_highlighting: bool) -> Self {
+        self.schema.enable_highlighting = Some(enable_highlighting);
+
+        self
+    }
+
+    /// 添加一个嵌套类型的子字段
+    pub fn sub_field(mut self, sub_field: FieldSchema) -> Self {
+        self.schema.field_schemas.push(sub_field);
+
+        self
+    }
+
+    /// 设置嵌套类型的子字段
+    pub fn sub_fields(mut self, sub_fields: impl IntoIterator<Item = FieldSchema>) -> Self {
+        self.schema.field_schemas = sub_fields.into_iter().collect();
+
+        self
+    }
+
+    pub fn build(self) -> FieldSchema {
+        self.schema
+    }
+}
+
+/// 构建多元索引的结构
+#[derive(Debug, Clone, Default)]
+pub struct IndexSchemaBuilder {
+    field_schemas: Vec<FieldSchema>,
+    routing_fields: Vec<String>,
+    index_sort: Option<Sort>,
+}
+
+impl IndexSchemaBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一个字段
+    pub fn field(mut self, field: FieldSchema) -> Self {
+        self.field_schemas.push(field);
+
+        self
+    }
+
+    /// 设置字段
+    pub fn fields(mut self, fields: impl IntoIterator<Item = FieldSchema>) -> Self {
+        self.field_schemas = fields.into_iter().collect();
+
+        self
+    }
+
+    /// 添加一个路由字段。路由字段必须是数据表的主键列
+    pub fn routing_field(mut self, field_name: impl Into<String>) -> Self {
+        self.routing_fields.push(field_name.into());
+
+        self
+    }
+
+    /// 设置索引预排序方式。默认按照数据表的主键排序
+    pub fn index_sort(mut self, sort: Sort) -> Self {
+        self.index_sort = Some(sort);
+
+        self
+    }
+
+    /// 校验全部字段的配置，详见 [`IndexSchemaBuilder::build`]
+    pub fn validate(&self) -> OtsResult<()> {
+        if self.field_schemas.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid index schema: field schemas can not be empty".to_string()));
+        }
+
+        validate_field_schemas(&self.field_schemas)?;
+
+        if let Some(sort) = &self.index_sort {
+            sort.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// 构建多元索引结构。不会校验字段的配置，创建多元索引的时候会校验，也可以先调用 [`IndexSchemaBuilder::validate`] 校验
+    pub fn build(self) -> IndexSchema {
+        let Self {
+            field_schemas,
+            routing_fields,
+            index_sort,
+        } = self;
+
+        IndexSchema {
+            field_schemas,
+            index_setting: if routing_fields.is_empty() {
+                None
+            } else {
+                Some(IndexSetting {
+                    routing_fields,
+                    ..Default::default()
+                })
+            },
+            index_sort: index_sort.map(crate::protos::search::Sort::from),
+        }
+    }
+}
+
+/// 校验同一层级的字段配置。虚拟列必须引用同一层级中存在的非虚拟列，嵌套类型的字段必须有子字段，
+/// 分词器、日期格式和向量参数只能用于对应类型的字段
+pub(crate) fn validate_field_schemas(schemas: &[FieldSchema]) -> OtsResult<()> {
+    for schema in schemas {
+        let name = schema.field_name.as_deref().unwrap_or_default();
+
+        if name.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid field schema: field name can not be empty".to_string()));
+        }
+
+        let field_type = FieldType::try_from(schema.field_type.unwrap_or_default())
+            .map_err(|_| OtsError::ValidationFailed(format!("invalid field {}: unknown field type", name)))?;
+
+        if field_type == FieldType::Text {
+            if schema.sort_and_agg.unwrap_or_default() {
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid text field {}: sort and aggregation is not supported",
+                    name
+                )));
+            }
+        } else if schema.analyzer.is_some() || schema.analyzer_parameter.is_some() || schema.enable_highlighting.unwrap_or_default() {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid field {}: analyzer and highlighting are only allowed for text field",
+                name
+            )));
+        }
+
+        if field_type != FieldType::Date && !schema.date_formats.is_empty() {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid field {}: date formats are only allowed for date field",
+                name
+            )));
+        }
+
+        match (field_type, &schema.vector_options) {
+            (FieldType::Vector, Some(options)) => {
+                let dimension = options.dimension.unwrap_or_default();
+                if dimension <= 0 {
+                    return Err(OtsError::ValidationFailed(format!(
+                        "invalid vector field {}: dimension must be greater than 0, got {}",
+                        name, dimension
+                    )));
+                }
+            }
+
+            (FieldType::Vector, None) => {
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid vector field {}: vector options are required",
+                    name
+                )));
+            }
+
+            (_, Some(_)) => {
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid field {}: vector options are only allowed for vector field",
+                    name
+                )));
+            }
+
+            (_, None) => {}
+        }
+
+        if schema.is_virtual_field.unwrap_or_default() {
+            if schema.source_field_names.is_empty() {
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid virtual field {}: source field name is required",
+                    name
+                )));
+            }
+
+            for source in &schema.source_field_names {
+                let exists = schemas
+                    .iter()
+                    .any(|s| s.field_name.as_ref() == Some(source) && !s.is_virtual_field.unwrap_or_default());
+
+                if !exists {
+                    return Err(OtsError::ValidationFailed(format!(
+                        "invalid virtual field {}: source field {} does not exist",
+                        name, source
+                    )));
+                }
+            }
+        } else if !schema.source_field_names.is_empty() {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid field {}: source field names are only allowed for virtual field",
+                name
+            )));
+        }
+
+        if schema.field_type == Some(FieldType::Nested as i32) {
+            if schema.field_schemas.is_empty() {
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid nested field {}: sub fields can not be empty",
+                    name
+                )));
+            }
+
+            validate_field_schemas(&schema.field_schemas)?;
+        } else if !schema.field_schemas.is_empty() {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid field {}: sub fields are only allowed for nested field",
+                name
+            )));
+        }
+    }
+
+    Ok(())
+}
 
 /// 接口创建一个多元索引。这个请求数据太复杂了，还是建议去控制台创建吧。Sorry
 ///
@@ -29,6 +458,10 @@ impl CreateSearchIndexOperation {
     }
 
     pub async fn send(self) -> OtsResult<()> {
+        if let Some(schema) = &self.request.schema {
+            validate_field_schemas(&schema.field_schemas)?;
+        }
+
         let Self { client, request, options } = self;
 
         let req = OtsRequest {
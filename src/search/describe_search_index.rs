@@ -3,7 +3,7 @@ use prost::Message;
 use crate::{
     add_per_request_options,
     error::OtsError,
-    protos::search::{DescribeSearchIndexRequest, DescribeSearchIndexResponse},
+    protos::search::{DescribeSearchIndexRequest, IndexSchema, IndexSetting, IndexStatusEnum, MeteringInfo, QueryFlowWeight, SyncPhase},
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
 
@@ -33,6 +33,112 @@ impl DescribeSearchIndexRequest {
     }
 }
 
+/// 查询多元索引描述信息的响应
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DescribeSearchIndexResponse {
+    /// 多元索引的结构，包括字段和索引配置
+    pub schema: Option<IndexSchema>,
+
+    /// 数据同步阶段。刚创建的多元索引先进行全量同步（`Full`），全量同步完成之后进入增量同步（`Incr`），
+    /// 所以变为 `Incr` 之后就可以查询到数据表中已有的全部数据
+    pub sync_phase: Option<SyncPhase>,
+
+    /// 同步进度的时间戳。这个时间点之前写入数据表的数据已经同步到多元索引中
+    pub current_sync_timestamp: Option<i64>,
+
+    /// 计量信息，包括存储大小和行数
+    pub metering_info: Option<MeteringInfo>,
+
+    /// 动态修改索引结构时创建的新索引的名称
+    pub brother_index_name: Option<String>,
+
+    /// 查询流量在多元索引之间的权重
+    pub query_flow_weight: Vec<QueryFlowWeight>,
+
+    /// 创建时间
+    pub create_time: Option<i64>,
+
+    /// 数据生命周期，单位为秒。`-1` 表示永不过期
+    pub ttl_seconds: Option<i32>,
+
+    /// 索引状态
+    pub index_status: Option<IndexStatusEnum>,
+
+    /// 索引状态的描述信息
+    pub index_status_description: Option<String>,
+}
+
+impl DescribeSearchIndexResponse {
+    /// 全量同步是否已经完成。全量同步完成之后，才可以查询到数据表中已有的全部数据
+    pub fn is_full_sync_finished(&self) -> bool {
+        self.sync_phase == Some(SyncPhase::Incr)
+    }
+
+    /// 查询流量在多元索引之间的分配情况，返回索引名称和权重。没有设置流量权重时为空
+    pub fn query_flow_weights(&self) -> Vec<(&str, i32)> {
+        self.query_flow_weight
+            .iter()
+            .map(|w| (w.index_name.as_deref().unwrap_or_default(), w.weight.unwrap_or_default()))
+            .collect()
+    }
+
+    /// 多元索引的配置，包括分片数和路由字段
+    pub fn index_setting(&self) -> Option<&IndexSetting> {
+        self.schema.as_ref().and_then(|s| s.index_setting.as_ref())
+    }
+
+    /// 多元索引中的行数。这个值由计量信息统计，有一定的延迟
+    pub fn row_count(&self) -> Option<i64> {
+        self.metering_info.as_ref().and_then(|m| m.row_count)
+    }
+
+    /// 多元索引中第一层字段的名称
+    pub fn field_names(&self) -> Vec<&str> {
+        self.schema
+            .as_ref()
+            .map(|s| s.field_schemas.iter().map(|f| f.field_name.as_deref().unwrap_or_default()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl From<crate::protos::search::DescribeSearchIndexResponse> for DescribeSearchIndexResponse {
+    fn from(value: crate::protos::search::DescribeSearchIndexResponse) -> Self {
+        let crate::protos::search::DescribeSearchIndexResponse {
+            schema,
+            sync_stat,
+            metering_info,
+            brother_index_name,
+            query_flow_weight,
+            create_time,
+            time_to_live,
+            index_status,
+        } = value;
+
+        let (sync_phase, current_sync_timestamp) = match sync_stat {
+            Some(s) => (s.sync_phase.and_then(|n| SyncPhase::try_from(n).ok()), s.current_sync_timestamp),
+            None => (None, None),
+        };
+
+        let (index_status, index_status_description) = match index_status {
+            Some(s) => (s.status.and_then(|n| IndexStatusEnum::try_from(n).ok()), s.status_description),
+            None => (None, None),
+        };
+
+        Self {
+            schema,
+            sync_phase,
+            current_sync_timestamp,
+            metering_info,
+            brother_index_name,
+            query_flow_weight,
+            create_time,
+            ttl_seconds: time_to_live,
+            index_status,
+            index_status_description,
+        }
+    }
+}
+
 impl DescribeSearchIndexOperation {
     pub(crate) fn new(client: OtsClient, table_name: &str, index_name: &str) -> Self {
         Self {
@@ -59,6 +165,8 @@ impl DescribeSearchIndexOperation {
         };
 
         let resp = client.send(req).await?;
-        Ok(DescribeSearchIndexResponse::decode(resp.bytes().await?)?)
+        let resp_msg = crate::protos::search::DescribeSearchIndexResponse::decode(resp.bytes().await?)?;
+
+        Ok(resp_msg.into())
     }
 }
@@ -2,7 +2,9 @@ use std::{collections::HashMap, ops::Range};
 
 use prost::Message;
 
-use super::{validate_aggregation_name, validate_group_name, validate_timezone_string, Aggregation, AggregationResult, Duration, GeoPoint, Query};
+use super::{
+    validate_aggregation_name, validate_group_name, validate_timezone_string, validate_unique_names, Aggregation, AggregationResult, Duration, GeoPoint, Query,
+};
 use crate::model::rules::validate_column_name;
 use crate::{
     error::OtsError,
@@ -208,6 +210,8 @@ impl GroupByField {
             s.validate()?;
         }
 
+        validate_unique_names(&self.sub_aggregations, &self.sub_group_bys)?;
+
         for g in &self.sub_group_bys {
             g.validate()?;
         }
@@ -327,6 +331,8 @@ impl GroupByFilter {
             return Err(OtsError::ValidationFailed("filters are required, please set a valid value".to_string()));
         }
 
+        validate_unique_names(&self.sub_aggregations, &self.sub_group_bys)?;
+
         for g in &self.sub_group_bys {
             g.validate()?;
         }
@@ -525,6 +531,8 @@ impl GroupByHistogram {
             s.validate()?;
         }
 
+        validate_unique_names(&self.sub_aggregations, &self.sub_group_bys)?;
+
         for g in &self.sub_group_bys {
             g.validate()?;
         }
@@ -669,6 +677,8 @@ impl GroupByRange {
             return Err(OtsError::ValidationFailed("ranges is required, please set a valid value".to_string()));
         }
 
+        validate_unique_names(&self.sub_aggregations, &self.sub_group_bys)?;
+
         for g in &self.sub_group_bys {
             g.validate()?;
         }
@@ -894,6 +904,8 @@ impl GroupByDateHistogram {
             s.validate()?;
         }
 
+        validate_unique_names(&self.sub_aggregations, &self.sub_group_bys)?;
+
         for g in &self.sub_group_bys {
             g.validate()?;
         }
@@ -1036,6 +1048,8 @@ impl GroupByGeoGrid {
             return Err(OtsError::ValidationFailed("size is too large".to_string()));
         }
 
+        validate_unique_names(&self.sub_aggregations, &self.sub_group_bys)?;
+
         for g in &self.sub_group_bys {
             g.validate()?;
         }
@@ -1179,6 +1193,8 @@ impl GroupByGeoDistance {
             return Err(OtsError::ValidationFailed("ranges must not be empty".to_string()));
         }
 
+        validate_unique_names(&self.sub_aggregations, &self.sub_group_bys)?;
+
         for g in &self.sub_group_bys {
             g.validate()?;
         }
@@ -1346,6 +1362,8 @@ impl GroupByComposite {
             g.validate()?;
         }
 
+        validate_unique_names(&self.sub_aggregations, &self.sub_group_bys)?;
+
         for g in &self.sub_group_bys {
             g.validate()?;
         }
@@ -1449,6 +1467,20 @@ impl From<GroupBy> for crate::protos::search::GroupBy {
 }
 
 impl GroupBy {
+    /// 分组名称
+    pub fn name(&self) -> &str {
+        match self {
+            GroupBy::Field(gb) => &gb.name,
+            GroupBy::Filter(gb) => &gb.name,
+            GroupBy::Range(gb) => &gb.name,
+            GroupBy::Histogram(gb) => &gb.name,
+            GroupBy::DateHistogram(gb) => &gb.name,
+            GroupBy::GeoGrid(gb) => &gb.name,
+            GroupBy::GeoDistance(gb) => &gb.name,
+            GroupBy::Composite(gb) => &gb.name,
+        }
+    }
+
     pub(crate) fn validate(&self) -> OtsResult<()> {
         match self {
             GroupBy::Field(gb) => gb.validate(),
@@ -1876,6 +1908,86 @@ pub enum GroupByResult {
     Composite(Vec<GroupByCompositeResultItem>),
 }
 
+impl GroupByResult {
+    /// 字段值分组的结果。不是字段值分组时返回 `None`
+    pub fn as_field(&self) -> Option<&Vec<GroupByFieldResultItem>> {
+        match self {
+            Self::Field(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 过滤器分组的结果。不是过滤器分组时返回 `None`
+    pub fn as_filter(&self) -> Option<&Vec<GroupByFilterResultItem>> {
+        match self {
+            Self::Filter(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 范围分组的结果。不是范围分组时返回 `None`
+    pub fn as_range(&self) -> Option<&Vec<GroupByRangeResultItem>> {
+        match self {
+            Self::Range(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 直方图分组的结果。不是直方图分组时返回 `None`
+    pub fn as_histogram(&self) -> Option<&Vec<GroupByHistogramResultItem>> {
+        match self {
+            Self::Histogram(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 日期直方图分组的结果。不是日期直方图分组时返回 `None`
+    pub fn as_date_histogram(&self) -> Option<&Vec<GroupByDateHistogramResultItem>> {
+        match self {
+            Self::DateHistogram(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 地理网格分组的结果。不是地理网格分组时返回 `None`
+    pub fn as_geo_grid(&self) -> Option<&Vec<GroupByGeoGridResultItem>> {
+        match self {
+            Self::GeoGrid(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 地理位置分组的结果。不是地理位置分组时返回 `None`
+    pub fn as_geo_distance(&self) -> Option<&Vec<GroupByGeoDistanceResultItem>> {
+        match self {
+            Self::GeoDistance(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 多列分组的结果。不是多列分组时返回 `None`
+    pub fn as_composite(&self) -> Option<&Vec<GroupByCompositeResultItem>> {
+        match self {
+            Self::Composite(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 所有分组的行数之和，不区分分组类型
+    pub fn row_count_total(&self) -> u64 {
+        match self {
+            Self::Field(items) => items.iter().map(|item| item.row_count).sum(),
+            Self::Filter(items) => items.iter().map(|item| item.row_count).sum(),
+            Self::Range(items) => items.iter().map(|item| item.row_count).sum(),
+            Self::Histogram(items) => items.iter().map(|item| item.row_count).sum(),
+            Self::DateHistogram(items) => items.iter().map(|item| item.row_count).sum(),
+            Self::GeoGrid(items) => items.iter().map(|item| item.row_count).sum(),
+            Self::GeoDistance(items) => items.iter().map(|item| item.row_count).sum(),
+            Self::Composite(items) => items.iter().map(|item| item.row_count).sum(),
+        }
+    }
+}
+
 impl TryFrom<crate::protos::search::GroupByResult> for GroupByResult {
     type Error = OtsError;
 
@@ -2041,10 +2153,100 @@ mod test_group_by {
 
     use prost::Message;
 
-    use crate::test_util::setup;
+    use crate::{
+        model::ColumnValue,
+        protos::search::{
+            GroupByFieldResult, GroupByFieldResultItem, GroupByHistogramItem, GroupByHistogramResult, GroupByRangeResult, GroupByRangeResultItem, GroupByType,
+            GroupBysResult,
+        },
+        test_util::setup,
+    };
 
     use super::GroupByResult;
 
+    fn group_by_result(name: &str, group_by_type: GroupByType, bytes: Vec<u8>) -> crate::protos::search::GroupByResult {
+        crate::protos::search::GroupByResult {
+            name: Some(name.to_string()),
+            r#type: Some(group_by_type as i32),
+            group_by_result: Some(bytes),
+        }
+    }
+
+    #[test]
+    fn test_group_by_result_accessors() {
+        let by_field = GroupByFieldResult {
+            group_by_field_result_items: vec![
+                GroupByFieldResultItem {
+                    key: Some("M".to_string()),
+                    row_count: Some(3),
+                    ..Default::default()
+                },
+                GroupByFieldResultItem {
+                    key: Some("F".to_string()),
+                    row_count: Some(4),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let by_range = GroupByRangeResult {
+            group_by_range_result_items: vec![GroupByRangeResultItem {
+                from: Some(0.0),
+                to: Some(10.0),
+                row_count: Some(5),
+                ..Default::default()
+            }],
+        };
+
+        let by_histogram = GroupByHistogramResult {
+            group_by_histogra_items: vec![
+                GroupByHistogramItem {
+                    key: Some(ColumnValue::Integer(0).encode_plain_buffer()),
+                    value: Some(1),
+                    ..Default::default()
+                },
+                GroupByHistogramItem {
+                    key: Some(ColumnValue::Integer(10).encode_plain_buffer()),
+                    value: Some(2),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let msg = GroupBysResult {
+            group_by_results: vec![
+                group_by_result("g_field", GroupByType::GroupByField, by_field.encode_to_vec()),
+                group_by_result("g_range", GroupByType::GroupByRange, by_range.encode_to_vec()),
+                group_by_result("g_histogram", GroupByType::GroupByHistogram, by_histogram.encode_to_vec()),
+            ],
+        };
+
+        let msg = GroupBysResult::decode(msg.encode_to_vec().as_slice()).unwrap();
+        let map = HashMap::<String, GroupByResult>::try_from(msg).unwrap();
+
+        let g_field = map.get("g_field").unwrap();
+        let items = g_field.as_field().unwrap();
+        assert_eq!(2, items.len());
+        assert_eq!("M", items[0].value);
+        assert!(g_field.as_range().is_none());
+        assert!(g_field.as_histogram().is_none());
+        assert_eq!(7, g_field.row_count_total());
+
+        let g_range = map.get("g_range").unwrap();
+        let items = g_range.as_range().unwrap();
+        assert_eq!(1, items.len());
+        assert_eq!(10.0, items[0].value_to);
+        assert!(g_range.as_field().is_none());
+        assert_eq!(5, g_range.row_count_total());
+
+        let g_histogram = map.get("g_histogram").unwrap();
+        let items = g_histogram.as_histogram().unwrap();
+        assert_eq!(2, items.len());
+        assert_eq!(ColumnValue::Integer(10), items[1].value);
+        assert!(g_histogram.as_filter().is_none());
+        assert_eq!(3, g_histogram.row_count_total());
+    }
+
     #[test]
     fn test_group_by_result_parser() {
         setup();
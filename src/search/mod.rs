@@ -1,9 +1,9 @@
 //! 多元索引模块
 
 use regex::Regex;
-use std::{fmt::Display, ops::Range};
+use std::{collections::HashSet, fmt::Display, ops::Range};
 
-use crate::protos::search::DateTimeUnit;
+use crate::{error::OtsError, protos::search::DateTimeUnit, OtsResult};
 
 mod aggregation;
 mod compute_splits;
@@ -69,6 +69,27 @@ pub(crate) fn validate_aggregation_name(name: &str) -> bool {
     validate_group_name(name)
 }
 
+/// 验证同一层级中的聚合名称和分组名称没有重复。
+/// 同名的聚合（或者分组）在结果中会互相覆盖，所以在发送请求之前返回错误。
+/// 聚合和分组的结果是分开存放的，所以聚合和分组之间可以同名
+pub(crate) fn validate_unique_names(aggregations: &[Aggregation], group_bys: &[GroupBy]) -> OtsResult<()> {
+    let mut names = HashSet::new();
+    for a in aggregations {
+        if !names.insert(a.name()) {
+            return Err(OtsError::ValidationFailed(format!("duplicated aggregation name: {}", a.name())));
+        }
+    }
+
+    let mut names = HashSet::new();
+    for g in group_bys {
+        if !names.insert(g.name()) {
+            return Err(OtsError::ValidationFailed(format!("duplicated group by name: {}", g.name())));
+        }
+    }
+
+    Ok(())
+}
+
 /// 验证是否是符合 OTS 要求的时区字符串
 pub(crate) fn validate_timezone_string(tz: &str) -> bool {
     let regex = Regex::new(r"(?m)^[+-]\d{2}:\d{2}$").unwrap();
@@ -142,6 +163,9 @@ impl From<Duration> for crate::protos::search::DateTimeValue {
     }
 }
 
+/// 计算球面距离时使用的地球平均半径，单位为米
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
 /// 坐标点，是一个经纬度值。
 #[derive(Debug, Default, Clone, Copy)]
 pub struct GeoPoint {
@@ -156,6 +180,43 @@ impl GeoPoint {
     pub fn new(lat: f64, lng: f64) -> Self {
         Self { latitude: lat, longitude: lng }
     }
+
+    /// 使用 Haversine 公式计算两个坐标点之间的球面距离，单位为米。
+    /// 把地球视为球体计算，和服务端的计算结果可能有细微的差别，适用于在客户端对查询结果做二次过滤
+    pub fn haversine_distance_m(&self, other: &GeoPoint) -> f64 {
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let d_lat = lat2 - lat1;
+        let d_lng = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
+
+        // 接近对跖点时浮点误差可能使 `a` 略大于 1，`asin` 会返回 `NaN`
+        2.0 * EARTH_RADIUS_M * a.sqrt().min(1.0).asin()
+    }
+
+    /// 纬度必须在 `[-90, 90]` 之内，经度必须在 `[-180, 180]` 之内
+    pub(crate) fn validate(&self) -> OtsResult<()> {
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid geo point {}: latitude {} is out of range [-90, 90]",
+                self, self.latitude
+            )));
+        }
+
+        if !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid geo point {}: longitude {} is out of range [-180, 180]",
+                self, self.longitude
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn same_as(&self, other: &GeoPoint) -> bool {
+        self.latitude == other.latitude && self.longitude == other.longitude
+    }
 }
 
 impl Display for GeoPoint {
@@ -184,18 +245,34 @@ impl From<Range<f64>> for crate::protos::search::Range {
 
 #[cfg(test)]
 mod test_search_index {
+    use futures_core::Stream;
+    use prost::Message;
+
     use crate::{
-        model::ColumnValue,
-        protos::search::{ColumnReturnType, CreateSearchIndexRequest, FieldSchema, FieldType, IndexSchema, SortOrder},
+        error::OtsError,
+        model::{ColumnValue, Row},
+        protos::{
+            plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM},
+            search::{
+                ColumnReturnType, CreateSearchIndexRequest, FieldType, HighlightField, HighlightResult, IndexSchema, IndexStatus, IndexStatusEnum,
+                MeteringInfo, SearchInnerHit, SortOrder, SyncPhase, SyncStat, UpdateSearchIndexRequest, VectorDataType, VectorMetricType,
+            },
+        },
         search::{
-            Aggregation, AvgAggregation, CountAggregation, DistinctCountAggregation, GroupBy, GroupByField, GroupByHistogram, GroupByRange, GroupByResult,
-            MaxAggregation, MinAggregation, ParallelScanRequest, PercentilesAggregation, ScanQuery, Sorter, SumAggregation, TopRowsAggregation,
+            create_search_index::validate_field_schemas, Aggregation, AggregationResult, Analyzer, AvgAggregation, CountAggregation,
+            DescribeSearchIndexResponse, DistinctCountAggregation, FieldSchemaBuilder, GroupBy, GroupByField, GroupByHistogram, GroupByRange, GroupByResult,
+            IndexSchemaBuilder, MaxAggregation, MinAggregation, ParallelScanRequest, PercentilesAggregation, ScanQuery, Sorter, SumAggregation,
+            TopRowsAggregation,
         },
         test_util::setup,
         OtsClient,
     };
 
-    use super::{BoolQuery, ConstScoreQuery, GroupByFilter, MatchQuery, Query, RangeQuery, SearchQuery, SearchRequest, WildcardQuery};
+    use super::{
+        BoolQuery, ConstScoreQuery, FieldSort, FunctionScoreQuery, GeoBoundingBoxQuery, GeoDistanceQuery, GeoPoint, GeoPolygonQuery, GroupByFilter, Highlight,
+        HighlightParameter, InnerHits, KnnVectorQuery, MatchAllQuery, MatchQuery, NestedQuery, Query, RangeQuery, SearchQuery, SearchRequest, SearchResponse,
+        TermQuery, WildcardQuery,
+    };
 
     #[tokio::test]
     async fn test_list_search_index() {
@@ -216,11 +293,7 @@ mod test_search_index {
                 table_name: "data_types".to_string(),
                 index_name: "si_1".to_string(),
                 schema: Some(IndexSchema {
-                    field_schemas: vec![FieldSchema {
-                        field_name: Some("str_col".to_string()),
-                        field_type: Some(FieldType::Text as i32),
-                        ..Default::default()
-                    }],
+                    field_schemas: vec![FieldSchemaBuilder::new("str_col", FieldType::Text).build()],
                     index_setting: None,
                     index_sort: None,
                 }),
@@ -234,6 +307,719 @@ mod test_search_index {
         assert!(resp.is_ok());
     }
 
+    #[test]
+    fn test_field_schema_builder() {
+        let fields = vec![
+            FieldSchemaBuilder::new("created_at", FieldType::Keyword).build(),
+            FieldSchemaBuilder::virtual_field("created_at_date", FieldType::Date, "created_at")
+                .date_format("yyyy-MM-dd HH:mm:ss")
+                .build(),
+            FieldSchemaBuilder::nested(
+                "orders",
+                [
+                    FieldSchemaBuilder::new("order_id", FieldType::Keyword).build(),
+                    FieldSchemaBuilder::new("amount", FieldType::Double).sort_and_agg(true).build(),
+                ],
+            )
+            .build(),
+        ];
+
+        assert_eq!(Some(true), fields[1].is_virtual_field);
+        assert_eq!(vec!["created_at".to_string()], fields[1].source_field_names);
+        assert_eq!(2, fields[2].field_schemas.len());
+        assert!(validate_field_schemas(&fields).is_ok());
+
+        // 虚拟列引用不存在的列
+        let invalid = vec![FieldSchemaBuilder::virtual_field("created_at_date", FieldType::Date, "created_at").build()];
+        assert!(validate_field_schemas(&invalid).is_err());
+
+        // 虚拟列引用另一个虚拟列
+        let invalid = vec![
+            FieldSchemaBuilder::new("created_at", FieldType::Keyword).build(),
+            FieldSchemaBuilder::virtual_field("v1", FieldType::Keyword, "created_at").build(),
+            FieldSchemaBuilder::virtual_field("v2", FieldType::Date, "v1").build(),
+        ];
+        assert!(validate_field_schemas(&invalid).is_err());
+
+        // 嵌套类型必须有子字段，子字段中的虚拟列也会校验
+        assert!(validate_field_schemas(&[FieldSchemaBuilder::nested("orders", []).build()]).is_err());
+        let invalid = vec![FieldSchemaBuilder::nested("orders", [FieldSchemaBuilder::virtual_field("v1", FieldType::Long, "order_id").build()]).build()];
+        assert!(validate_field_schemas(&invalid).is_err());
+
+        // 非嵌套类型不能有子字段
+        let invalid = vec![FieldSchemaBuilder::new("name", FieldType::Keyword)
+            .sub_field(FieldSchemaBuilder::new("sub", FieldType::Keyword).build())
+            .build()];
+        assert!(validate_field_schemas(&invalid).is_err());
+    }
+
+    fn users_index_schema() -> IndexSchemaBuilder {
+        IndexSchemaBuilder::new()
+            .field(
+                FieldSchemaBuilder::text("full_name")
+                    .text_analyzer(Analyzer::Fuzzy {
+                        min_chars: Some(1),
+                        max_chars: Some(5),
+                        case_sensitive: None,
+                    })
+                    .enable_highlighting(true)
+                    .build(),
+            )
+            .field(FieldSchemaBuilder::keyword("gender").sort_and_agg(true).build())
+            .field(FieldSchemaBuilder::long("age").sort_and_agg(true).build())
+            .field(FieldSchemaBuilder::double("score").sort_and_agg(true).build())
+            .field(FieldSchemaBuilder::boolean("validated").build())
+            .field(FieldSchemaBuilder::geo_point("location").build())
+            .field(FieldSchemaBuilder::date("created_at").date_format("yyyy-MM-dd HH:mm:ss").build())
+            .field(FieldSchemaBuilder::vector("embedding", 4, VectorMetricType::VmCosine).build())
+            .field(FieldSchemaBuilder::nested("tags", [FieldSchemaBuilder::keyword("name").build()]).build())
+    }
+
+    #[test]
+    fn test_index_schema_builder() {
+        let builder = users_index_schema();
+        assert!(builder.validate().is_ok());
+
+        let schema = builder.routing_field("user_id").build();
+        assert_eq!(9, schema.field_schemas.len());
+        assert_eq!(vec!["user_id".to_string()], schema.index_setting.unwrap().routing_fields);
+
+        let full_name = &schema.field_schemas[0];
+        assert_eq!(Some("fuzzy".to_string()), full_name.analyzer);
+        let param = crate::protos::search::FuzzyAnalyzerParameter::decode(full_name.analyzer_parameter.as_deref().unwrap()).unwrap();
+        assert_eq!(Some(5), param.max_chars);
+
+        let embedding = schema.field_schemas[7].vector_options.unwrap();
+        assert_eq!(Some(4), embedding.dimension);
+        assert_eq!(Some(VectorMetricType::VmCosine as i32), embedding.metric_type);
+
+        let invalid = [
+            // 只有 Text 类型可以设置分词器
+            FieldSchemaBuilder::keyword("gender").text_analyzer(Analyzer::MaxWord).build(),
+            // Text 类型不支持排序和统计聚合
+            FieldSchemaBuilder::text("full_name").sort_and_agg(true).build(),
+            // 只有 Date 类型可以设置日期格式
+            FieldSchemaBuilder::long("age").date_format("yyyy-MM-dd").build(),
+            // 向量维度必须大于 0
+            FieldSchemaBuilder::vector("embedding", 0, VectorMetricType::VmEuclidean).build(),
+            // 向量类型必须设置向量参数
+            FieldSchemaBuilder::new("embedding", FieldType::Vector).build(),
+        ];
+
+        for field in invalid {
+            assert!(IndexSchemaBuilder::new().field(field).validate().is_err());
+        }
+
+        assert!(IndexSchemaBuilder::new().validate().is_err());
+    }
+
+    async fn test_create_search_index_with_builder_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let resp = client
+            .create_search_index(CreateSearchIndexRequest {
+                table_name: "users".to_string(),
+                index_name: "users_index_builder".to_string(),
+                schema: Some(users_index_schema().build()),
+                ..Default::default()
+            })
+            .send()
+            .await;
+
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+
+        let resp = client.describe_search_index("users", "users_index_builder").send().await;
+        assert!(resp.is_ok());
+
+        let resp = client.delete_search_index("users", "users_index_builder").send().await;
+        assert!(resp.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_search_index_with_builder() {
+        test_create_search_index_with_builder_impl().await;
+    }
+
+    #[test]
+    fn test_field_schema_builder_vector() {
+        let schema = FieldSchemaBuilder::vector("embedding", 128, VectorMetricType::VmCosine).build();
+        assert_eq!(Some(FieldType::Vector as i32), schema.field_type);
+
+        let options = schema.vector_options.unwrap();
+        assert_eq!(Some(VectorDataType::VdFloat32 as i32), options.data_type);
+        assert_eq!(Some(128), options.dimension);
+        assert_eq!(Some(VectorMetricType::VmCosine as i32), options.metric_type);
+        assert!(validate_field_schemas(&[schema]).is_ok());
+
+        // 维度必须大于 0
+        let schema = FieldSchemaBuilder::vector("embedding", 0, VectorMetricType::VmEuclidean).build();
+        assert!(matches!(validate_field_schemas(&[schema]), Err(OtsError::ValidationFailed(_))));
+    }
+
+    #[test]
+    fn test_knn_vector_query_validate_with_schema() {
+        let schema = IndexSchemaBuilder::new()
+            .field(FieldSchemaBuilder::vector("embedding", 128, VectorMetricType::VmCosine).build())
+            .field(FieldSchemaBuilder::keyword("name").build())
+            .build();
+
+        assert!(KnnVectorQuery::new("embedding", vec![0.1; 128], 10).validate_with_schema(&schema).is_ok());
+
+        // 向量长度和维度不一致
+        assert!(KnnVectorQuery::new("embedding", vec![0.1; 64], 10).validate_with_schema(&schema).is_err());
+
+        // 不是向量字段，或者字段不存在
+        assert!(KnnVectorQuery::new("name", vec![0.1; 128], 10).validate_with_schema(&schema).is_err());
+        assert!(KnnVectorQuery::new("missing", vec![0.1; 128], 10).validate_with_schema(&schema).is_err());
+    }
+
+    async fn test_create_search_index_with_vector_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let resp = client
+            .create_search_index(CreateSearchIndexRequest {
+                table_name: "users".to_string(),
+                index_name: "users_vector_index".to_string(),
+                schema: Some(
+                    IndexSchemaBuilder::new()
+                        .field(FieldSchemaBuilder::vector("embedding", 128, VectorMetricType::VmCosine).build())
+                        .build(),
+                ),
+                ..Default::default()
+            })
+            .send()
+            .await;
+
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+
+        let resp = client.describe_search_index("users", "users_vector_index").send().await;
+        assert!(resp.is_ok());
+
+        let schema = resp.unwrap().schema.unwrap();
+        let options = schema.field_schemas[0].vector_options.unwrap();
+        assert_eq!(Some(128), options.dimension);
+        assert_eq!(Some(VectorMetricType::VmCosine as i32), options.metric_type);
+        assert!(KnnVectorQuery::new("embedding", vec![0.1; 128], 10).validate_with_schema(&schema).is_ok());
+
+        let resp = client.delete_search_index("users", "users_vector_index").send().await;
+        assert!(resp.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_search_index_with_vector() {
+        test_create_search_index_with_vector_impl().await;
+    }
+
+    #[test]
+    fn test_update_search_index_query_flow_weight() {
+        let request = UpdateSearchIndexRequest::new("users", "users_index")
+            .query_flow_weight("users_index", 80)
+            .query_flow_weight("users_index_reindex", 20);
+        assert!(request.validate().is_ok());
+        assert_eq!(2, request.query_flow_weight.len());
+
+        let request = UpdateSearchIndexRequest::new("users", "users_index")
+            .query_flow_weight("users_index", 80)
+            .query_flow_weight("users_index_reindex", 30);
+        assert!(request.validate().is_err());
+
+        let request = UpdateSearchIndexRequest::new("users", "users_index")
+            .query_flow_weight("users_index", 120)
+            .query_flow_weight("users_index_reindex", -20);
+        assert!(request.validate().is_err());
+
+        let request = UpdateSearchIndexRequest::new("users", "users_index")
+            .query_flow_weight("users_index", 50)
+            .query_flow_weight("users_index", 50);
+        assert!(request.validate().is_err());
+
+        assert!(UpdateSearchIndexRequest::new("users", "users_index").validate().is_ok());
+
+        let resp = DescribeSearchIndexResponse {
+            query_flow_weight: request.query_flow_weight,
+            ..Default::default()
+        };
+        assert_eq!(vec![("users_index", 50), ("users_index", 50)], resp.query_flow_weights());
+    }
+
+    #[test]
+    fn test_search_response_total_count() {
+        let msg = crate::protos::search::SearchResponse {
+            total_hits: Some(-1),
+            ..Default::default()
+        };
+
+        let resp = SearchResponse::try_from(msg).unwrap();
+        assert_eq!(None, resp.total_count);
+        assert_eq!(0, resp.total_hits);
+        assert!(!resp.is_total_count_exact);
+
+        let msg = crate::protos::search::SearchResponse {
+            total_hits: Some(42),
+            ..Default::default()
+        };
+
+        let resp = SearchResponse::try_from(msg).unwrap();
+        assert_eq!(Some(42), resp.total_count);
+        assert_eq!(42, resp.total_hits);
+    }
+
+    #[test]
+    fn test_search_response_consumed() {
+        let msg = crate::protos::search::SearchResponse {
+            consumed: Some(crate::protos::ConsumedCapacity {
+                capacity_unit: crate::protos::CapacityUnit { read: Some(3), write: None },
+                capacity_data_size: None,
+            }),
+            ..Default::default()
+        };
+
+        let resp = SearchResponse::try_from(msg).unwrap();
+        assert_eq!(Some(3), resp.consumed.read_cu());
+        assert_eq!(None, resp.reserved_consumed.read_cu());
+    }
+
+    async fn test_search_consumed_capacity_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new())).limit(100);
+        let resp = client
+            .search(SearchRequest::new("users", "users_index", query).column_return_type(ColumnReturnType::ReturnAllFromIndex))
+            .send()
+            .await
+            .unwrap();
+
+        log::debug!("consumed: {:?}, reserved consumed: {:?}", resp.consumed, resp.reserved_consumed);
+
+        // 按量模式和预留模式的实例分别在不同的字段中返回消耗的读服务能力单元
+        let read_cu = resp.consumed.read_cu().unwrap_or(0) + resp.reserved_consumed.read_cu().unwrap_or(0);
+        assert!(read_cu > 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_consumed_capacity() {
+        test_search_consumed_capacity_impl().await;
+    }
+
+    async fn test_search_track_total_count_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new())).track_total_count(true).limit(1);
+        let resp = client.search(SearchRequest::new("users", "users_index", query)).send().await.unwrap();
+
+        log::debug!("total count: {:?}", resp.total_count);
+        assert!(resp.total_count.unwrap() > 0);
+        assert!(resp.is_total_count_exact);
+        assert_eq!(resp.total_count.unwrap() as u64, resp.total_hits);
+    }
+
+    #[tokio::test]
+    async fn test_search_track_total_count() {
+        test_search_track_total_count_impl().await;
+    }
+
+    async fn test_search_highlight_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let highlight = Highlight::new().parameter(HighlightParameter::new("full_name").pre_tag("<b>").post_tag("</b>").number_of_fragments(1));
+        let query = SearchQuery::new(Query::Match(MatchQuery::new("full_name", "万宇驰")))
+            .highlight(highlight)
+            .limit(10);
+
+        let resp = client
+            .search(SearchRequest::new("users", "users_index", query).column_return_type(ColumnReturnType::ReturnAllFromIndex))
+            .send()
+            .await
+            .unwrap();
+
+        assert!(!resp.hits.is_empty());
+
+        for hit in &resp.hits {
+            log::debug!("{:?}: {:?}", hit.row.get_primary_key_value("user_id"), hit.highlights);
+        }
+
+        let fragments = resp.hits.iter().filter_map(|h| h.highlights.get("full_name")).flatten().collect::<Vec<_>>();
+        assert!(!fragments.is_empty());
+        assert!(fragments.iter().any(|f| f.contains("<b>") && f.contains("</b>")));
+    }
+
+    #[tokio::test]
+    async fn test_search_highlight() {
+        test_search_highlight_impl().await;
+    }
+
+    #[test]
+    fn test_search_response_hits() {
+        let row1 = Row::new().primary_key_column_string("user_id", "u1").column_string("name", "alice");
+        let row2 = Row::new().primary_key_column_string("user_id", "u2").column_string("name", "bob");
+
+        let highlight = |name: &str, fragment: &str| HighlightResult {
+            highlight_fields: vec![HighlightField {
+                field_name: Some(name.to_string()),
+                field_fragments: vec![fragment.to_string()],
+            }],
+        };
+
+        let msg = crate::protos::search::SearchResponse {
+            total_hits: Some(2),
+            rows: vec![
+                row1.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM),
+                vec![],
+                row2.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM),
+            ],
+            search_hits: vec![
+                crate::protos::search::SearchHit {
+                    score: Some(1.5),
+                    highlight_result: Some(highlight("name", "<em>alice</em>")),
+                    search_inner_hits: vec![SearchInnerHit {
+                        path: Some("tags".to_string()),
+                        search_hits: vec![crate::protos::search::SearchHit {
+                            score: Some(0.5),
+                            highlight_result: Some(highlight("tags.name", "<em>red</em>")),
+                            search_inner_hits: vec![],
+                            nested_doc_offset: Some(2),
+                        }],
+                    }],
+                    nested_doc_offset: None,
+                },
+                crate::protos::search::SearchHit::default(),
+                crate::protos::search::SearchHit {
+                    score: Some(0.8),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let resp = SearchResponse::try_from(msg).unwrap();
+        assert_eq!(2, resp.rows.len());
+        assert_eq!(2, resp.hits.len());
+
+        let hit = &resp.hits[0];
+        assert_eq!(resp.rows[0].primary_key, hit.row.primary_key);
+        assert_eq!(Some(1.5), hit.score);
+        assert_eq!(Some(&vec!["<em>alice</em>".to_string()]), hit.highlights.get("name"));
+
+        let tags = hit.nested.get("tags").unwrap();
+        assert_eq!(1, tags.len());
+        assert_eq!(Some(2), tags[0].offset);
+        assert_eq!(Some(0.5), tags[0].score);
+        assert_eq!(Some(&vec!["<em>red</em>".to_string()]), tags[0].highlights.get("tags.name"));
+
+        // 跳过空行之后，得分仍然对应到正确的行
+        let hit = &resp.hits[1];
+        assert_eq!(resp.rows[1].primary_key, hit.row.primary_key);
+        assert_eq!(Some(0.8), hit.score);
+        assert!(hit.highlights.is_empty());
+        assert!(hit.nested.is_empty());
+    }
+
+    #[test]
+    fn test_search_response_nested_hits() {
+        let row = Row::new()
+            .primary_key_column_string("user_id", "u1")
+            .column_string("tags", r#"[{"name":"red"},{"name":"blue"},{"name":"red"}]"#);
+
+        let inner_hit = |offset: i32, inner: Vec<SearchInnerHit>| crate::protos::search::SearchHit {
+            score: Some(1.0),
+            search_inner_hits: inner,
+            nested_doc_offset: Some(offset),
+            ..Default::default()
+        };
+
+        let msg = crate::protos::search::SearchResponse {
+            rows: vec![row.encode_plain_buffer(MASK_HEADER | MASK_ROW_CHECKSUM)],
+            search_hits: vec![crate::protos::search::SearchHit {
+                search_inner_hits: vec![SearchInnerHit {
+                    path: Some("tags".to_string()),
+                    search_hits: vec![
+                        inner_hit(0, vec![]),
+                        inner_hit(
+                            2,
+                            vec![SearchInnerHit {
+                                path: Some("tags.colors".to_string()),
+                                search_hits: vec![inner_hit(1, vec![])],
+                            }],
+                        ),
+                    ],
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let resp = SearchResponse::try_from(msg).unwrap();
+        let hit = &resp.hits[0];
+
+        // 数组中的多个子行都命中
+        let tags = hit.nested_hits("tags");
+        assert_eq!(vec![Some(0), Some(2)], tags.iter().map(|h| h.offset).collect::<Vec<_>>());
+        assert_eq!(Some(1), tags[1].nested.get("tags.colors").unwrap()[0].offset);
+        assert!(hit.nested_hits("missing").is_empty());
+    }
+
+    async fn test_query_nested_inner_hits_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let inner_hits = InnerHits::new().offset(0).limit(2);
+        let query = Query::Nested(Box::new(
+            NestedQuery::new("tags", Query::Term(TermQuery::new("tags.name", ColumnValue::String("red".to_string())))).inner_hits(inner_hits),
+        ));
+
+        let resp = client
+            .search(SearchRequest::new("users", "users_index", SearchQuery::new(query).limit(10)).column_return_type(ColumnReturnType::ReturnAllFromIndex))
+            .send()
+            .await
+            .unwrap();
+
+        assert!(!resp.hits.is_empty());
+
+        for hit in &resp.hits {
+            let tags = hit.nested_hits("tags");
+            log::debug!("{:?}: {:?}", hit.row.get_primary_key_value("user_id"), tags);
+            assert!(!tags.is_empty());
+            assert!(tags.len() <= 2);
+            assert!(tags.iter().all(|h| h.offset.is_some()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_nested_inner_hits() {
+        test_query_nested_inner_hits_impl().await;
+    }
+
+    #[test]
+    fn test_geo_point_haversine_distance() {
+        let beijing = GeoPoint::new(39.9042, 116.4074);
+        let shanghai = GeoPoint::new(31.2304, 121.4737);
+
+        // 北京到上海的球面距离约为 1067 公里
+        let d = beijing.haversine_distance_m(&shanghai);
+        assert!((d - 1_067_310.0).abs() < 1000.0, "distance: {}", d);
+        assert!((d - shanghai.haversine_distance_m(&beijing)).abs() < 1e-6);
+
+        assert_eq!(0.0, beijing.haversine_distance_m(&beijing));
+
+        // 同一经线上纬度相差 1 度约为 111.195 公里
+        let d = GeoPoint::new(0.0, 120.0).haversine_distance_m(&GeoPoint::new(1.0, 120.0));
+        assert!((d - 111_194.93).abs() < 1.0, "distance: {}", d);
+
+        // 对跖点之间的距离是半个大圆
+        let half_circle = std::f64::consts::PI * 6_371_000.0;
+        for (a, b) in [
+            (GeoPoint::new(0.0, 0.0), GeoPoint::new(0.0, 180.0)),
+            (GeoPoint::new(90.0, 0.0), GeoPoint::new(-90.0, 0.0)),
+            (GeoPoint::new(39.9042, 116.4074), GeoPoint::new(-39.9042, -63.5926)),
+        ] {
+            let d = a.haversine_distance_m(&b);
+            assert!(!d.is_nan());
+            assert!((d - half_circle).abs() < 1.0, "distance: {}", d);
+        }
+    }
+
+    #[test]
+    fn test_geo_bounding_box_from_center_radius() {
+        let center = GeoPoint::new(30.2741, 120.1551);
+        let query = GeoBoundingBoxQuery::from_center_radius("location", center, 1000.0);
+
+        assert!(query.top_left.latitude > center.latitude);
+        assert!(query.top_left.longitude < center.longitude);
+        assert!(query.bottom_right.latitude < center.latitude);
+        assert!(query.bottom_right.longitude > center.longitude);
+
+        // 中心到四条边的距离都等于半径
+        let north = GeoPoint::new(query.top_left.latitude, center.longitude);
+        let west = GeoPoint::new(center.latitude, query.top_left.longitude);
+        let south = GeoPoint::new(query.bottom_right.latitude, center.longitude);
+        let east = GeoPoint::new(center.latitude, query.bottom_right.longitude);
+
+        for p in [north, south] {
+            assert!((center.haversine_distance_m(&p) - 1000.0).abs() < 0.01);
+        }
+
+        // 沿纬线计算的距离比沿大圆计算的距离略大，所以东西两侧的距离在半径附近
+        for p in [west, east] {
+            assert!((center.haversine_distance_m(&p) - 1000.0).abs() < 1.0);
+        }
+
+        assert!(Query::GeoBoundingBox(query).validate().is_ok());
+
+        // 靠近极点时纬度不超过 90 度
+        let query = GeoBoundingBoxQuery::from_center_radius("location", GeoPoint::new(89.999, 0.0), 10_000.0);
+        assert_eq!(90.0, query.top_left.latitude);
+    }
+
+    #[test]
+    fn test_geo_query_validate_points() {
+        let valid = GeoPoint::new(30.0, 120.0);
+        let invalid = GeoPoint::new(91.0, 120.0);
+
+        let err = GeoDistanceQuery::new("location", invalid, 1000.0).validate().unwrap_err();
+        assert!(err.to_string().contains("91"), "{}", err);
+        assert!(GeoDistanceQuery::new("location", valid, 1000.0).validate().is_ok());
+        assert!(GeoDistanceQuery::new("location", valid, -1.0).validate().is_err());
+        assert!(GeoDistanceQuery::new("location", GeoPoint::new(30.0, -180.5), 1000.0).validate().is_err());
+
+        assert!(GeoBoundingBoxQuery::new("location", invalid, valid).validate().is_err());
+        assert!(GeoBoundingBoxQuery::new("location", GeoPoint::new(31.0, 119.0), valid).validate().is_ok());
+        assert!(GeoBoundingBoxQuery::new("location", valid, GeoPoint::new(31.0, 121.0)).validate().is_err());
+
+        let a = GeoPoint::new(30.0, 120.0);
+        let b = GeoPoint::new(31.0, 120.0);
+        let c = GeoPoint::new(31.0, 121.0);
+
+        // 两个点不能构成多边形，首尾相同的点只算一个顶点
+        assert!(GeoPolygonQuery::new("location", [a, b]).validate().is_err());
+        assert!(GeoPolygonQuery::new("location", [a, b, a]).validate().is_err());
+        assert!(GeoPolygonQuery::new("location", [a, b, invalid]).validate().is_err());
+        assert!(GeoPolygonQuery::new("location", [a, b, c, a]).validate().is_ok());
+
+        // 没有闭合的多边形会自动闭合
+        let msg = crate::protos::search::GeoPolygonQuery::from(GeoPolygonQuery::new("location", [a, b, c]));
+        assert_eq!(vec!["30,120", "31,120", "31,121", "30,120"], msg.points);
+
+        let msg = crate::protos::search::GeoPolygonQuery::from(GeoPolygonQuery::new("location", [a, b, c, a]));
+        assert_eq!(4, msg.points.len());
+    }
+
+    #[test]
+    fn test_aggregation_result_as_f64() {
+        assert_eq!(Some(1.5), AggregationResult::Min(1.5).as_f64());
+        assert_eq!(Some(9.5), AggregationResult::Max(9.5).as_f64());
+        assert_eq!(Some(3.25), AggregationResult::Avg(3.25).as_f64());
+        assert_eq!(Some(100.0), AggregationResult::Sum(100.0).as_f64());
+        assert_eq!(Some(42.0), AggregationResult::Count(42).as_f64());
+        assert_eq!(Some(7.0), AggregationResult::DistinctCount(7).as_f64());
+        assert_eq!(None, AggregationResult::Percentiles(vec![]).as_f64());
+        assert!(AggregationResult::Sum(100.0).as_rows().is_none());
+
+        let rows = AggregationResult::TopRows(vec![Row::new().primary_key_column_string("id", "1")]);
+        assert_eq!(None, rows.as_f64());
+        assert_eq!(1, rows.as_rows().unwrap().len());
+    }
+
+    #[test]
+    fn test_function_score_query() {
+        let query = Query::FunctionScore(Box::new(FunctionScoreQuery::new(Query::Match(MatchQuery::new("name", "明")), "height")));
+        assert!(query.validate().is_ok());
+
+        let bytes = crate::protos::search::Query::from(query).encode_to_vec();
+        let msg = crate::protos::search::Query::decode(bytes.as_slice()).unwrap();
+        assert_eq!(Some(crate::protos::search::QueryType::FunctionScoreQuery as i32), msg.r#type);
+
+        let fsq = crate::protos::search::FunctionScoreQuery::decode(msg.query.unwrap().as_slice()).unwrap();
+        assert_eq!(Some("height".to_string()), fsq.field_value_factor.unwrap().field_name);
+
+        let inner = fsq.query.unwrap();
+        assert_eq!(Some(crate::protos::search::QueryType::MatchQuery as i32), inner.r#type);
+        let mq = crate::protos::search::MatchQuery::decode(inner.query.unwrap().as_slice()).unwrap();
+        assert_eq!(Some("name".to_string()), mq.field_name);
+        assert_eq!(Some("明".to_string()), mq.text);
+
+        let query = Query::FunctionScore(Box::new(FunctionScoreQuery::new(Query::MatchAll(MatchAllQuery::new()), "")));
+        assert!(query.validate().is_err());
+    }
+
+    #[test]
+    fn test_describe_search_index_response_sync_stat() {
+        let msg = crate::protos::search::DescribeSearchIndexResponse {
+            schema: Some(
+                IndexSchemaBuilder::new()
+                    .field(FieldSchemaBuilder::keyword("name").build())
+                    .routing_field("id")
+                    .build(),
+            ),
+            sync_stat: Some(SyncStat {
+                sync_phase: Some(SyncPhase::Incr as i32),
+                current_sync_timestamp: Some(1_700_000_000_000_000),
+            }),
+            metering_info: Some(MeteringInfo {
+                row_count: Some(42),
+                ..Default::default()
+            }),
+            time_to_live: Some(-1),
+            index_status: Some(IndexStatus {
+                status: Some(IndexStatusEnum::Running as i32),
+                status_description: None,
+            }),
+            ..Default::default()
+        };
+
+        let resp = DescribeSearchIndexResponse::from(crate::protos::search::DescribeSearchIndexResponse::decode(msg.encode_to_vec().as_slice()).unwrap());
+        assert_eq!(Some(SyncPhase::Incr), resp.sync_phase);
+        assert!(resp.is_full_sync_finished());
+        assert_eq!(Some(1_700_000_000_000_000), resp.current_sync_timestamp);
+        assert_eq!(Some(-1), resp.ttl_seconds);
+        assert_eq!(Some(IndexStatusEnum::Running), resp.index_status);
+        assert_eq!(vec!["id".to_string()], resp.index_setting().unwrap().routing_fields);
+        assert_eq!(Some(42), resp.row_count());
+        assert_eq!(vec!["name"], resp.field_names());
+
+        let resp = DescribeSearchIndexResponse::from(crate::protos::search::DescribeSearchIndexResponse::default());
+        assert_eq!(None, resp.sync_phase);
+        assert!(!resp.is_full_sync_finished());
+        assert_eq!(None, resp.current_sync_timestamp);
+        assert!(resp.index_setting().is_none());
+        assert!(resp.field_names().is_empty());
+    }
+
+    async fn test_describe_search_index_sync_phase_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let schema = IndexSchemaBuilder::new()
+            .field(FieldSchemaBuilder::keyword("full_name").build())
+            .field(FieldSchemaBuilder::long("age").sort_and_agg(true).build())
+            .build();
+
+        let resp = client
+            .create_search_index(CreateSearchIndexRequest {
+                table_name: "users".to_string(),
+                index_name: "users_index_sync".to_string(),
+                schema: Some(schema),
+                ..Default::default()
+            })
+            .send()
+            .await;
+        assert!(resp.is_ok());
+
+        let mut phase = None;
+        for _ in 0..30 {
+            let resp = client.describe_search_index("users", "users_index_sync").send().await.unwrap();
+            log::debug!("sync phase: {:?}, timestamp: {:?}", resp.sync_phase, resp.current_sync_timestamp);
+            assert_eq!(vec!["full_name", "age"], resp.field_names());
+
+            phase = resp.sync_phase;
+            if phase == Some(SyncPhase::Incr) {
+                break;
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        }
+
+        assert!(phase.is_some());
+
+        let resp = client.delete_search_index("users", "users_index_sync").send().await;
+        assert!(resp.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_describe_search_index_sync_phase() {
+        test_describe_search_index_sync_phase_impl().await;
+    }
+
     #[tokio::test]
     async fn test_describe_search_index() {
         setup();
@@ -302,6 +1088,48 @@ mod test_search_index {
         test_search_match_query_impl().await;
     }
 
+    async fn test_search_row_stream_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let search_query = SearchQuery::new(Query::Match(MatchQuery::new("full_name", "万宇驰")))
+            .sorter(Sorter::PrimaryKey(SortOrder::Asc))
+            .limit(10);
+        let mut search_req = SearchRequest::new("users", "users_index", search_query).column_return_type(ColumnReturnType::ReturnAll);
+
+        let mut expected = 0;
+
+        loop {
+            let resp = client.search(search_req.clone()).send().await.unwrap();
+            expected += resp.rows.len();
+
+            match resp.next_token {
+                Some(token) => search_req.search_query.token = token,
+                None => break,
+            }
+        }
+
+        search_req.search_query.token = vec![];
+        let stream = client.search(search_req).into_row_stream();
+        tokio::pin!(stream);
+
+        let mut total_rows = 0;
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            assert!(row.is_ok());
+            total_rows += 1;
+        }
+
+        log::debug!("total rows: {}", total_rows);
+        assert!(total_rows > 0);
+        assert_eq!(expected, total_rows);
+    }
+
+    #[tokio::test]
+    async fn test_search_row_stream() {
+        test_search_row_stream_impl().await;
+    }
+
     async fn test_search_match_query_with_aggr_impl() {
         setup();
 
@@ -342,6 +1170,81 @@ mod test_search_index {
         test_search_match_query_with_aggr_impl().await;
     }
 
+    /// 测试折叠之后每组返回的行是按照排序设置排在最前面的行
+    async fn test_search_collapse_with_sorter_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let sorter = Sorter::Field(FieldSort::new("score").order(SortOrder::Desc));
+
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new()))
+            .collapse_field_name("gender")
+            .sorter(sorter.clone())
+            .limit(10);
+
+        let search_req = SearchRequest::new("users", "users_index", query).column_return_type(ColumnReturnType::ReturnAll);
+
+        let resp = client.search(search_req).send().await;
+        log::debug!("{:?}", resp);
+        assert!(resp.is_ok());
+
+        let resp = resp.unwrap();
+        assert!(!resp.rows.is_empty());
+
+        for row in &resp.rows {
+            let gender = row.get_column_value("gender").cloned();
+            assert!(gender.is_some());
+
+            let query = SearchQuery::new(Query::Term(TermQuery::new("gender", gender.unwrap())))
+                .sorter(sorter.clone())
+                .limit(1);
+
+            let search_req = SearchRequest::new("users", "users_index", query).column_return_type(ColumnReturnType::ReturnAll);
+            let group_resp = client.search(search_req).send().await;
+            assert!(group_resp.is_ok());
+
+            let group_resp = group_resp.unwrap();
+            assert_eq!(1, group_resp.rows.len());
+            assert_eq!(row.get_column_value("score"), group_resp.rows[0].get_column_value("score"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_collapse_with_sorter() {
+        test_search_collapse_with_sorter_impl().await;
+    }
+
+    async fn test_search_hydrate_rows_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let query = SearchQuery::new(Query::Match(MatchQuery::new("full_name", "万宇驰"))).limit(10);
+        let search_req = SearchRequest::new("users", "users_index", query).column_return_type(ColumnReturnType::ReturnAllFromIndex);
+
+        let resp = client.search(search_req).send().await;
+        assert!(resp.is_ok());
+
+        let resp = resp.unwrap();
+
+        let rows = resp.hydrate_rows(&client, "users").await;
+        log::debug!("{:?}", rows);
+        assert!(rows.is_ok());
+
+        let rows = rows.unwrap();
+        assert!(rows.len() <= resp.rows.len());
+
+        for row in &rows {
+            assert!(resp.rows.iter().any(|r| r.primary_key == row.primary_key));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_hydrate_rows() {
+        test_search_hydrate_rows_impl().await;
+    }
+
     /// 测试搜索的时候使用 Filter 分组
     async fn test_search_with_group_by_filter_impl() {
         setup();
@@ -475,6 +1378,84 @@ mod test_search_index {
         test_search_with_group_by_histogram_impl().await;
     }
 
+    #[test]
+    fn test_search_query_duplicated_names() {
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new()))
+            .aggregation(Aggregation::Avg(AvgAggregation::new("avg_x", "x")))
+            .aggregation(Aggregation::Avg(AvgAggregation::new("avg_x", "y")));
+
+        let err = query.validate().unwrap_err();
+        assert!(matches!(&err, OtsError::ValidationFailed(msg) if msg.contains("avg_x")));
+
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new()))
+            .group_by(GroupBy::Field(GroupByField::new("g", "x", 10)))
+            .group_by(GroupBy::Field(GroupByField::new("g", "y", 10)));
+        assert!(query.validate().is_err());
+
+        // 子聚合中重复的名称也会被检查
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new())).group_by(GroupBy::Field(
+            GroupByField::new("g", "x", 10)
+                .sub_aggregation(Aggregation::Sum(SumAggregation::new("sum_x", "x")))
+                .sub_aggregation(Aggregation::Sum(SumAggregation::new("sum_x", "x"))),
+        ));
+        assert!(query.validate().is_err());
+
+        // 不同层级之间，以及聚合和分组之间可以同名
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new()))
+            .aggregation(Aggregation::Avg(AvgAggregation::new("avg_x", "x")))
+            .group_by(GroupBy::Field(
+                GroupByField::new("avg_x", "x", 10).sub_aggregation(Aggregation::Avg(AvgAggregation::new("avg_x", "x"))),
+            ));
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn test_search_request_columns_to_get() {
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new()));
+
+        let msg = crate::protos::search::SearchRequest::from(SearchRequest::new("users", "users_index", query.clone()).columns_to_get(["full_name"]));
+        let columns = msg.columns_to_get.unwrap();
+        assert_eq!(Some(ColumnReturnType::ReturnSpecified as i32), columns.return_type);
+        assert_eq!(vec!["full_name".to_string()], columns.column_names);
+
+        // 直接设置字段的时候，也使用 `ReturnSpecified`
+        let mut request = SearchRequest::new("users", "users_index", query.clone());
+        request.columns_to_get.insert("gender".to_string());
+        let columns = crate::protos::search::SearchRequest::from(request).columns_to_get.unwrap();
+        assert_eq!(Some(ColumnReturnType::ReturnSpecified as i32), columns.return_type);
+
+        let msg = crate::protos::search::SearchRequest::from(SearchRequest::new("users", "users_index", query).column_return_type(ColumnReturnType::ReturnAll));
+        let columns = msg.columns_to_get.unwrap();
+        assert_eq!(Some(ColumnReturnType::ReturnAll as i32), columns.return_type);
+        assert!(columns.column_names.is_empty());
+    }
+
+    async fn test_search_columns_to_get_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let query = SearchQuery::new(Query::MatchAll(MatchAllQuery::new())).limit(10);
+        let resp = client
+            .search(SearchRequest::new("users", "users_index", query).columns_to_get(["full_name"]))
+            .send()
+            .await
+            .unwrap();
+
+        assert!(!resp.rows.is_empty());
+
+        for row in &resp.rows {
+            // 主键总是会返回，属性列只返回指定的列
+            assert!(row.get_primary_key_value("user_id").is_some());
+            assert!(row.columns.iter().all(|c| c.name == "full_name"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_columns_to_get() {
+        test_search_columns_to_get_impl().await;
+    }
+
     /// 测试常量打分查询
     async fn test_query_const_score_impl() {
         setup();
@@ -638,4 +1619,56 @@ mod test_search_index {
     async fn test_parallel_scan() {
         test_parallel_scan_impl().await;
     }
+
+    async fn test_parallel_scan_all_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let query = Query::Match(MatchQuery::new("full_name", "万宇驰"));
+
+        let search_req = SearchRequest::new(
+            "users",
+            "users_index",
+            SearchQuery::new(query.clone()).sorter(Sorter::PrimaryKey(SortOrder::Asc)).limit(100),
+        );
+        let stream = client.search(search_req).into_row_stream();
+        tokio::pin!(stream);
+
+        let mut expected = 0;
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            assert!(row.is_ok());
+            expected += 1;
+        }
+
+        let stream = client.parallel_scan_all("users", "users_index", ScanQuery::new(query, 1, 0).limit(100), 4);
+        tokio::pin!(stream);
+
+        let mut total_rows = 0;
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            let row = row.unwrap();
+            assert_eq!(Some(&ColumnValue::String("万宇驰".to_string())), row.get_column_value("full_name"));
+            total_rows += 1;
+        }
+
+        log::debug!("total rows: {}", total_rows);
+        assert!(total_rows > 0);
+        assert_eq!(expected, total_rows);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_scan_all() {
+        test_parallel_scan_all_impl().await;
+    }
+
+    #[tokio::test]
+    async fn test_parallel_scan_all_invalid_concurrency() {
+        let client = OtsClient::new("aid", "asec", "https://instance.cn-hangzhou.ots.aliyuncs.com");
+        let stream = client.parallel_scan_all("users", "users_index", ScanQuery::new(Query::MatchAll(MatchAllQuery::new()), 1, 0), 0);
+        tokio::pin!(stream);
+
+        let item = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+        assert!(matches!(item, Some(Err(crate::error::OtsError::ValidationFailed(_)))));
+        assert!(std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await.is_none());
+    }
 }
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use prost::Message;
 
 use super::Query;
+use crate::data::row_stream::{concurrent_row_stream, ChannelRowStream, PagedRowOperation};
 use crate::model::rules::{validate_index_name, validate_table_name};
 use crate::OtsRequestOptions;
 use crate::{
@@ -254,7 +255,9 @@ impl From<ParallelScanRequest> for crate::protos::search::ParallelScanRequest {
     }
 }
 
-/// 并行扫描响应
+/// 并行扫描响应。
+///
+/// 服务端在并行扫描的响应中不返回消耗的服务能力单元，所以这里没有 `consumed` 字段
 #[derive(Debug, Clone)]
 pub struct ParallelScanResponse {
     /// 扫描到的数据行
@@ -319,3 +322,48 @@ impl ParallelScanOperation {
         ParallelScanResponse::try_from(resp_msg)
     }
 }
+
+impl PagedRowOperation for ParallelScanOperation {
+    async fn next_page(self) -> OtsResult<(Vec<Row>, Option<Self>)> {
+        let mut next_op = self.clone();
+        let ParallelScanResponse { rows, next_token } = self.send().await?;
+
+        let next = next_token.filter(|token| !token.is_empty()).map(|token| {
+            next_op.request.scan_query.token = Some(token);
+            next_op
+        });
+
+        Ok((rows, next))
+    }
+}
+
+/// 使用 `ComputeSplits` 获取最大并发数和 sessionId，在后台任务中最多同时读取 `concurrency` 个并发 ID，
+/// 每个并发 ID 都会使用 `next_token` 自动翻页直到读取完毕。
+///
+/// `scan_query` 中的 `max_parallel`、`current_parallel_id` 和 `token` 会被忽略。
+/// 不同并发 ID 的行可能交错返回。出错之后返回这个错误，然后结束
+pub(crate) fn parallel_scan_all(client: OtsClient, table_name: &str, index_name: &str, scan_query: ScanQuery, concurrency: usize) -> ChannelRowStream {
+    let table_name = table_name.to_string();
+    let index_name = index_name.to_string();
+
+    concurrent_row_stream(concurrency, async move {
+        let splits = client.compute_splits(&table_name, &index_name).send().await?;
+
+        Ok((0..splits.splits_size)
+            .map(|parallel_id| {
+                let query = ScanQuery {
+                    max_parallel: splits.splits_size,
+                    current_parallel_id: parallel_id,
+                    token: None,
+                    ..scan_query.clone()
+                };
+
+                let request = ParallelScanRequest::new(&table_name, &index_name, query)
+                    .session_id(splits.session_id.clone())
+                    .column_return_type(ColumnReturnType::ReturnAllFromIndex);
+
+                client.parallel_scan(request)
+            })
+            .collect())
+    })
+}
@@ -1,12 +1,13 @@
 use prost::Message;
 
-use super::{Aggregation, GeoPoint, GroupBy, ScoreFunction, Sort, Sorter};
+use super::{validate_unique_names, Aggregation, GeoPoint, GroupBy, ScoreFunction, Sort, Sorter, EARTH_RADIUS_M};
 use crate::model::rules::validate_column_name;
 use crate::{
     error::OtsError,
     model::ColumnValue,
     protos::search::{
-        Collapse, FunctionCombineMode, FunctionScoreMode, HighlightEncoder, HighlightFragmentOrder, QueryOperator, QueryType, ScoreMode, SearchFilter,
+        Collapse, FieldType, FunctionCombineMode, FunctionScoreMode, HighlightEncoder, HighlightFragmentOrder, IndexSchema, QueryOperator, QueryType,
+        ScoreMode, SearchFilter,
     },
     OtsResult,
 };
@@ -459,6 +460,59 @@ impl From<FunctionsScoreQuery> for crate::protos::search::FunctionsScoreQuery {
     }
 }
 
+/// 使用单个字段的值对文档打分的 Query。
+/// 查询结束后，使用文档中 `field_name` 字段（必须为 `long` 或者 `double` 类型）的值作为分数，并以最终分数排序。
+/// 需要设置因子、打分算法或者多个打分函数的时候，请使用 [`FunctionsScoreQuery`]
+#[derive(Debug, Clone)]
+pub struct FunctionScoreQuery {
+    pub query: Query,
+
+    /// 用于打分的字段名称
+    pub field_name: String,
+}
+
+impl FunctionScoreQuery {
+    pub fn new(query: Query, field_name: &str) -> Self {
+        Self {
+            query,
+            field_name: field_name.to_string(),
+        }
+    }
+
+    pub fn query(mut self, query: Query) -> Self {
+        self.query = query;
+
+        self
+    }
+
+    pub fn field_name(mut self, field_name: impl Into<String>) -> Self {
+        self.field_name = field_name.into();
+
+        self
+    }
+
+    pub(crate) fn validate(&self) -> OtsResult<()> {
+        self.query.validate()?;
+
+        if !validate_column_name(&self.field_name) {
+            return Err(OtsError::ValidationFailed(format!("Invalid field name: {}", self.field_name)));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<FunctionScoreQuery> for crate::protos::search::FunctionScoreQuery {
+    fn from(value: FunctionScoreQuery) -> Self {
+        let FunctionScoreQuery { query, field_name } = value;
+
+        Self {
+            query: Some(crate::protos::search::Query::from(query)),
+            field_value_factor: Some(crate::protos::search::FieldValueFactor { field_name: Some(field_name) }),
+        }
+    }
+}
+
 /// 表示列存在性查询配置。`ExistsQuery` 也叫 NULL 查询或者空值查询，
 /// 一般用于判断稀疏数据中某一行的某一列是否存在。例如查询所有数据中 `address` 列不为空的行。
 #[derive(Debug, Clone, Default)]
@@ -514,6 +568,23 @@ impl GeoBoundingBoxQuery {
         }
     }
 
+    /// 使用中心点和半径（单位为米）构造外接矩形。矩形中心到四条边的距离都等于半径，
+    /// 所以矩形会比圆形多包含四个角的区域，需要精确的圆形范围时请使用 [`GeoDistanceQuery`]。
+    ///
+    /// 纬度限制在 `[-90, 90]` 之内，经度限制在 `[-180, 180]` 之内，不处理跨越 180 度经线的情况
+    pub fn from_center_radius(field_name: &str, center: GeoPoint, radius_m: f64) -> Self {
+        let d_lat = (radius_m / EARTH_RADIUS_M).to_degrees();
+
+        // 沿纬线方向，经度每度对应的距离随纬度变小
+        let d_lng = (d_lat / center.latitude.to_radians().cos()).min(180.0);
+
+        Self::new(
+            field_name,
+            GeoPoint::new((center.latitude + d_lat).min(90.0), (center.longitude - d_lng).max(-180.0)),
+            GeoPoint::new((center.latitude - d_lat).max(-90.0), (center.longitude + d_lng).min(180.0)),
+        )
+    }
+
     pub fn field_name(mut self, field_name: &str) -> Self {
         self.field_name = field_name.to_string();
 
@@ -537,6 +608,16 @@ impl GeoBoundingBoxQuery {
             return Err(OtsError::ValidationFailed(format!("invalid geo bounding box field name: {}", self.field_name)));
         }
 
+        self.top_left.validate()?;
+        self.bottom_right.validate()?;
+
+        if self.top_left.latitude < self.bottom_right.latitude {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid geo bounding box: top left {} is below bottom right {}",
+                self.top_left, self.bottom_right
+            )));
+        }
+
         Ok(())
     }
 }
@@ -595,6 +676,12 @@ impl GeoDistanceQuery {
             return Err(OtsError::ValidationFailed(format!("invalid geo distance field name: {}", self.field_name)));
         }
 
+        self.center.validate()?;
+
+        if !self.distance_in_meter.is_finite() || self.distance_in_meter < 0.0 {
+            return Err(OtsError::ValidationFailed(format!("invalid geo distance: {}", self.distance_in_meter)));
+        }
+
         Ok(())
     }
 }
@@ -650,19 +737,47 @@ impl GeoPolygonQuery {
             return Err(OtsError::ValidationFailed(format!("invalid geo polygon field name: {}", self.field_name)));
         }
 
-        if self.points.len() < 3 {
-            return Err(OtsError::ValidationFailed(format!("invalid geo polygon points: {}", self.points.len())));
+        for p in &self.points {
+            p.validate()?;
+        }
+
+        // 首尾相同的点只算一个顶点
+        let closed = self.is_closed();
+        let vertices = if closed { self.points.len() - 1 } else { self.points.len() };
+
+        if vertices < 3 {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid geo polygon: at least 3 distinct points are required, got {}",
+                vertices
+            )));
         }
 
         Ok(())
     }
+
+    fn is_closed(&self) -> bool {
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => self.points.len() > 1 && first.same_as(last),
+            _ => false,
+        }
+    }
 }
 
 impl From<GeoPolygonQuery> for crate::protos::search::GeoPolygonQuery {
     fn from(value: GeoPolygonQuery) -> Self {
+        let closed = value.is_closed();
+        let GeoPolygonQuery { field_name, mut points } = value;
+
+        // 首尾不同的时候，把第一个点加到最后形成闭合的多边形
+        if !closed {
+            if let Some(first) = points.first().copied() {
+                points.push(first);
+            }
+        }
+
         Self {
-            field_name: Some(value.field_name),
-            points: value.points.into_iter().map(|p| format!("{}", p)).collect(),
+            field_name: Some(field_name),
+            points: points.into_iter().map(|p| format!("{}", p)).collect(),
         }
     }
 }
@@ -784,6 +899,38 @@ impl KnnVectorQuery {
 
         Ok(())
     }
+
+    /// 使用多元索引的结构校验查询，例如 [`DescribeSearchIndexResponse`](`crate::search::DescribeSearchIndexResponse`) 中的 `schema`。
+    /// 除了 [`KnnVectorQuery`] 自身的校验之外，还要求 `field_name` 是索引中的向量字段，并且查询向量的长度和字段的维度一致
+    pub fn validate_with_schema(&self, schema: &IndexSchema) -> OtsResult<()> {
+        self.validate()?;
+
+        let field = match schema.field_schemas.iter().find(|f| f.field_name.as_deref() == Some(self.field_name.as_str())) {
+            Some(f) => f,
+            None => {
+                return Err(OtsError::ValidationFailed(format!(
+                    "knn vector field {} does not exist in index schema",
+                    self.field_name
+                )))
+            }
+        };
+
+        let dimension = match (field.field_type, field.vector_options) {
+            (Some(t), Some(options)) if t == FieldType::Vector as i32 => options.dimension.unwrap_or_default(),
+            _ => return Err(OtsError::ValidationFailed(format!("field {} is not a vector field", self.field_name))),
+        };
+
+        if self.vector.len() != dimension as usize {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid knn vector: field {} has dimension {}, but vector length is {}",
+                self.field_name,
+                dimension,
+                self.vector.len()
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 impl From<KnnVectorQuery> for crate::protos::search::KnnVectorQuery {
@@ -1316,6 +1463,7 @@ pub enum Query {
     MatchPhrase(MatchPhraseQuery),
     Bool(BoolQuery),
     ConstScore(Box<ConstScoreQuery>),
+    FunctionScore(Box<FunctionScoreQuery>),
     FunctionsScore(Box<FunctionsScoreQuery>),
     Exists(ExistsQuery),
     GeoBoundingBox(GeoBoundingBoxQuery),
@@ -1359,6 +1507,11 @@ impl From<Query> for crate::protos::search::Query {
                 query: Some(crate::protos::search::ConstScoreQuery::from(*cq).encode_to_vec()),
             },
 
+            Query::FunctionScore(fq) => Self {
+                r#type: Some(QueryType::FunctionScoreQuery as i32),
+                query: Some(crate::protos::search::FunctionScoreQuery::from(*fq).encode_to_vec()),
+            },
+
             Query::FunctionsScore(fq) => Self {
                 r#type: Some(QueryType::FunctionsScoreQuery as i32),
                 query: Some(crate::protos::search::FunctionsScoreQuery::from(*fq).encode_to_vec()),
@@ -1435,6 +1588,7 @@ impl Query {
             Query::MatchPhrase(mq) => mq.validate(),
             Query::Bool(bq) => bq.validate(),
             Query::ConstScore(cq) => cq.validate(),
+            Query::FunctionScore(fq) => fq.validate(),
             Query::FunctionsScore(fq) => fq.validate(),
             Query::Exists(eq) => eq.validate(),
             Query::GeoBoundingBox(gbq) => gbq.validate(),
@@ -1729,6 +1883,10 @@ pub struct SearchQuery {
     /// 按照指定列对返回结果进行去重。
     ///
     /// 按该列对结果集做折叠，只支持应用于整型、浮点数和 `Keyword` 类型的列，不支持数组类型的列。
+    ///
+    /// 服务端不支持为折叠之后的每一组单独指定排序。每组只返回一行，这一行是按照 `sorters` 排序之后该组中排在最前面的行，
+    /// 例如按照分数倒序排序时返回每组中分数最高的行，按照时间列倒序排序时返回每组中最新的行。
+    /// 排序值相同时，由默认添加的主键排序决定返回哪一行，如果设置了 `disable_default_pk_sorter`，则返回哪一行是不确定的。
     pub collapse_field_name: Option<String>,
 
     /// 返回结果的排序方式。
@@ -1805,7 +1963,7 @@ impl SearchQuery {
         self
     }
 
-    /// 设置对返回结果进行去重的列名
+    /// 设置对返回结果进行去重的列名。每组返回的是按照排序设置排在最前面的行，详见 [`SearchQuery::collapse_field_name`] 字段的说明
     pub fn collapse_field_name(mut self, field_name: impl Into<String>) -> Self {
         self.collapse_field_name = Some(field_name.into());
 
@@ -1904,6 +2062,8 @@ impl SearchQuery {
             s.validate()?;
         }
 
+        validate_unique_names(&self.aggregations, &self.group_bys)?;
+
         for g in &self.group_bys {
             g.validate()?;
         }
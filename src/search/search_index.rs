@@ -2,9 +2,12 @@
 
 use std::collections::{HashMap, HashSet};
 
+use futures_core::Stream;
 use prost::Message;
 
 use super::{AggregationResult, GroupByResult, SearchQuery};
+use crate::data::row_stream::{PagedRowOperation, RowStream};
+use crate::data::{BatchGetRowRequest, TableInBatchGetRowRequest};
 use crate::model::rules::{validate_index_name, validate_table_name};
 use crate::{
     add_per_request_options,
@@ -12,12 +15,17 @@ use crate::{
     model::{PrimaryKey, Row},
     protos::{
         plain_buffer::{MASK_HEADER, MASK_ROW_CHECKSUM},
-        search::{ColumnReturnType, SearchHit},
+        search::ColumnReturnType,
         ConsumedCapacity,
     },
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
 
+/// 一次 `BatchGetRow` 最多可以读取的行数
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/batchgetrow>
+const MAX_ROWS_TO_HYDRATE: usize = 100;
+
 /// 通过多元索引查询数据。
 ///
 /// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/search>
@@ -35,7 +43,7 @@ pub struct SearchRequest {
     /// 路由键的值。默认为空，表示不使用路由键。大部分时候不需要使用此值
     pub routing_values: Vec<PrimaryKey>,
 
-    /// 需要返回的全部列的列名
+    /// 需要返回的全部列的列名。不为空并且没有设置 `column_return_type` 的时候，使用 `ColumnReturnType::ReturnSpecified`
     pub columns_to_get: HashSet<String>,
 
     /// 列返回类型
@@ -79,16 +87,18 @@ impl SearchRequest {
         self
     }
 
-    /// 添加要返回的列名
+    /// 添加要返回的列名，同时设置列返回类型为 `ColumnReturnType::ReturnSpecified`
     pub fn column_to_get(mut self, col: impl Into<String>) -> Self {
         self.columns_to_get.insert(col.into());
+        self.column_return_type = Some(ColumnReturnType::ReturnSpecified);
 
         self
     }
 
-    /// 设置要返回的列名
+    /// 设置要返回的列名，同时设置列返回类型为 `ColumnReturnType::ReturnSpecified`
     pub fn columns_to_get(mut self, cols: impl IntoIterator<Item = impl Into<String>>) -> Self {
         self.columns_to_get = cols.into_iter().map(|col| col.into()).collect();
+        self.column_return_type = Some(ColumnReturnType::ReturnSpecified);
 
         self
     }
@@ -138,7 +148,7 @@ impl From<SearchRequest> for crate::protos::search::SearchRequest {
             index_name: Some(index_name),
             columns_to_get: if !columns_to_get.is_empty() || column_return_type.is_some() {
                 Some(crate::protos::search::ColumnsToGet {
-                    return_type: column_return_type.map(|v| v as i32),
+                    return_type: Some(column_return_type.unwrap_or(ColumnReturnType::ReturnSpecified) as i32),
                     column_names: columns_to_get.into_iter().collect(),
                 })
             } else {
@@ -154,17 +164,111 @@ impl From<SearchRequest> for crate::protos::search::SearchRequest {
     }
 }
 
+/// 嵌套类型字段中命中的子行
+#[derive(Debug, Default, Clone)]
+pub struct NestedSearchHit {
+    /// 子行在父行的嵌套类型字段（JSON 数组）中的位置
+    pub offset: Option<usize>,
+
+    /// 子行的得分
+    pub score: Option<f64>,
+
+    /// 子行的高亮结果。key 是字段名，value 是高亮片段
+    pub highlights: HashMap<String, Vec<String>>,
+
+    /// 更深一层嵌套字段中命中的子行。key 是嵌套字段的路径
+    pub nested: HashMap<String, Vec<NestedSearchHit>>,
+}
+
+/// 一行命中结果，包括数据行、得分、高亮片段和嵌套字段中命中的子行
+#[derive(Debug, Default, Clone)]
+pub struct SearchHit {
+    /// 数据行
+    pub row: Row,
+
+    /// 得分。只有按照得分排序或者进行向量检索时才有值
+    pub score: Option<f64>,
+
+    /// 高亮结果。key 是字段名，value 是高亮片段
+    pub highlights: HashMap<String, Vec<String>>,
+
+    /// 嵌套字段中命中的子行。key 是嵌套字段的路径。
+    /// 嵌套字段的子行没有单独的行数据，需要通过 `offset` 从父行的嵌套字段值中获取
+    pub nested: HashMap<String, Vec<NestedSearchHit>>,
+}
+
+impl SearchHit {
+    /// 获取嵌套字段中命中的子行，顺序和服务端返回的顺序相同。
+    /// 返回的子行受 [`InnerHits`](`crate::search::InnerHits`) 中的 `offset` 和 `limit` 限制，没有命中的时候返回空列表
+    pub fn nested_hits(&self, path: &str) -> &[NestedSearchHit] {
+        self.nested.get(path).map(|hits| hits.as_slice()).unwrap_or_default()
+    }
+}
+
+/// 把高亮结果转换为字段名到高亮片段的映射
+fn highlights_from(highlight_result: Option<crate::protos::search::HighlightResult>) -> HashMap<String, Vec<String>> {
+    highlight_result
+        .map(|h| h.highlight_fields)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|f| f.field_name.map(|name| (name, f.field_fragments)))
+        .collect()
+}
+
+/// 把内部命中结果转换为嵌套字段路径到子行的映射
+fn nested_from(inner_hits: Vec<crate::protos::search::SearchInnerHit>) -> HashMap<String, Vec<NestedSearchHit>> {
+    let mut nested = HashMap::<String, Vec<NestedSearchHit>>::new();
+
+    for inner_hit in inner_hits {
+        let path = inner_hit.path.unwrap_or_default();
+
+        nested.entry(path).or_default().extend(inner_hit.search_hits.into_iter().map(|h| {
+            let crate::protos::search::SearchHit {
+                score,
+                highlight_result,
+                search_inner_hits,
+                nested_doc_offset,
+            } = h;
+
+            NestedSearchHit {
+                offset: nested_doc_offset.and_then(|n| usize::try_from(n).ok()),
+                score,
+                highlights: highlights_from(highlight_result),
+                nested: nested_from(search_inner_hits),
+            }
+        }));
+    }
+
+    nested
+}
+
 /// 通过多元索引查询数据响应结构
 #[derive(Debug, Default, Clone)]
 pub struct SearchResponse {
-    /// 命中的总行数
+    /// 命中的总行数。没有开启 `track_total_count` 的时候，服务端不统计总行数，这个值为 `0`
     pub total_hits: u64,
 
-    /// 数据行
+    /// 服务端返回的匹配总行数。服务端没有返回总行数的时候为 `None`
+    pub total_count: Option<i64>,
+
+    /// `total_count` 是否为精确的匹配总行数。只有在查询中开启 `track_total_count` 的时候才是精确值，
+    /// 否则只能作为“约 N 条结果”展示
+    pub is_total_count_exact: bool,
+
+    /// 数据行，和 `hits` 中每一个命中结果的 `row` 相同
     pub rows: Vec<Row>,
 
-    /// 返回的命中结果。当使用查询摘要与高亮功能或向量检索进行查询时才有返回值。
-    pub search_hits: Vec<SearchHit>,
+    /// 返回的原始命中结果。当使用查询摘要与高亮功能或向量检索进行查询时才有返回值。
+    ///
+    /// 和服务端返回的行按位置对应，其中包括被跳过的空行，所以不能直接和 `rows` 对齐。
+    /// 这些信息都已经解析到 `hits` 中
+    #[deprecated(note = "use `hits` instead, which keeps the row, score, highlights and nested hits together")]
+    pub search_hits: Vec<crate::protos::search::SearchHit>,
+
+    /// 命中结果。每一行对应的得分、高亮片段和嵌套字段中命中的子行都在这里，
+    /// 嵌套字段中命中的子行统一使用 [`NestedSearchHit`] 表示。
+    /// `rows` 是其中数据行的列表，顺序和这里相同，只是为了兼容以前的版本而保留
+    pub hits: Vec<SearchHit>,
 
     /// 是否全部成功。
     pub is_all_succeeded: bool,
@@ -195,12 +299,49 @@ impl SearchResponse {
     pub fn get_group_by_result(&self, group_by_name: impl AsRef<str>) -> Option<&GroupByResult> {
         self.group_by_results.get(group_by_name.as_ref())
     }
+
+    /// 使用返回行的主键，通过 `BatchGetRow` 从数据表中读取完整的行数据（每列只读取最新版本）。
+    ///
+    /// 适用于使用 `ColumnReturnType::ReturnAllFromIndex` 或者只返回主键的查询。
+    /// 返回的行和 `rows` 的顺序相同，在数据表中已经不存在的行（例如建立索引之后被删除）会被跳过。
+    /// 每次最多读取 100 行，任意一行读取失败都会返回错误。
+    pub async fn hydrate_rows(&self, client: &OtsClient, table_name: &str) -> OtsResult<Vec<Row>> {
+        let mut rows = vec![];
+
+        for chunk in self.rows.chunks(MAX_ROWS_TO_HYDRATE) {
+            let table = TableInBatchGetRowRequest::new(table_name).primary_keys(chunk.iter().map(|r| r.primary_key.clone()));
+
+            let resp = client.batch_get_row(BatchGetRowRequest::new().table(table)).send().await?;
+
+            for t in resp.tables {
+                for r in t.rows {
+                    if !r.is_ok {
+                        return Err(OtsError::from_row_error(r.error, format_args!("get row from table {}", t.table_name)));
+                    }
+
+                    // 行不存在的时候返回的行数据为空
+                    if let Some(row) = r.row {
+                        rows.push(row);
+                    }
+                }
+            }
+        }
+
+        Ok(rows)
+    }
 }
 
 impl TryFrom<crate::protos::search::SearchResponse> for SearchResponse {
     type Error = OtsError;
 
     fn try_from(value: crate::protos::search::SearchResponse) -> Result<Self, Self::Error> {
+        Self::try_from_checked(value, false)
+    }
+}
+
+impl SearchResponse {
+    /// 转换响应。`verify_checksum` 为 `true` 的时候在解码行的同时校验行校验码
+    pub(crate) fn try_from_checked(value: crate::protos::search::SearchResponse, verify_checksum: bool) -> OtsResult<Self> {
         let crate::protos::search::SearchResponse {
             total_hits,
             rows: rows_bytes,
@@ -214,10 +355,31 @@ impl TryFrom<crate::protos::search::SearchResponse> for SearchResponse {
         } = value;
 
         let mut rows = vec![];
-        for row_bytes in rows_bytes {
-            if !row_bytes.is_empty() {
-                rows.push(Row::decode_plain_buffer(row_bytes, MASK_HEADER)?);
+        let mut hits = vec![];
+
+        // `search_hits` 和 `rows_bytes` 按位置对应，要在跳过空行之前对齐
+        for (i, row_bytes) in rows_bytes.into_iter().enumerate() {
+            if row_bytes.is_empty() {
+                continue;
             }
+
+            let row = Row::decode_plain_buffer_checked(row_bytes, MASK_HEADER, verify_checksum)?;
+
+            let hit = match search_hits.get(i).cloned() {
+                Some(h) => SearchHit {
+                    row: row.clone(),
+                    score: h.score,
+                    highlights: highlights_from(h.highlight_result),
+                    nested: nested_from(h.search_inner_hits),
+                },
+                None => SearchHit {
+                    row: row.clone(),
+                    ..Default::default()
+                },
+            };
+
+            rows.push(row);
+            hits.push(hit);
         }
 
         let aggregation_results = if let Some(bytes) = aggs_bytes {
@@ -234,11 +396,18 @@ impl TryFrom<crate::protos::search::SearchResponse> for SearchResponse {
             HashMap::new()
         };
 
+        // 不统计总行数时服务端返回 -1
+        let total_count = total_hits.filter(|n| *n >= 0);
+
+        #[allow(deprecated)]
         Ok(Self {
-            total_hits: total_hits.map_or(0, |n| n as u64),
+            total_hits: total_count.map_or(0, |n| n as u64),
+            total_count,
+            is_total_count_exact: false,
             rows,
             is_all_succeeded: is_all_succeeded.unwrap_or(true),
             search_hits,
+            hits,
             next_token,
             aggregation_results,
             group_by_results,
@@ -272,6 +441,7 @@ impl SearchOperation {
 
         let Self { client, request, options } = self;
 
+        let track_total_count = request.search_query.track_total_count;
         let msg = crate::protos::search::SearchRequest::from(request);
 
         let req = OtsRequest {
@@ -285,6 +455,45 @@ impl SearchOperation {
 
         let resp_msg = crate::protos::search::SearchResponse::decode(resp.bytes().await?)?;
 
-        SearchResponse::try_from(resp_msg)
+        let mut response = SearchResponse::try_from_checked(resp_msg, client.options.verify_checksums)?;
+        response.is_total_count_exact = track_total_count && response.total_count.is_some();
+
+        Ok(response)
+    }
+
+    /// 转换为自动翻页的行读取流。
+    ///
+    /// 在内部使用响应中的 `next_token` 设置查询的游标继续发送请求，直到服务端不再返回 `next_token` 为止。
+    /// 出错之后返回这个错误，然后结束。
+    ///
+    /// 流中只有数据行。统计聚合和分组的结果只在第一页的响应中返回，需要这些结果的时候请使用 [`SearchOperation::send`]
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let stream = client.search(request).into_row_stream();
+    /// tokio::pin!(stream);
+    ///
+    /// while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+    ///     let row = row?;
+    ///     println!("{:?}", row);
+    /// }
+    /// ```
+    pub fn into_row_stream(self) -> impl Stream<Item = OtsResult<Row>> {
+        RowStream::new(self)
+    }
+}
+
+impl PagedRowOperation for SearchOperation {
+    async fn next_page(self) -> OtsResult<(Vec<Row>, Option<Self>)> {
+        let mut next_op = self.clone();
+        let SearchResponse { rows, next_token, .. } = self.send().await?;
+
+        let next = next_token.filter(|token| !token.is_empty()).map(|token| {
+            next_op.request.search_query.token = token;
+            next_op
+        });
+
+        Ok((rows, next))
     }
 }
@@ -1,6 +1,16 @@
 use prost::Message;
 
-use crate::{add_per_request_options, error::OtsError, protos::search::UpdateSearchIndexRequest, OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult};
+use crate::{
+    add_per_request_options,
+    error::OtsError,
+    protos::search::{QueryFlowWeight, UpdateSearchIndexRequest},
+    OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
+};
+
+/// 流量权重的总和
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/updatesearchindex>
+const TOTAL_QUERY_FLOW_WEIGHT: i32 = 100;
 
 /// 接口更新多元索引的配置，包括数据生命周期（TTL）和多元索引 schema。
 ///
@@ -20,7 +30,28 @@ pub struct UpdateSearchIndexOperation {
 add_per_request_options!(UpdateSearchIndexOperation);
 
 impl UpdateSearchIndexRequest {
-    fn validate(&self) -> OtsResult<()> {
+    pub fn new(table_name: &str, index_name: &str) -> Self {
+        Self {
+            table_name: Some(table_name.to_string()),
+            index_name: Some(index_name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// 设置一个多元索引的查询流量权重，取值范围为 `[0, 100]`。
+    ///
+    /// 修改多元索引 schema 时，服务端会为原索引创建一个同源的新索引，可以通过权重把一部分查询流量分配到新索引上，逐步切换流量。
+    /// 需要同时设置原索引和新索引的权重，两者的和必须为 `100`。当前的权重可以通过 `DescribeSearchIndex` 查询
+    pub fn query_flow_weight(mut self, index_name: &str, weight: i32) -> Self {
+        self.query_flow_weight.push(QueryFlowWeight {
+            index_name: Some(index_name.to_string()),
+            weight: Some(weight),
+        });
+
+        self
+    }
+
+    pub(crate) fn validate(&self) -> OtsResult<()> {
         if self.table_name.is_none() || self.table_name.as_ref().unwrap().is_empty() {
             return Err(OtsError::ValidationFailed("table name must not be empty".to_string()));
         }
@@ -29,10 +60,60 @@ impl UpdateSearchIndexRequest {
             return Err(OtsError::ValidationFailed("index name must not be empty".to_string()));
         }
 
+        if !self.query_flow_weight.is_empty() {
+            validate_query_flow_weights(&self.query_flow_weight)?;
+        }
+
         Ok(())
     }
 }
 
+/// 校验流量权重：最多两个不同的索引，每个权重在 `[0, 100]` 之间，并且总和为 `100`
+fn validate_query_flow_weights(weights: &[QueryFlowWeight]) -> OtsResult<()> {
+    if weights.len() > 2 {
+        return Err(OtsError::ValidationFailed(format!(
+            "invalid query flow weights: at most 2 indexes are allowed, got {}",
+            weights.len()
+        )));
+    }
+
+    let mut sum = 0;
+
+    for (i, w) in weights.iter().enumerate() {
+        let name = w.index_name.as_deref().unwrap_or_default();
+
+        if name.is_empty() {
+            return Err(OtsError::ValidationFailed(
+                "invalid query flow weight: index name must not be empty".to_string(),
+            ));
+        }
+
+        if weights[..i].iter().any(|other| other.index_name == w.index_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid query flow weight: duplicated index name {}", name)));
+        }
+
+        let weight = w.weight.unwrap_or_default();
+
+        if !(0..=TOTAL_QUERY_FLOW_WEIGHT).contains(&weight) {
+            return Err(OtsError::ValidationFailed(format!(
+                "invalid query flow weight for index {}: {}, must be in [0, {}]",
+                name, weight, TOTAL_QUERY_FLOW_WEIGHT
+            )));
+        }
+
+        sum += weight;
+    }
+
+    if sum != TOTAL_QUERY_FLOW_WEIGHT {
+        return Err(OtsError::ValidationFailed(format!(
+            "invalid query flow weights: sum of weights is {}, must be {}",
+            sum, TOTAL_QUERY_FLOW_WEIGHT
+        )));
+    }
+
+    Ok(())
+}
+
 impl UpdateSearchIndexOperation {
     pub(crate) fn new(client: OtsClient, request: UpdateSearchIndexRequest) -> Self {
         Self {
@@ -0,0 +1,214 @@
+use prost::Message;
+
+use crate::{add_per_request_options, error::OtsError, protos::StreamStatus, OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult};
+
+/// 获取 Stream 详细信息的请求
+#[derive(Debug, Default, Clone)]
+pub struct DescribeStreamRequest {
+    /// Stream ID
+    pub stream_id: String,
+
+    /// 从这个分区开始返回分区信息（包含这个分区）
+    pub inclusive_start_shard_id: Option<String>,
+
+    /// 最多返回的分区数量
+    pub shard_limit: Option<u32>,
+}
+
+impl DescribeStreamRequest {
+    pub fn new(stream_id: &str) -> Self {
+        Self {
+            stream_id: stream_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// 设置起始分区 ID
+    pub fn inclusive_start_shard_id(mut self, shard_id: &str) -> Self {
+        self.inclusive_start_shard_id = Some(shard_id.to_string());
+
+        self
+    }
+
+    /// 设置最多返回的分区数量
+    pub fn shard_limit(mut self, limit: u32) -> Self {
+        self.shard_limit = Some(limit);
+
+        self
+    }
+
+    fn validate(&self) -> OtsResult<()> {
+        if self.stream_id.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid stream id: can not be empty".to_string()));
+        }
+
+        if let Some(n) = self.shard_limit {
+            if n == 0 || n > i32::MAX as u32 {
+                return Err(OtsError::ValidationFailed(format!("invalid shard limit: {}", n)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<DescribeStreamRequest> for crate::protos::DescribeStreamRequest {
+    fn from(value: DescribeStreamRequest) -> Self {
+        let DescribeStreamRequest {
+            stream_id,
+            inclusive_start_shard_id,
+            shard_limit,
+        } = value;
+
+        crate::protos::DescribeStreamRequest {
+            stream_id,
+            inclusive_start_shard_id,
+            shard_limit: shard_limit.map(|n| n as i32),
+            support_timeseries_data_table: None,
+        }
+    }
+}
+
+/// Stream 的分区。
+///
+/// 分区会分裂和合并，读取一个分区之前需要先读取完它的父分区 `parent_id` 和父分区的兄弟分区 `parent_sibling_id`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StreamShard {
+    /// 分区 ID
+    pub shard_id: String,
+
+    /// 父分区 ID
+    pub parent_id: Option<String>,
+
+    /// 父分区的兄弟分区 ID。两个分区合并的时候才有值
+    pub parent_sibling_id: Option<String>,
+}
+
+impl From<crate::protos::StreamShard> for StreamShard {
+    fn from(value: crate::protos::StreamShard) -> Self {
+        let crate::protos::StreamShard {
+            shard_id,
+            parent_id,
+            parent_sibling_id,
+        } = value;
+
+        Self {
+            shard_id,
+            parent_id,
+            parent_sibling_id,
+        }
+    }
+}
+
+/// Stream 详细信息
+#[derive(Debug, Clone)]
+pub struct StreamDetails {
+    /// Stream ID
+    pub stream_id: String,
+
+    /// 表名
+    pub table_name: String,
+
+    /// Stream 的创建时间。单位为微秒
+    pub creation_time: i64,
+
+    /// Stream 的状态
+    pub status: StreamStatus,
+
+    /// Stream 数据的过期时间。单位为小时
+    pub expiration_time: i32,
+
+    /// 分区信息。Stream 处于 `StreamActive` 状态但还没有分区的时候为空
+    pub shards: Vec<StreamShard>,
+
+    /// 分区没有返回完的时候，下一次请求的起始分区 ID
+    pub next_shard_id: Option<String>,
+}
+
+impl TryFrom<crate::protos::DescribeStreamResponse> for StreamDetails {
+    type Error = OtsError;
+
+    fn try_from(value: crate::protos::DescribeStreamResponse) -> Result<Self, Self::Error> {
+        let crate::protos::DescribeStreamResponse {
+            stream_id,
+            expiration_time,
+            table_name,
+            creation_time,
+            stream_status,
+            shards,
+            next_shard_id,
+            is_timeseries_data_table: _,
+        } = value;
+
+        let status = match StreamStatus::try_from(stream_status) {
+            Ok(s) => s,
+            Err(_) => return Err(OtsError::ValidationFailed(format!("invalid stream status: {}", stream_status))),
+        };
+
+        Ok(Self {
+            stream_id,
+            table_name,
+            creation_time,
+            status,
+            expiration_time,
+            shards: shards.into_iter().map(StreamShard::from).collect(),
+            next_shard_id,
+        })
+    }
+}
+
+/// 获取 Stream 的详细信息，包括分区信息。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/describestream>
+#[derive(Clone)]
+pub struct DescribeStreamOperation {
+    client: OtsClient,
+    request: DescribeStreamRequest,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(DescribeStreamOperation);
+
+impl DescribeStreamOperation {
+    pub(crate) fn new(client: OtsClient, stream_id: &str) -> Self {
+        Self {
+            client,
+            request: DescribeStreamRequest::new(stream_id),
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    /// 设置起始分区 ID
+    pub fn inclusive_start_shard_id(mut self, shard_id: &str) -> Self {
+        self.request = self.request.inclusive_start_shard_id(shard_id);
+
+        self
+    }
+
+    /// 设置最多返回的分区数量
+    pub fn shard_limit(mut self, limit: u32) -> Self {
+        self.request = self.request.shard_limit(limit);
+
+        self
+    }
+
+    pub async fn send(self) -> OtsResult<StreamDetails> {
+        self.request.validate()?;
+
+        let Self { client, request, options } = self;
+
+        let msg = crate::protos::DescribeStreamRequest::from(request);
+
+        let req = OtsRequest {
+            operation: OtsOp::DescribeStream,
+            body: msg.encode_to_vec(),
+            options,
+            ..Default::default()
+        };
+
+        let response = client.send(req).await?;
+        let msg = crate::protos::DescribeStreamResponse::decode(response.bytes().await?)?;
+
+        StreamDetails::try_from(msg)
+    }
+}
@@ -0,0 +1,147 @@
+use prost::Message;
+
+use crate::{add_per_request_options, error::OtsError, OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult};
+
+/// 获取分区迭代器的请求
+#[derive(Debug, Default, Clone)]
+pub struct GetShardIteratorRequest {
+    /// Stream ID
+    pub stream_id: String,
+
+    /// 分区 ID
+    pub shard_id: String,
+
+    /// 从这个时间点开始读取数据。单位为毫秒
+    pub timestamp: Option<i64>,
+
+    /// 上一次获取分区迭代器时返回的 token，用于继续获取
+    pub token: Option<String>,
+}
+
+impl GetShardIteratorRequest {
+    pub fn new(stream_id: &str, shard_id: &str) -> Self {
+        Self {
+            stream_id: stream_id.to_string(),
+            shard_id: shard_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// 设置开始读取数据的时间点。单位为毫秒
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+
+        self
+    }
+
+    /// 设置 token
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+
+        self
+    }
+
+    fn validate(&self) -> OtsResult<()> {
+        if self.stream_id.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid stream id: can not be empty".to_string()));
+        }
+
+        if self.shard_id.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid shard id: can not be empty".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<GetShardIteratorRequest> for crate::protos::GetShardIteratorRequest {
+    fn from(value: GetShardIteratorRequest) -> Self {
+        let GetShardIteratorRequest {
+            stream_id,
+            shard_id,
+            timestamp,
+            token,
+        } = value;
+
+        crate::protos::GetShardIteratorRequest {
+            stream_id,
+            shard_id,
+            timestamp,
+            token,
+        }
+    }
+}
+
+/// 获取分区迭代器的响应
+#[derive(Debug, Default, Clone)]
+pub struct GetShardIteratorResponse {
+    /// 分区迭代器，用于读取分区中的数据
+    pub shard_iterator: String,
+
+    /// 用于继续获取分区迭代器的 token
+    pub next_token: Option<String>,
+}
+
+impl From<crate::protos::GetShardIteratorResponse> for GetShardIteratorResponse {
+    fn from(value: crate::protos::GetShardIteratorResponse) -> Self {
+        let crate::protos::GetShardIteratorResponse { shard_iterator, next_token } = value;
+
+        Self { shard_iterator, next_token }
+    }
+}
+
+/// 获取分区迭代器。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/getsharditerator>
+#[derive(Clone)]
+pub struct GetShardIteratorOperation {
+    client: OtsClient,
+    request: GetShardIteratorRequest,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(GetShardIteratorOperation);
+
+impl GetShardIteratorOperation {
+    pub(crate) fn new(client: OtsClient, stream_id: &str, shard_id: &str) -> Self {
+        Self {
+            client,
+            request: GetShardIteratorRequest::new(stream_id, shard_id),
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    /// 设置开始读取数据的时间点。单位为毫秒
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.request = self.request.timestamp(timestamp);
+
+        self
+    }
+
+    /// 设置 token
+    pub fn token(mut self, token: &str) -> Self {
+        self.request = self.request.token(token);
+
+        self
+    }
+
+    pub async fn send(self) -> OtsResult<GetShardIteratorResponse> {
+        self.request.validate()?;
+
+        let Self { client, request, options } = self;
+
+        let msg = crate::protos::GetShardIteratorRequest::from(request);
+
+        let req = OtsRequest {
+            operation: OtsOp::GetShardIterator,
+            body: msg.encode_to_vec(),
+            options,
+            ..Default::default()
+        };
+
+        let response = client.send(req).await?;
+        let msg = crate::protos::GetShardIteratorResponse::decode(response.bytes().await?)?;
+
+        Ok(GetShardIteratorResponse::from(msg))
+    }
+}
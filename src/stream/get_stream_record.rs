@@ -0,0 +1,198 @@
+use prost::Message;
+
+use crate::{
+    add_per_request_options,
+    error::OtsError,
+    model::Row,
+    protos::{plain_buffer::MASK_HEADER, ActionType, ConsumedCapacity},
+    OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
+};
+
+/// 读取 Stream 记录的请求
+#[derive(Debug, Default, Clone)]
+pub struct GetStreamRecordRequest {
+    /// 分区迭代器
+    pub shard_iterator: String,
+
+    /// 最多返回的记录数
+    pub limit: Option<u32>,
+}
+
+impl GetStreamRecordRequest {
+    pub fn new(shard_iterator: &str) -> Self {
+        Self {
+            shard_iterator: shard_iterator.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// 设置最多返回的记录数
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    fn validate(&self) -> OtsResult<()> {
+        if self.shard_iterator.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid shard iterator: can not be empty".to_string()));
+        }
+
+        if let Some(n) = self.limit {
+            if n == 0 || n > i32::MAX as u32 {
+                return Err(OtsError::ValidationFailed(format!("invalid limit: {}", n)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<GetStreamRecordRequest> for crate::protos::GetStreamRecordRequest {
+    fn from(value: GetStreamRecordRequest) -> Self {
+        let GetStreamRecordRequest { shard_iterator, limit } = value;
+
+        crate::protos::GetStreamRecordRequest {
+            shard_iterator,
+            limit: limit.map(|n| n as i32),
+            table_name: None,
+        }
+    }
+}
+
+/// 一条 Stream 记录
+#[derive(Debug, Clone)]
+pub enum StreamRecord {
+    /// 写入行。行中包含写入的全部数据列
+    Put(Row),
+
+    /// 更新行。数据列的 `op` 表示对这一列的操作，为 `None` 的时候表示写入这一列
+    Update(Row),
+
+    /// 删除行。只包含主键
+    Delete(Row),
+}
+
+impl StreamRecord {
+    /// 记录对应的行
+    pub fn row(&self) -> &Row {
+        match self {
+            Self::Put(row) | Self::Update(row) | Self::Delete(row) => row,
+        }
+    }
+
+    /// 取出记录对应的行
+    pub fn into_row(self) -> Row {
+        match self {
+            Self::Put(row) | Self::Update(row) | Self::Delete(row) => row,
+        }
+    }
+
+    /// 记录的操作类型
+    pub fn action_type(&self) -> ActionType {
+        match self {
+            Self::Put(_) => ActionType::PutRow,
+            Self::Update(_) => ActionType::UpdateRow,
+            Self::Delete(_) => ActionType::DeleteRow,
+        }
+    }
+}
+
+impl TryFrom<crate::protos::get_stream_record_response::StreamRecord> for StreamRecord {
+    type Error = OtsError;
+
+    fn try_from(value: crate::protos::get_stream_record_response::StreamRecord) -> Result<Self, Self::Error> {
+        let crate::protos::get_stream_record_response::StreamRecord {
+            action_type,
+            record,
+            origin_record: _,
+        } = value;
+
+        let action_type = match ActionType::try_from(action_type) {
+            Ok(t) => t,
+            Err(_) => return Err(OtsError::ValidationFailed(format!("invalid stream record action type: {}", action_type))),
+        };
+
+        let row = Row::decode_plain_buffer(record, MASK_HEADER)?;
+
+        Ok(match action_type {
+            ActionType::PutRow => Self::Put(row),
+            ActionType::UpdateRow => Self::Update(row),
+            ActionType::DeleteRow => Self::Delete(row),
+        })
+    }
+}
+
+/// 读取 Stream 记录的响应
+#[derive(Debug, Clone, Default)]
+pub struct GetStreamRecordResponse {
+    /// 记录
+    pub records: Vec<StreamRecord>,
+
+    /// 下一次读取使用的分区迭代器。为 `None` 的时候表示分区已经读取完毕（分区已经分裂或者合并）
+    pub next_shard_iterator: Option<String>,
+
+    /// 本次操作消耗的服务能力单元
+    pub consumed: ConsumedCapacity,
+}
+
+impl TryFrom<crate::protos::GetStreamRecordResponse> for GetStreamRecordResponse {
+    type Error = OtsError;
+
+    fn try_from(value: crate::protos::GetStreamRecordResponse) -> Result<Self, Self::Error> {
+        let crate::protos::GetStreamRecordResponse {
+            stream_records,
+            next_shard_iterator,
+            consumed,
+            may_more_record: _,
+        } = value;
+
+        Ok(Self {
+            records: stream_records.into_iter().map(StreamRecord::try_from).collect::<OtsResult<Vec<_>>>()?,
+            next_shard_iterator,
+            consumed: consumed.unwrap_or_default(),
+        })
+    }
+}
+
+/// 读取 Stream 记录。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/getstreamrecord>
+#[derive(Clone)]
+pub struct GetStreamRecordOperation {
+    client: OtsClient,
+    request: GetStreamRecordRequest,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(GetStreamRecordOperation);
+
+impl GetStreamRecordOperation {
+    pub(crate) fn new(client: OtsClient, request: GetStreamRecordRequest) -> Self {
+        Self {
+            client,
+            request,
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    pub async fn send(self) -> OtsResult<GetStreamRecordResponse> {
+        self.request.validate()?;
+
+        let Self { client, request, options } = self;
+
+        let msg = crate::protos::GetStreamRecordRequest::from(request);
+
+        let req = OtsRequest {
+            operation: OtsOp::GetStreamRecord,
+            body: msg.encode_to_vec(),
+            options,
+            ..Default::default()
+        };
+
+        let response = client.send(req).await?;
+        let msg = crate::protos::GetStreamRecordResponse::decode(response.bytes().await?)?;
+
+        GetStreamRecordResponse::try_from(msg)
+    }
+}
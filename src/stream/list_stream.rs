@@ -0,0 +1,110 @@
+use prost::Message;
+
+use crate::{add_per_request_options, error::OtsError, model::rules::validate_table_name, OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult};
+
+/// 列出实例下的 Stream 的请求
+#[derive(Debug, Default, Clone)]
+pub struct ListStreamRequest {
+    /// 表名。为空的时候列出实例下所有表的 Stream
+    pub table_name: Option<String>,
+}
+
+impl ListStreamRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置表名，只列出这个表的 Stream
+    pub fn table_name(mut self, table_name: &str) -> Self {
+        self.table_name = Some(table_name.to_string());
+
+        self
+    }
+
+    fn validate(&self) -> OtsResult<()> {
+        if let Some(table_name) = &self.table_name {
+            if !validate_table_name(table_name) {
+                return Err(OtsError::ValidationFailed(format!("invalid table name: {}", table_name)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ListStreamRequest> for crate::protos::ListStreamRequest {
+    fn from(value: ListStreamRequest) -> Self {
+        crate::protos::ListStreamRequest { table_name: value.table_name }
+    }
+}
+
+/// Stream 信息
+#[derive(Debug, Default, Clone)]
+pub struct StreamInfo {
+    /// Stream ID
+    pub stream_id: String,
+
+    /// 表名
+    pub table_name: String,
+
+    /// Stream 的创建时间。单位为微秒
+    pub creation_time: i64,
+}
+
+impl From<crate::protos::Stream> for StreamInfo {
+    fn from(value: crate::protos::Stream) -> Self {
+        let crate::protos::Stream {
+            stream_id,
+            table_name,
+            creation_time,
+        } = value;
+
+        Self {
+            stream_id,
+            table_name,
+            creation_time,
+        }
+    }
+}
+
+/// 列出实例下的 Stream。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/liststream>
+#[derive(Clone)]
+pub struct ListStreamOperation {
+    client: OtsClient,
+    request: ListStreamRequest,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(ListStreamOperation);
+
+impl ListStreamOperation {
+    pub(crate) fn new(client: OtsClient, request: ListStreamRequest) -> Self {
+        Self {
+            client,
+            request,
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    pub async fn send(self) -> OtsResult<Vec<StreamInfo>> {
+        self.request.validate()?;
+
+        let Self { client, request, options } = self;
+
+        let msg = crate::protos::ListStreamRequest::from(request);
+
+        let req = OtsRequest {
+            operation: OtsOp::ListStream,
+            body: msg.encode_to_vec(),
+            options,
+            ..Default::default()
+        };
+
+        let response = client.send(req).await?;
+        let msg = crate::protos::ListStreamResponse::decode(response.bytes().await?)?;
+
+        Ok(msg.streams.into_iter().map(StreamInfo::from).collect())
+    }
+}
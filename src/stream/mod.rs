@@ -0,0 +1,243 @@
+//! 数据变更记录（Stream）模块
+
+mod describe_stream;
+mod get_shard_iterator;
+mod get_stream_record;
+mod list_stream;
+mod stream_reader;
+
+pub use describe_stream::*;
+pub use get_shard_iterator::*;
+pub use get_stream_record::*;
+pub use list_stream::*;
+pub use stream_reader::*;
+
+#[cfg(test)]
+mod test_stream {
+    use crate::{
+        data::PutRowRequest,
+        model::{ColumnOp, Row},
+        protos::{
+            get_stream_record_response, plain_buffer::MASK_HEADER, ActionType, DescribeStreamResponse, GetStreamRecordResponse, StreamShard, StreamStatus,
+        },
+        test_util::setup,
+        OtsClient,
+    };
+
+    use super::{stream_reader::next_poll_interval_ms, StreamDetails, StreamRecord};
+
+    #[tokio::test]
+    async fn test_list_stream() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let resp = client.list_stream(Some("data_types")).send().await;
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+
+        let streams = resp.unwrap();
+        assert!(!streams.is_empty());
+        assert!(streams.iter().all(|s| s.table_name == "data_types"));
+    }
+
+    async fn test_describe_stream_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let streams = client.list_stream(Some("data_types")).send().await.unwrap();
+        assert!(!streams.is_empty());
+
+        let resp = client.describe_stream(&streams[0].stream_id).send().await;
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+
+        let details = resp.unwrap();
+        assert_eq!(streams[0].stream_id, details.stream_id);
+        assert_eq!("data_types", details.table_name);
+    }
+
+    #[tokio::test]
+    async fn test_describe_stream() {
+        test_describe_stream_impl().await
+    }
+
+    #[test]
+    fn test_stream_details_from_response() {
+        let msg = DescribeStreamResponse {
+            stream_id: "stream-1".to_string(),
+            expiration_time: 24,
+            table_name: "data_types".to_string(),
+            creation_time: 1,
+            stream_status: StreamStatus::StreamActive as i32,
+            shards: vec![],
+            next_shard_id: None,
+            is_timeseries_data_table: None,
+        };
+
+        // 没有分区的 Stream 返回空的分区列表
+        let details = StreamDetails::try_from(msg.clone()).unwrap();
+        assert_eq!(StreamStatus::StreamActive, details.status);
+        assert!(details.shards.is_empty());
+
+        let details = StreamDetails::try_from(DescribeStreamResponse {
+            shards: vec![StreamShard {
+                shard_id: "s2".to_string(),
+                parent_id: Some("s0".to_string()),
+                parent_sibling_id: Some("s1".to_string()),
+            }],
+            ..msg.clone()
+        })
+        .unwrap();
+        assert_eq!(1, details.shards.len());
+        assert_eq!("s2", details.shards[0].shard_id);
+        assert_eq!(Some("s0".to_string()), details.shards[0].parent_id);
+        assert_eq!(Some("s1".to_string()), details.shards[0].parent_sibling_id);
+
+        assert!(StreamDetails::try_from(DescribeStreamResponse { stream_status: 100, ..msg }).is_err());
+    }
+
+    async fn test_get_stream_record_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let row = Row::new()
+            .primary_key_column_string("str_id", "stream-record-test")
+            .column_string("str_col", "stream");
+        client.put_row(PutRowRequest::new("data_types").row(row)).send().await.unwrap();
+
+        let streams = client.list_stream(Some("data_types")).send().await.unwrap();
+        let details = client.describe_stream(&streams[0].stream_id).send().await.unwrap();
+        assert!(!details.shards.is_empty());
+
+        let shard = &details.shards[0];
+        let mut shard_iterator = client
+            .get_shard_iterator(&details.stream_id, &shard.shard_id)
+            .send()
+            .await
+            .unwrap()
+            .shard_iterator;
+
+        // 只读取几批，避免分区数据太多的时候测试时间过长
+        for _ in 0..10 {
+            let resp = client.get_stream_record(&shard_iterator, Some(100)).send().await;
+            log::debug!("{:#?}", resp);
+            assert!(resp.is_ok());
+
+            let resp = resp.unwrap();
+            for record in &resp.records {
+                assert!(!record.row().primary_key.columns.is_empty());
+
+                match record {
+                    StreamRecord::Delete(row) => assert!(row.deleted),
+                    StreamRecord::Put(row) | StreamRecord::Update(row) => assert!(!row.deleted),
+                }
+            }
+
+            match resp.next_shard_iterator {
+                Some(s) => shard_iterator = s,
+                None => break,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_stream_record() {
+        test_get_stream_record_impl().await
+    }
+
+    #[test]
+    fn test_stream_record_from_response() {
+        let put_row = Row::new().primary_key_column_string("str_id", "a").column_string("str_col", "put");
+        let update_row = Row::new()
+            .primary_key_column_string("str_id", "b")
+            .column_integer("int_col", 1)
+            .column_to_delete_all_versions("str_col");
+        let delete_row = Row::new().primary_key_column_string("str_id", "c").delete_marker();
+
+        let record = |action_type: ActionType, row: &Row| get_stream_record_response::StreamRecord {
+            action_type: action_type as i32,
+            record: row.encode_plain_buffer(MASK_HEADER),
+            origin_record: None,
+        };
+
+        let msg = GetStreamRecordResponse {
+            stream_records: vec![
+                record(ActionType::PutRow, &put_row),
+                record(ActionType::UpdateRow, &update_row),
+                record(ActionType::DeleteRow, &delete_row),
+            ],
+            next_shard_iterator: Some("next".to_string()),
+            consumed: None,
+            may_more_record: None,
+        };
+
+        let resp = super::GetStreamRecordResponse::try_from(msg.clone()).unwrap();
+        assert_eq!(Some("next".to_string()), resp.next_shard_iterator);
+        assert_eq!(3, resp.records.len());
+
+        assert!(matches!(&resp.records[0], StreamRecord::Put(row) if row.get_column_value("str_col").is_some()));
+        assert_eq!(ActionType::PutRow, resp.records[0].action_type());
+
+        let StreamRecord::Update(row) = &resp.records[1] else {
+            panic!("expect update record, got: {:?}", resp.records[1]);
+        };
+        let ops = row.columns.iter().map(|c| (c.name.as_str(), c.op)).collect::<Vec<_>>();
+        assert_eq!(vec![("int_col", None), ("str_col", Some(ColumnOp::DeleteAll))], ops);
+
+        let StreamRecord::Delete(row) = &resp.records[2] else {
+            panic!("expect delete record, got: {:?}", resp.records[2]);
+        };
+        assert!(row.deleted);
+        assert_eq!(ActionType::DeleteRow, resp.records[2].action_type());
+
+        let mut msg = msg;
+        msg.stream_records[0].action_type = 100;
+        assert!(super::GetStreamRecordResponse::try_from(msg).is_err());
+    }
+
+    async fn test_stream_reader_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+
+        let streams = client.list_stream(Some("data_types")).send().await.unwrap();
+        let details = client.describe_stream(&streams[0].stream_id).send().await.unwrap();
+        assert!(!details.shards.is_empty());
+
+        let mut reader = client
+            .stream_reader(&details.stream_id, &details.shards[0].shard_id)
+            .poll_interval_ms(100)
+            .max_poll_interval_ms(400)
+            .limit(100);
+
+        // 分区可能一直处于打开状态，只读取有限的记录
+        let mut n = 0;
+        while let Some(record) = tokio::time::timeout(std::time::Duration::from_secs(5), reader.next()).await.ok().flatten() {
+            assert!(record.is_ok());
+            n += 1;
+
+            if n >= 10 {
+                break;
+            }
+        }
+
+        log::debug!("{} records read", n);
+    }
+
+    #[tokio::test]
+    async fn test_stream_reader() {
+        test_stream_reader_impl().await
+    }
+
+    #[test]
+    fn test_next_poll_interval_ms() {
+        assert_eq!(100, next_poll_interval_ms(0, 100, 1000));
+        assert_eq!(200, next_poll_interval_ms(100, 100, 1000));
+        assert_eq!(800, next_poll_interval_ms(400, 100, 1000));
+        assert_eq!(1000, next_poll_interval_ms(800, 100, 1000));
+        assert_eq!(1000, next_poll_interval_ms(1000, 100, 1000));
+        assert_eq!(50, next_poll_interval_ms(0, 100, 50));
+        assert_eq!(u64::MAX, next_poll_interval_ms(u64::MAX, 100, u64::MAX));
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{
+    data::row_stream::{PagedRowOperation, RowStream},
+    OtsClient, OtsResult,
+};
+
+use super::StreamRecord;
+
+/// 默认的轮询间隔，单位为毫秒
+const DEFAULT_POLL_INTERVAL_MS: u64 = 1000;
+
+/// 默认的最大轮询间隔，单位为毫秒
+const DEFAULT_MAX_POLL_INTERVAL_MS: u64 = 30000;
+
+/// 分区连续返回空数据时的下一次轮询间隔：从 `poll_interval_ms` 开始翻倍，不超过 `max_poll_interval_ms`
+pub(crate) fn next_poll_interval_ms(current_ms: u64, poll_interval_ms: u64, max_poll_interval_ms: u64) -> u64 {
+    if current_ms == 0 {
+        poll_interval_ms.min(max_poll_interval_ms)
+    } else {
+        current_ms.saturating_mul(2).min(max_poll_interval_ms)
+    }
+}
+
+/// 读取一个分区的一页记录的操作
+struct ShardPage {
+    client: OtsClient,
+    stream_id: String,
+    shard_id: String,
+    limit: Option<u32>,
+    poll_interval_ms: u64,
+    max_poll_interval_ms: u64,
+
+    /// 当前的分区迭代器。还没有获取的时候为 `None`
+    shard_iterator: Option<String>,
+
+    /// 当前的轮询间隔，为 `0` 表示上一次读取到了数据
+    current_interval_ms: u64,
+}
+
+impl PagedRowOperation<StreamRecord> for ShardPage {
+    async fn next_page(mut self) -> OtsResult<(Vec<StreamRecord>, Option<Self>)> {
+        let shard_iterator = match self.shard_iterator.take() {
+            Some(s) => s,
+            None => self.client.get_shard_iterator(&self.stream_id, &self.shard_id).send().await?.shard_iterator,
+        };
+
+        let resp = self.client.get_stream_record(&shard_iterator, self.limit).send().await?;
+
+        match resp.next_shard_iterator {
+            Some(s) => self.shard_iterator = Some(s),
+            // 分区已经关闭，返回剩余的记录之后结束
+            None => return Ok((resp.records, None)),
+        }
+
+        if !resp.records.is_empty() {
+            self.current_interval_ms = 0;
+            return Ok((resp.records, Some(self)));
+        }
+
+        self.current_interval_ms = next_poll_interval_ms(self.current_interval_ms, self.poll_interval_ms, self.max_poll_interval_ms);
+
+        if self.client.options.sleep_or_shutdown(self.current_interval_ms).await {
+            return Ok((vec![], None));
+        }
+
+        Ok((vec![], Some(self)))
+    }
+}
+
+/// 持续读取 Stream 中一个分区的记录。
+///
+/// 在内部通过 `GetStreamRecord` 循环读取，并自动使用返回的下一个分区迭代器，翻页的逻辑和 `get_range` 的行读取流相同。
+/// 分区暂时没有新数据的时候，从 `poll_interval_ms` 开始以翻倍的间隔重新读取，最大不超过 `max_poll_interval_ms`。
+/// 分区关闭（分裂或者合并）之后，返回剩余的记录然后结束；出错或者收到客户端的停机信号也会结束。
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut reader = client.stream_reader(&stream_id, &shard_id).poll_interval_ms(500);
+///
+/// while let Some(record) = reader.next().await {
+///     let record = record?;
+///     println!("{:?}", record);
+/// }
+/// ```
+pub struct StreamReader {
+    /// 第一次读取之前的读取配置
+    page: Option<ShardPage>,
+
+    /// 第一次读取的时候创建
+    stream: Option<RowStream<ShardPage, StreamRecord>>,
+}
+
+impl StreamReader {
+    pub(crate) fn new(client: OtsClient, stream_id: &str, shard_id: &str) -> Self {
+        Self {
+            page: Some(ShardPage {
+                client,
+                stream_id: stream_id.to_string(),
+                shard_id: shard_id.to_string(),
+                limit: None,
+                poll_interval_ms: DEFAULT_POLL_INTERVAL_MS,
+                max_poll_interval_ms: DEFAULT_MAX_POLL_INTERVAL_MS,
+                shard_iterator: None,
+                current_interval_ms: 0,
+            }),
+            stream: None,
+        }
+    }
+
+    /// 设置轮询间隔，单位为毫秒。默认为 `1000`
+    pub fn poll_interval_ms(mut self, ms: u64) -> Self {
+        if let Some(page) = &mut self.page {
+            page.poll_interval_ms = ms;
+        }
+
+        self
+    }
+
+    /// 设置最大轮询间隔，单位为毫秒。默认为 `30000`
+    pub fn max_poll_interval_ms(mut self, ms: u64) -> Self {
+        if let Some(page) = &mut self.page {
+            page.max_poll_interval_ms = ms;
+        }
+
+        self
+    }
+
+    /// 设置每次 `GetStreamRecord` 最多读取的记录数
+    pub fn limit(mut self, limit: u32) -> Self {
+        if let Some(page) = &mut self.page {
+            page.limit = Some(limit);
+        }
+
+        self
+    }
+
+    /// 从已有的分区迭代器继续读取，而不是从分区的开始读取
+    pub fn shard_iterator(mut self, shard_iterator: &str) -> Self {
+        if let Some(page) = &mut self.page {
+            page.shard_iterator = Some(shard_iterator.to_string());
+        }
+
+        self
+    }
+
+    /// 读取下一条记录。返回 `None` 表示读取结束
+    pub async fn next(&mut self) -> Option<OtsResult<StreamRecord>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl Stream for StreamReader {
+    type Item = OtsResult<StreamRecord>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        let stream = match this.page.take() {
+            Some(page) => this.stream.insert(RowStream::new(page)),
+            None => match this.stream.as_mut() {
+                Some(stream) => stream,
+                None => return Poll::Ready(None),
+            },
+        };
+
+        Pin::new(stream).poll_next(cx)
+    }
+}
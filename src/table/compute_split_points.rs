@@ -121,6 +121,30 @@ pub struct ComputeSplitPointsBySizeResponse {
     pub locations: Vec<SplitLocation>,
 }
 
+impl ComputeSplitPointsBySizeResponse {
+    /// 把分割点转换成首尾相接的主键范围 `(start, end)`，第一个范围从全部主键列为 `-inf` 开始，
+    /// 最后一个范围到全部主键列为 `+inf` 结束。每个范围可以直接作为 `GetRange` 的起始主键（包含）和结束主键（不包含），
+    /// 用来并发读取全表数据。没有分割点的时候返回一个覆盖全表的范围
+    pub fn into_primary_key_ranges(self) -> Vec<(PrimaryKey, PrimaryKey)> {
+        let Self { schema, split_points, .. } = self;
+
+        let inf_min = PrimaryKey::new().columns(schema.iter().map(|s| PrimaryKeyColumn::inf_min(&s.name)));
+        let inf_max = PrimaryKey::new().columns(schema.iter().map(|s| PrimaryKeyColumn::inf_max(&s.name)));
+
+        let mut ranges = Vec::with_capacity(split_points.len() + 1);
+        let mut start = inf_min;
+
+        for pk in split_points {
+            ranges.push((start, pk.clone()));
+            start = pk;
+        }
+
+        ranges.push((start, inf_max));
+
+        ranges
+    }
+}
+
 impl TryFrom<crate::protos::ComputeSplitPointsBySizeResponse> for ComputeSplitPointsBySizeResponse {
     type Error = OtsError;
 
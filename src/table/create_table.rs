@@ -306,7 +306,7 @@ impl CreateTableRequest {
     }
 
     /// Validate the create table settings
-    fn validate(&self) -> OtsResult<()> {
+    pub(crate) fn validate(&self) -> OtsResult<()> {
         if !validate_table_name(&self.table_name) {
             return Err(OtsError::ValidationFailed(format!("invalid table name: \"{}\"", self.table_name)));
         }
@@ -335,6 +335,21 @@ impl CreateTableRequest {
             }
         }
 
+        if let Some(n) = self.max_versions {
+            if n < 1 {
+                return Err(OtsError::ValidationFailed(format!("invalid max versions: {}. must be greater than 0", n)));
+            }
+        }
+
+        if let Some(n) = self.deviation_cell_version_in_sec {
+            if n < 1 {
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid deviation cell version in seconds: {}. must be greater than 0",
+                    n
+                )));
+            }
+        }
+
         for col in &self.defined_columns {
             if !validate_column_name(&col.name) {
                 return Err(OtsError::ValidationFailed(format!("invalid column name: \"{}\"", col.name)));
@@ -38,6 +38,11 @@ impl DescribeTableOperation {
         Ok(())
     }
 
+    /// 发送请求。除了表结构和预留吞吐量之外，响应中还包括：
+    ///
+    /// - `table_options`：数据生命周期、最大版本数等表配置；
+    /// - `stream_details`：Stream 是否开启（`enable_stream`）以及过期时间（`expiration_time`，单位为小时）；
+    /// - `index_metas`：表上的全部二级索引。
     pub async fn send(self) -> OtsResult<DescribeTableResponse> {
         self.validate()?;
 
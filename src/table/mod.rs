@@ -15,15 +15,18 @@ pub use update_table::*;
 
 #[cfg(test)]
 mod test_table {
+    use prost::Message;
+
     use crate::{
         index::IndexMetaBuilder,
-        protos::IndexType,
+        model::PrimaryKey,
+        protos::{DescribeTableResponse, IndexType, PrimaryKeySchema, PrimaryKeyType, StreamDetails, TableMeta},
         table::{CreateTableRequest, UpdateTableRequest},
         test_util::setup,
         OtsClient,
     };
 
-    use super::ComputeSplitPointsBySizeRequest;
+    use super::{ComputeSplitPointsBySizeRequest, ComputeSplitPointsBySizeResponse};
 
     #[tokio::test]
     async fn test_list_table() {
@@ -52,6 +55,48 @@ mod test_table {
         assert_eq!("user_id", &pk.first().unwrap().name);
     }
 
+    #[test]
+    fn test_decode_describe_table_response() {
+        let msg = DescribeTableResponse {
+            table_meta: TableMeta {
+                table_name: "ccs2".to_string(),
+                ..Default::default()
+            },
+            stream_details: Some(StreamDetails {
+                enable_stream: true,
+                expiration_time: Some(24),
+                ..Default::default()
+            }),
+            index_metas: vec![IndexMetaBuilder::new("index_cn").primary_key("cc_id").build()],
+            ..Default::default()
+        };
+
+        let resp = DescribeTableResponse::decode(msg.encode_to_vec().as_slice()).unwrap();
+        let stream = resp.stream_details.unwrap();
+        assert!(stream.enable_stream);
+        assert_eq!(Some(24), stream.expiration_time);
+        assert_eq!(vec!["index_cn"], resp.index_metas.iter().map(|idx| idx.name.as_str()).collect::<Vec<_>>());
+        assert_eq!(vec!["cc_id".to_string()], resp.index_metas[0].primary_key);
+    }
+
+    async fn test_desc_table_indexes_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let resp = client.describe_table("ccs2").send().await;
+        log::debug!("describe table ccs2: {:#?}", resp);
+        assert!(resp.is_ok());
+
+        let resp = resp.unwrap();
+        assert!(resp.index_metas.iter().any(|idx| idx.name == "index_cn"));
+        assert!(resp.stream_details.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_desc_table_indexes() {
+        test_desc_table_indexes_impl().await;
+    }
+
     async fn test_create_table_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -97,6 +142,70 @@ mod test_table {
         test_create_table_impl().await;
     }
 
+    #[test]
+    fn test_create_table_request_table_options() {
+        let req = CreateTableRequest::new("users_ttl")
+            .primary_key_string("user_id")
+            .ttl_seconds(86400)
+            .max_versions(3)
+            .deviation_cell_version_seconds(3600);
+        assert!(req.validate().is_ok());
+
+        let msg: crate::protos::CreateTableRequest = req.into();
+        let opts = msg.table_options.unwrap();
+        assert_eq!(Some(86400), opts.time_to_live);
+        assert_eq!(Some(3), opts.max_versions);
+        assert_eq!(Some(3600), opts.deviation_cell_version_in_sec);
+
+        // 没有设置的时候使用默认值
+        let msg: crate::protos::CreateTableRequest = CreateTableRequest::new("users_ttl").primary_key_string("user_id").into();
+        let opts = msg.table_options.unwrap();
+        assert_eq!(Some(-1), opts.time_to_live);
+        assert_eq!(Some(1), opts.max_versions);
+        assert_eq!(Some(86400), opts.deviation_cell_version_in_sec);
+
+        assert!(CreateTableRequest::new("users_ttl")
+            .primary_key_string("user_id")
+            .ttl_seconds(3600)
+            .validate()
+            .is_err());
+        assert!(CreateTableRequest::new("users_ttl")
+            .primary_key_string("user_id")
+            .max_versions(0)
+            .validate()
+            .is_err());
+        assert!(CreateTableRequest::new("users_ttl")
+            .primary_key_string("user_id")
+            .deviation_cell_version_seconds(0)
+            .validate()
+            .is_err());
+    }
+
+    async fn test_create_table_with_table_options_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let req = CreateTableRequest::new("users_ttl")
+            .primary_key_string("user_id")
+            .ttl_seconds(86400)
+            .max_versions(3);
+        let response = client.create_table(req).send().await;
+        log::debug!("{:#?}", response);
+        assert!(response.is_ok());
+
+        let desc = client.describe_table("users_ttl").send().await.unwrap();
+        assert_eq!(Some(86400), desc.table_options.time_to_live);
+        assert_eq!(Some(3), desc.table_options.max_versions);
+
+        let response = client.delete_table("users_ttl").send().await;
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_table_with_table_options() {
+        test_create_table_with_table_options_impl().await;
+    }
+
     async fn test_validate_create_table_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -147,6 +256,55 @@ mod test_table {
         test_update_table_impl().await;
     }
 
+    #[test]
+    fn test_update_table_request_stream() {
+        let msg: crate::protos::UpdateTableRequest = UpdateTableRequest::new("ccs").enable_stream(24).into();
+        let spec = msg.stream_spec.unwrap();
+        assert!(spec.enable_stream);
+        assert_eq!(Some(24), spec.expiration_time);
+
+        let msg: crate::protos::UpdateTableRequest = UpdateTableRequest::new("ccs").enable_stream(24).disable_stream().into();
+        let spec = msg.stream_spec.unwrap();
+        assert!(!spec.enable_stream);
+        assert_eq!(None, spec.expiration_time);
+
+        let msg: crate::protos::UpdateTableRequest = UpdateTableRequest::new("ccs").stream(false).into();
+        assert!(!msg.stream_spec.unwrap().enable_stream);
+
+        // 没有设置 stream 的时候不修改 stream 配置
+        let msg: crate::protos::UpdateTableRequest = UpdateTableRequest::new("ccs").max_versions(1).into();
+        assert!(msg.stream_spec.is_none());
+
+        assert!(UpdateTableRequest::new("ccs").enable_stream(0).validate().is_err());
+        assert!(UpdateTableRequest::new("ccs").enable_stream(169).validate().is_err());
+        assert!(UpdateTableRequest::new("ccs").enable_stream(168).validate().is_ok());
+    }
+
+    async fn test_update_table_stream_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let response = client.update_table(UpdateTableRequest::new("ccs").enable_stream(24)).send().await;
+        log::debug!("{:#?}", response);
+        assert!(response.is_ok());
+
+        let desc = client.describe_table("ccs").send().await.unwrap();
+        let stream = desc.stream_details.unwrap();
+        assert!(stream.enable_stream);
+        assert_eq!(Some(24), stream.expiration_time);
+
+        let response = client.update_table(UpdateTableRequest::new("ccs").disable_stream()).send().await;
+        assert!(response.is_ok());
+
+        let desc = client.describe_table("ccs").send().await.unwrap();
+        assert!(!desc.stream_details.unwrap().enable_stream);
+    }
+
+    #[tokio::test]
+    async fn test_update_table_stream() {
+        test_update_table_stream_impl().await;
+    }
+
     async fn test_delete_table_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -190,5 +348,50 @@ mod test_table {
             .await;
 
         log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+
+        let ranges = resp.unwrap().into_primary_key_ranges();
+        assert!(!ranges.is_empty());
+        assert!(ranges.windows(2).all(|w| w[0].1 == w[1].0));
+    }
+
+    #[test]
+    fn test_split_points_into_primary_key_ranges() {
+        let schema = vec![
+            PrimaryKeySchema {
+                name: "a".to_string(),
+                r#type: PrimaryKeyType::String as i32,
+                option: None,
+            },
+            PrimaryKeySchema {
+                name: "b".to_string(),
+                r#type: PrimaryKeyType::Integer as i32,
+                option: None,
+            },
+        ];
+
+        let resp = ComputeSplitPointsBySizeResponse {
+            schema: schema.clone(),
+            split_points: vec![
+                PrimaryKey::new().column_string("a", "m").column_inf_min("b"),
+                PrimaryKey::new().column_string("a", "t").column_inf_min("b"),
+            ],
+            ..Default::default()
+        };
+
+        let ranges = resp.into_primary_key_ranges();
+        assert_eq!(3, ranges.len());
+        assert_eq!(PrimaryKey::new().column_inf_min("a").column_inf_min("b"), ranges[0].0);
+        assert_eq!(PrimaryKey::new().column_info_max("a").column_info_max("b"), ranges[2].1);
+
+        // 相邻的范围首尾相接
+        assert!(ranges.windows(2).all(|w| w[0].1 == w[1].0));
+        assert_eq!(PrimaryKey::new().column_string("a", "m").column_inf_min("b"), ranges[1].0);
+
+        // 没有分割点的时候覆盖全表
+        let ranges = ComputeSplitPointsBySizeResponse { schema, ..Default::default() }.into_primary_key_ranges();
+        assert_eq!(1, ranges.len());
+        assert_eq!(PrimaryKey::new().column_inf_min("a").column_inf_min("b"), ranges[0].0);
+        assert_eq!(PrimaryKey::new().column_info_max("a").column_info_max("b"), ranges[0].1);
     }
 }
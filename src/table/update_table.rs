@@ -33,6 +33,9 @@ pub struct UpdateTableRequest {
 
     // stream spec.
     pub stream_enabled: bool,
+    /// 是否修改 stream 配置。为 `false` 并且 `stream_enabled` 也为 `false` 时不修改 stream 配置，
+    /// 使用 `stream`、`enable_stream` 或者 `disable_stream` 时会设置为 `true`
+    pub update_stream: bool,
     pub stream_expiration_hour: Option<i32>,
     pub stream_columns: HashSet<String>,
 }
@@ -86,9 +89,27 @@ impl UpdateTableRequest {
         self
     }
 
-    /// 设置是否启用 stream
+    /// 设置是否启用 stream。设置为 `false` 时关闭 stream
     pub fn stream(mut self, enabled: bool) -> Self {
         self.stream_enabled = enabled;
+        self.update_stream = true;
+        self
+    }
+
+    /// 开启 stream，`expiration_hours` 是 stream 数据的过期时间，单位为小时，取值范围为 `[1, 168]`
+    pub fn enable_stream(mut self, expiration_hours: u32) -> Self {
+        self.stream_enabled = true;
+        self.update_stream = true;
+        self.stream_expiration_hour = Some(expiration_hours.min(i32::MAX as u32) as i32);
+        self
+    }
+
+    /// 关闭 stream
+    pub fn disable_stream(mut self) -> Self {
+        self.stream_enabled = false;
+        self.update_stream = true;
+        self.stream_expiration_hour = None;
+        self.stream_columns.clear();
         self
     }
 
@@ -104,11 +125,20 @@ impl UpdateTableRequest {
         self
     }
 
-    fn validate(&self) -> OtsResult<()> {
+    pub(crate) fn validate(&self) -> OtsResult<()> {
         if !validate_table_name(&self.table_name) {
             return Err(OtsError::ValidationFailed(format!("Invalid table name: {}", self.table_name)));
         }
 
+        if let Some(exp) = self.stream_expiration_hour {
+            if self.stream_enabled && !(1..=168).contains(&exp) {
+                return Err(OtsError::ValidationFailed(format!(
+                    "Invalid stream expiration hours: {}. must be in range [1, 168]",
+                    exp
+                )));
+            }
+        }
+
         Ok(())
     }
 }
@@ -124,6 +154,7 @@ impl From<UpdateTableRequest> for crate::protos::UpdateTableRequest {
             deviation_cell_version_in_sec,
             allow_update,
             stream_enabled,
+            update_stream,
             stream_expiration_hour,
             stream_columns,
         } = value;
@@ -151,11 +182,15 @@ impl From<UpdateTableRequest> for crate::protos::UpdateTableRequest {
             } else {
                 None
             },
-            stream_spec: if stream_enabled {
+            stream_spec: if stream_enabled || update_stream {
                 Some(StreamSpecification {
                     enable_stream: stream_enabled,
-                    expiration_time: stream_expiration_hour,
-                    columns_to_get: stream_columns.into_iter().collect::<Vec<_>>(),
+                    expiration_time: if stream_enabled { stream_expiration_hour } else { None },
+                    columns_to_get: if stream_enabled {
+                        stream_columns.into_iter().collect::<Vec<_>>()
+                    } else {
+                        vec![]
+                    },
                 })
             } else {
                 None
@@ -8,3 +8,70 @@ pub(crate) fn setup() {
         dotenvy::dotenv().unwrap();
     });
 }
+
+/// 请求 ID 的前缀，每个响应的请求 ID 是这个前缀加上序号
+pub(crate) const MOCK_REQUEST_ID_PREFIX: &str = "mock-request-id-";
+
+/// 启动一个本地的 HTTP 服务，对每个请求都返回 `status` 状态码和错误码为 `code` 的错误响应。返回服务的 endpoint
+pub(crate) async fn mock_error_server(status: u16, code: &str) -> String {
+    use prost::Message;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+    let body = crate::protos::Error {
+        code: code.to_string(),
+        message: Some("mock error".to_string()),
+        access_denied_detail: None,
+    }
+    .encode_to_vec();
+
+    tokio::spawn(async move {
+        let mut seq = 0;
+
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let body = body.clone();
+            let request_id = format!("{}{}", MOCK_REQUEST_ID_PREFIX, seq);
+            seq += 1;
+
+            tokio::spawn(async move {
+                // 读取完整的请求，避免在客户端发送完成之前关闭连接
+                let mut buf = vec![];
+                let mut chunk = [0u8; 4096];
+
+                loop {
+                    let n = socket.read(&mut chunk).await.unwrap_or(0);
+                    if n == 0 {
+                        return;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+
+                    let text = String::from_utf8_lossy(&buf).to_lowercase();
+                    if let Some(pos) = text.find("\r\n\r\n") {
+                        let content_length = text[..pos]
+                            .lines()
+                            .find_map(|l| l.strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap_or(0)))
+                            .unwrap_or(0);
+
+                        if buf.len() >= pos + 4 + content_length {
+                            break;
+                        }
+                    }
+                }
+
+                let head = format!(
+                    "HTTP/1.1 {} Mock\r\nx-ots-requestid: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    status,
+                    request_id,
+                    body.len()
+                );
+                let _ = socket.write_all(head.as_bytes()).await;
+                let _ = socket.write_all(&body).await;
+                let _ = socket.shutdown().await;
+            });
+        }
+    });
+
+    endpoint
+}
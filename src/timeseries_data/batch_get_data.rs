@@ -0,0 +1,263 @@
+use tokio::task::JoinSet;
+
+use crate::{
+    add_per_request_options,
+    error::OtsError,
+    timeseries_model::{rules::validate_timeseries_table_name, TimeseriesFieldToGet, TimeseriesKey, TimeseriesRow},
+    util::join_next_or_resume,
+    OtsClient, OtsRequestOptions, OtsResult,
+};
+
+use super::{GetTimeseriesDataRequest, GetTimeseriesDataResponse};
+
+/// 默认同时查询的时间线数量
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// 一次查询多条时间线的数据。
+///
+/// 服务端的 `GetTimeseriesData` 接口一次只能查询一条时间线，这里对每个时间线标识发送一个 `GetTimeseriesData` 请求，
+/// 并发执行，除时间线标识以外的查询条件对所有时间线都相同。
+/// 需要翻页时，可以使用返回结果中每条时间线的 `next_token` 构造 [`GetTimeseriesDataRequest`] 单独继续查询。
+#[derive(Debug, Default, Clone)]
+pub struct BatchGetTimeseriesDataRequest {
+    /// 表名
+    pub table_name: String,
+
+    /// 时间线标识
+    pub keys: Vec<TimeseriesKey>,
+
+    /// 开始时间。格式为微秒单位时间戳（从 1970-01-01 00:00:00 UTC 计算起的微秒数）
+    pub begin_time_us: u64,
+
+    /// 结束时间。格式为微秒单位时间戳（从 1970-01-01 00:00:00 UTC 计算起的微秒数）
+    pub end_time_us: u64,
+
+    /// 特定时间。格式为微秒单位时间戳（从 1970-01-01 00:00:00 UTC 计算起的微秒数）
+    pub specific_time_us: Option<u64>,
+
+    /// 每条时间线最多返回的行数
+    pub limit: Option<u32>,
+
+    /// 是否按照时间倒序读取。默认为正序读取
+    pub backward: bool,
+
+    /// 指定读取部分数据列
+    pub fields_to_get: Vec<TimeseriesFieldToGet>,
+}
+
+impl BatchGetTimeseriesDataRequest {
+    pub fn new(table_name: &str) -> Self {
+        Self {
+            table_name: table_name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// 添加一个时间线标识
+    pub fn key(mut self, key: TimeseriesKey) -> Self {
+        self.keys.push(key);
+
+        self
+    }
+
+    /// 设置时间线标识
+    pub fn keys(mut self, keys: impl IntoIterator<Item = TimeseriesKey>) -> Self {
+        self.keys = keys.into_iter().collect();
+
+        self
+    }
+
+    /// 设置开始时间。微秒时间戳（从 1970-01-01 00:00:00 UTC 计算起的微秒数）
+    pub fn begin_time_us(mut self, begin_time: u64) -> Self {
+        self.begin_time_us = begin_time;
+
+        self
+    }
+
+    /// 设置结束时间。微秒时间戳（从 1970-01-01 00:00:00 UTC 计算起的微秒数）
+    pub fn end_time_us(mut self, end_time: u64) -> Self {
+        self.end_time_us = end_time;
+
+        self
+    }
+
+    /// 设置指定时间。微秒时间戳（从 1970-01-01 00:00:00 UTC 计算起的微秒数）
+    pub fn specific_time_us(mut self, specific_time: u64) -> Self {
+        self.specific_time_us = Some(specific_time);
+
+        self
+    }
+
+    /// 设置每条时间线最多返回行数
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    /// 设置是否按照时间倒序
+    pub fn backward(mut self, backward: bool) -> Self {
+        self.backward = backward;
+
+        self
+    }
+
+    /// 添加一个要获取的列
+    pub fn field_to_get(mut self, field: TimeseriesFieldToGet) -> Self {
+        self.fields_to_get.push(field);
+
+        self
+    }
+
+    /// 设置要获取的列
+    pub fn fields_to_get(mut self, fields_to_get: impl IntoIterator<Item = TimeseriesFieldToGet>) -> Self {
+        self.fields_to_get = fields_to_get.into_iter().collect();
+
+        self
+    }
+
+    /// 构造查询单条时间线的请求
+    pub(crate) fn to_request(&self, key: TimeseriesKey) -> GetTimeseriesDataRequest {
+        GetTimeseriesDataRequest {
+            table_name: self.table_name.clone(),
+            key,
+            begin_time_us: self.begin_time_us,
+            end_time_us: self.end_time_us,
+            specific_time_us: self.specific_time_us,
+            token: None,
+            limit: self.limit,
+            backward: self.backward,
+            fields_to_get: self.fields_to_get.clone(),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> OtsResult<()> {
+        if !validate_timeseries_table_name(&self.table_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid table name: {}", self.table_name)));
+        }
+
+        if self.keys.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid keys: can not be empty".to_string()));
+        }
+
+        for key in &self.keys {
+            self.to_request(key.clone()).validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// 一条时间线的查询结果
+#[derive(Debug, Clone)]
+pub struct TimeseriesTimelineData {
+    /// 时间线标识
+    pub key: TimeseriesKey,
+
+    /// 行数据
+    pub rows: Vec<TimeseriesRow>,
+
+    /// 分页 token
+    pub next_token: Option<Vec<u8>>,
+}
+
+/// 批量查询时间线数据的响应
+#[derive(Debug, Clone, Default)]
+pub struct BatchGetTimeseriesDataResponse {
+    /// 每条时间线的查询结果，和请求中时间线标识的顺序相同
+    pub timelines: Vec<TimeseriesTimelineData>,
+}
+
+/// 一次查询多条时间线的数据
+#[derive(Clone)]
+pub struct BatchGetTimeseriesDataOperation {
+    client: OtsClient,
+    request: BatchGetTimeseriesDataRequest,
+    concurrency: usize,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(BatchGetTimeseriesDataOperation);
+
+impl BatchGetTimeseriesDataOperation {
+    pub(crate) fn new(client: OtsClient, request: BatchGetTimeseriesDataRequest) -> Self {
+        Self {
+            client,
+            request,
+            concurrency: DEFAULT_CONCURRENCY,
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    /// 同时发送的 `GetTimeseriesData` 请求的最大数量，默认为 `8`
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+
+        self
+    }
+
+    /// 等待正在执行的查询任务完成一个，并把结果放到对应的位置
+    async fn join_next(
+        tasks: &mut JoinSet<(usize, OtsResult<GetTimeseriesDataResponse>)>,
+        responses: &mut [Option<GetTimeseriesDataResponse>],
+    ) -> OtsResult<()> {
+        if let Some((i, result)) = join_next_or_resume(tasks).await {
+            responses[i] = Some(result?);
+        }
+
+        Ok(())
+    }
+
+    /// 发送请求。任意一条时间线查询失败，都会返回错误
+    pub async fn send(self) -> OtsResult<BatchGetTimeseriesDataResponse> {
+        self.request.validate()?;
+
+        if self.concurrency == 0 {
+            return Err(OtsError::ValidationFailed("invalid concurrency: must be greater than 0".to_string()));
+        }
+
+        let Self {
+            client,
+            request,
+            concurrency,
+            options,
+        } = self;
+
+        let mut responses = vec![None; request.keys.len()];
+        let mut tasks = JoinSet::new();
+
+        for (i, key) in request.keys.iter().enumerate() {
+            while tasks.len() >= concurrency {
+                Self::join_next(&mut tasks, &mut responses).await?;
+            }
+
+            let mut op = client.get_timeseries_data(request.to_request(key.clone()));
+
+            if let Some(ms) = options.timeout_ms {
+                op = op.timeout_ms(ms);
+            }
+
+            tasks.spawn(async move { (i, op.send().await) });
+        }
+
+        while !tasks.is_empty() {
+            Self::join_next(&mut tasks, &mut responses).await?;
+        }
+
+        let timelines = request
+            .keys
+            .into_iter()
+            .zip(responses)
+            .map(|(key, resp)| {
+                let GetTimeseriesDataResponse { rows, next_token } = resp.unwrap_or(GetTimeseriesDataResponse {
+                    rows: vec![],
+                    next_token: None,
+                });
+
+                TimeseriesTimelineData { key, rows, next_token }
+            })
+            .collect();
+
+        Ok(BatchGetTimeseriesDataResponse { timelines })
+    }
+}
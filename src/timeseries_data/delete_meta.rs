@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+
 use prost::Message;
 
 use crate::{
     add_per_request_options,
     error::OtsError,
-    protos::timeseries::DeleteTimeseriesMetaResponse,
+    protos::timeseries::FailedRowInfo,
     timeseries_model::{rules::validate_timeseries_table_name, TimeseriesKey, SUPPORTED_TABLE_VERSION},
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
@@ -35,7 +37,7 @@ impl DeleteTimeseriesMetaRequest {
         self
     }
 
-    /// 设置时间线标识
+    /// 设置时间线标识。一次请求可以删除多条时间线
     pub fn keys(mut self, keys: impl IntoIterator<Item = TimeseriesKey>) -> Self {
         self.keys = keys.into_iter().collect();
 
@@ -71,6 +73,37 @@ impl From<DeleteTimeseriesMetaRequest> for crate::protos::timeseries::DeleteTime
     }
 }
 
+/// 删除时间线元数据的响应
+#[derive(Debug, Clone, Default)]
+pub struct DeleteTimeseriesMetaResponse {
+    /// 请求中的时间线标识，顺序和请求中一致
+    pub keys: Vec<TimeseriesKey>,
+
+    /// 删除失败的行。`row_index` 是时间线标识在 `keys` 中的序号
+    pub failed_rows: Vec<FailedRowInfo>,
+}
+
+impl DeleteTimeseriesMetaResponse {
+    /// 是否全部删除成功
+    pub fn is_all_succeeded(&self) -> bool {
+        self.failed_rows.is_empty()
+    }
+
+    /// 每一个时间线标识的删除结果，顺序和请求中一致。删除失败的时候返回服务端返回的失败信息
+    pub fn results(&self) -> Vec<(&TimeseriesKey, Result<(), &FailedRowInfo>)> {
+        let failed = self.failed_rows.iter().map(|f| (f.row_index as usize, f)).collect::<HashMap<_, _>>();
+
+        self.keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| match failed.get(&i) {
+                Some(f) => (key, Err(*f)),
+                None => (key, Ok(())),
+            })
+            .collect()
+    }
+}
+
 #[derive(Clone)]
 pub struct DeleteTimeseriesMetaOperation {
     client: OtsClient,
@@ -94,6 +127,7 @@ impl DeleteTimeseriesMetaOperation {
 
         let Self { client, request, options } = self;
 
+        let keys = request.keys.clone();
         let msg = crate::protos::timeseries::DeleteTimeseriesMetaRequest::from(request);
 
         let req = OtsRequest {
@@ -105,6 +139,11 @@ impl DeleteTimeseriesMetaOperation {
 
         let resp = client.send(req).await?;
 
-        Ok(crate::protos::timeseries::DeleteTimeseriesMetaResponse::decode(resp.bytes().await?)?)
+        let resp_msg = crate::protos::timeseries::DeleteTimeseriesMetaResponse::decode(resp.bytes().await?)?;
+
+        Ok(DeleteTimeseriesMetaResponse {
+            keys,
+            failed_rows: resp_msg.failed_rows,
+        })
     }
 }
@@ -1,7 +1,9 @@
+use futures_core::Stream;
 use prost::Message;
 
 use crate::{
     add_per_request_options,
+    data::row_stream::{PagedRowOperation, RowStream},
     error::OtsError,
     model::decode_plainbuf_rows,
     protos::plain_buffer::MASK_HEADER,
@@ -219,4 +221,38 @@ impl GetTimeseriesDataOperation {
 
         resp_msg.try_into()
     }
+
+    /// 转换成自动翻页的行读取流。
+    ///
+    /// 在内部使用响应中的 `next_token` 继续发送请求，直到服务端不再返回 token 为止。
+    /// 请求中的 `limit` 作为每一页的行数。出错之后返回这个错误，然后结束。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let stream = client.get_timeseries_data(request).into_row_stream();
+    /// tokio::pin!(stream);
+    ///
+    /// while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+    ///     let row = row?;
+    ///     println!("{:?}", row);
+    /// }
+    /// ```
+    pub fn into_row_stream(self) -> impl Stream<Item = OtsResult<TimeseriesRow>> {
+        RowStream::new(self)
+    }
+}
+
+impl PagedRowOperation<TimeseriesRow> for GetTimeseriesDataOperation {
+    async fn next_page(self) -> OtsResult<(Vec<TimeseriesRow>, Option<Self>)> {
+        let mut next_op = self.clone();
+        let GetTimeseriesDataResponse { rows, next_token } = self.send().await?;
+
+        let next = next_token.filter(|token| !token.is_empty()).map(|token| {
+            next_op.request.token = Some(token);
+            next_op
+        });
+
+        Ok((rows, next))
+    }
 }
@@ -1,5 +1,6 @@
 //! 时序数据
 
+mod batch_get_data;
 mod delete_meta;
 mod get_data;
 mod put_data;
@@ -8,6 +9,7 @@ mod scan_data;
 mod split_scan;
 mod update_meta;
 
+pub use batch_get_data::*;
 pub use delete_meta::*;
 pub use get_data::*;
 pub use put_data::*;
@@ -18,18 +20,24 @@ pub use update_meta::*;
 
 #[cfg(test)]
 mod test_timeseries_data {
+    use std::collections::HashSet;
+
+    use futures_core::Stream;
+
     use crate::{
         protos::timeseries::MetaQueryCompositeOperator,
         test_util::setup,
         timeseries_data::SplitTimeseriesScanTaskRequest,
-        timeseries_model::{CompositeMetaQuery, DatasourceMetaQuery, MeasurementMetaQuery, MetaQuery, TimeseriesKey, TimeseriesMeta, TimeseriesRow},
+        timeseries_model::{
+            AttributeMetaQuery, CompositeMetaQuery, DatasourceMetaQuery, MeasurementMetaQuery, MetaQuery, TimeseriesKey, TimeseriesMeta, TimeseriesRow,
+        },
         util::current_time_ms,
         OtsClient,
     };
 
     use super::{
-        DeleteTimeseriesMetaRequest, GetTimeseriesDataRequest, PutTimeseriesDataRequest, QueryTimeseriesMetaRequest, ScanTimeseriesDataRequest,
-        UpdateTimeseriesMetaRequest,
+        BatchGetTimeseriesDataRequest, DeleteTimeseriesMetaRequest, DeleteTimeseriesMetaResponse, GetTimeseriesDataRequest, PutTimeseriesDataRequest,
+        QueryTimeseriesMetaRequest, ScanTimeseriesDataRequest, UpdateTimeseriesMetaRequest,
     };
 
     /// Test query timeseries data
@@ -69,6 +77,103 @@ mod test_timeseries_data {
         test_get_timeseries_data_impl().await;
     }
 
+    async fn test_get_timeseries_data_row_stream_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let datasource = format!("stream_{}", current_time_ms());
+        let key = TimeseriesKey::new()
+            .measurement_name("measure_stream")
+            .datasource(&datasource)
+            .tag("cluster", "cluster_1");
+        let ts_us = (current_time_ms() * 1000) as u64;
+
+        let mut request = PutTimeseriesDataRequest::new("timeseries_demo_with_data");
+        for i in 0..25 {
+            request = request.row(
+                TimeseriesRow::new()
+                    .measurement_name("measure_stream")
+                    .datasource(&datasource)
+                    .tag("cluster", "cluster_1")
+                    .timestamp_us(ts_us + i * 1000)
+                    .field_integer("seq", i as i64),
+            );
+        }
+
+        client.put_timeseries_data(request).send().await.unwrap();
+
+        let request = GetTimeseriesDataRequest::new("timeseries_demo_with_data", key)
+            .begin_time_us(ts_us)
+            .end_time_us(ts_us + 25 * 1000)
+            .limit(10);
+
+        let stream = client.get_timeseries_data(request).into_row_stream();
+        tokio::pin!(stream);
+
+        let mut timestamps = vec![];
+        while let Some(row) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            timestamps.push(row.unwrap().timestamp_us);
+        }
+
+        assert_eq!((0..25).map(|i| ts_us + i * 1000).collect::<Vec<_>>(), timestamps);
+    }
+
+    #[tokio::test]
+    async fn test_get_timeseries_data_row_stream() {
+        test_get_timeseries_data_row_stream_impl().await;
+    }
+
+    async fn test_batch_get_timeseries_data_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let keys = (1..=3)
+            .map(|i| {
+                TimeseriesKey::new()
+                    .measurement_name("measure_7")
+                    .datasource(format!("data_{}", i))
+                    .tag("cluster", format!("cluster_{}", i))
+            })
+            .collect::<Vec<_>>();
+
+        let request = BatchGetTimeseriesDataRequest::new("timeseries_demo_with_data")
+            .keys(keys.clone())
+            .end_time_us(1744119422199000)
+            .limit(10);
+
+        let resp = client.batch_get_timeseries_data(request).concurrency(2).send().await;
+        log::debug!("{:?}", resp);
+        assert!(resp.is_ok());
+
+        let resp = resp.unwrap();
+        assert_eq!(keys.len(), resp.timelines.len());
+
+        for (key, timeline) in keys.iter().zip(&resp.timelines) {
+            assert_eq!(key, &timeline.key);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_get_timeseries_data() {
+        test_batch_get_timeseries_data_impl().await;
+    }
+
+    #[test]
+    fn test_batch_get_timeseries_data_validate() {
+        let request = BatchGetTimeseriesDataRequest::new("timeseries_demo_with_data").end_time_us(1744119422199000);
+        assert!(request.validate().is_err());
+
+        let request = request.key(TimeseriesKey::new().measurement_name("measure_7").datasource("data_1"));
+        assert!(request.validate().is_ok());
+
+        let single = request.to_request(request.keys[0].clone());
+        assert_eq!(request.end_time_us, single.end_time_us);
+        assert_eq!(request.keys[0], single.key);
+
+        let request = request.key(TimeseriesKey::new().measurement_name("").datasource("data_1"));
+        assert!(request.validate().is_err());
+    }
+
     async fn test_put_timeseries_data_impl() {
         setup();
 
@@ -106,6 +211,72 @@ mod test_timeseries_data {
         test_put_timeseries_data_impl().await
     }
 
+    #[test]
+    fn test_put_timeseries_data_auto_timestamp() {
+        let row = TimeseriesRow::new()
+            .measurement_name("measure_11")
+            .datasource("data_11")
+            .tag("cluster", "cluster_11")
+            .field_integer("temp", 123);
+
+        let request = PutTimeseriesDataRequest::new("timeseries_demo_with_data").row(row.clone());
+        assert!(request.auto_timestamp);
+        assert!(request.validate().is_ok());
+
+        let before_us = (current_time_ms() * 1000) as u64;
+        let msg = crate::protos::timeseries::PutTimeseriesDataRequest::from(request);
+        assert!(!msg.rows_data.rows_data.is_empty());
+
+        let request = PutTimeseriesDataRequest::new("timeseries_demo_with_data")
+            .row(row.clone())
+            .auto_timestamp(false);
+        assert!(request.validate().is_err());
+
+        let request = PutTimeseriesDataRequest::new("timeseries_demo_with_data")
+            .row(row.timestamp_us(before_us))
+            .auto_timestamp(false);
+        assert!(request.validate().is_ok());
+    }
+
+    async fn test_put_timeseries_data_auto_timestamp_read_back_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let datasource = format!("auto_ts_{}", current_time_ms());
+        let key = TimeseriesKey::new().measurement_name("measure_11").datasource(&datasource);
+
+        let before_us = (current_time_ms() * 1000) as u64;
+        let request = PutTimeseriesDataRequest::new("timeseries_demo_with_data").row(
+            TimeseriesRow::new()
+                .measurement_name("measure_11")
+                .datasource(&datasource)
+                .field_integer("temp", 123),
+        );
+        client.put_timeseries_data(request).send().await.unwrap();
+        let after_us = (current_time_ms() * 1000) as u64;
+
+        let request = GetTimeseriesDataRequest::new("timeseries_demo_with_data", key)
+            .begin_time_us(before_us - 60_000_000)
+            .end_time_us(after_us + 60_000_000);
+        let resp = client.get_timeseries_data(request).send().await.unwrap();
+
+        // 自动设置的时间戳在发送请求前后的时间之间
+        assert_eq!(1, resp.rows.len());
+        let ts_us = resp.rows[0].timestamp_us;
+        assert!(
+            (before_us..=after_us).contains(&ts_us),
+            "timestamp {} not in [{}, {}]",
+            ts_us,
+            before_us,
+            after_us
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_timeseries_data_auto_timestamp_read_back() {
+        test_put_timeseries_data_auto_timestamp_read_back_impl().await;
+    }
+
     async fn test_query_timeseries_meta_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -158,6 +329,40 @@ mod test_timeseries_data {
         test_query_timeseries_meta_impl().await
     }
 
+    async fn test_query_timeseries_meta_stream_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let condition = MetaQuery::Measurement(MeasurementMetaQuery::Equal("measure_7".to_string()));
+
+        let resp = client
+            .query_timeseries_meta(QueryTimeseriesMetaRequest::new("timeseries_demo_with_data", condition.clone()).get_total_hit(true))
+            .send()
+            .await
+            .unwrap();
+
+        let total_hit = resp.total_hit.unwrap();
+        assert!(total_hit > 2);
+
+        let req = QueryTimeseriesMetaRequest::new("timeseries_demo_with_data", condition).limit(2);
+        let stream = client.query_timeseries_meta(req).into_meta_stream();
+        tokio::pin!(stream);
+
+        let mut count = 0;
+        while let Some(meta) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            let meta = meta.unwrap();
+            assert_eq!(&Some("measure_7".to_string()), &meta.key.measurement_name);
+            count += 1;
+        }
+
+        assert_eq!(total_hit, count);
+    }
+
+    #[tokio::test]
+    async fn test_query_timeseries_meta_stream() {
+        test_query_timeseries_meta_stream_impl().await;
+    }
+
     async fn test_query_timeseries_meta_with_attributes_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -177,6 +382,47 @@ mod test_timeseries_data {
         test_query_timeseries_meta_with_attributes_impl().await
     }
 
+    async fn test_query_timeseries_meta_by_attribute_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let attr_value = format!("owner_{}", current_time_ms());
+
+        let req = UpdateTimeseriesMetaRequest::new("timeseries_demo_with_data").meta(
+            TimeseriesMeta::new()
+                .measurement_name("measure_13")
+                .datasource("data_13")
+                .attribute("owner", &attr_value),
+        );
+
+        client.update_timeseries_meta(req).send().await.unwrap();
+
+        // 时间线元数据的索引是异步更新的
+        tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+
+        let req = QueryTimeseriesMetaRequest::new(
+            "timeseries_demo_with_data",
+            MetaQuery::Attribute(AttributeMetaQuery::Equal("owner".to_string(), attr_value.clone())),
+        );
+
+        let resp = client.query_timeseries_meta(req).send().await.unwrap();
+        assert_eq!(1, resp.metas.len());
+        assert_eq!(&Some("measure_13".to_string()), &resp.metas[0].key.measurement_name);
+        assert_eq!(&Some("data_13".to_string()), &resp.metas[0].key.datasource);
+
+        let req = QueryTimeseriesMetaRequest::new(
+            "timeseries_demo_with_data",
+            MetaQuery::attribute_in("owner", ["no_such_owner", attr_value.as_str()]),
+        );
+        let resp = client.query_timeseries_meta(req).send().await.unwrap();
+        assert_eq!(1, resp.metas.len());
+    }
+
+    #[tokio::test]
+    async fn test_query_timeseries_meta_by_attribute() {
+        test_query_timeseries_meta_by_attribute_impl().await
+    }
+
     async fn test_update_timeseries_meta_impl() {
         setup();
 
@@ -201,6 +447,65 @@ mod test_timeseries_data {
         test_update_timeseries_meta_impl().await
     }
 
+    async fn test_update_timeseries_meta_delete_attribute_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let datasource = format!("data_{}", current_time_ms());
+        let condition = MetaQuery::Datasource(DatasourceMetaQuery::Equal(datasource.clone()));
+
+        let req = UpdateTimeseriesMetaRequest::new("timeseries_demo_with_data").meta(
+            TimeseriesMeta::new()
+                .measurement_name("measure_14")
+                .datasource(&datasource)
+                .attribute("attr1", "value1")
+                .attribute("attr2", "value2"),
+        );
+        client.update_timeseries_meta(req).send().await.unwrap();
+
+        // 时间线元数据的索引是异步更新的
+        tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+
+        let resp = client
+            .query_timeseries_meta(QueryTimeseriesMetaRequest::new("timeseries_demo_with_data", condition.clone()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(1, resp.metas.len());
+        assert_eq!(2, resp.metas[0].attributes.len());
+
+        let meta = resp.metas.into_iter().next().unwrap();
+        let meta = TimeseriesMeta { update_time_us: None, ..meta }.delete_attribute("attr1");
+
+        client
+            .update_timeseries_meta(UpdateTimeseriesMetaRequest::new("timeseries_demo_with_data").meta(meta))
+            .send()
+            .await
+            .unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+
+        let resp = client
+            .query_timeseries_meta(QueryTimeseriesMetaRequest::new("timeseries_demo_with_data", condition))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(1, resp.metas.len());
+
+        let attributes = &resp.metas[0].attributes;
+        assert_eq!(1, attributes.len());
+        assert_eq!(Some(&"value2".to_string()), attributes.get("attr2"));
+
+        let req =
+            DeleteTimeseriesMetaRequest::new("timeseries_demo_with_data").key(TimeseriesKey::new().measurement_name("measure_14").datasource(&datasource));
+        client.delete_timeseries_meta(req).send().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_timeseries_meta_delete_attribute() {
+        test_update_timeseries_meta_delete_attribute_impl().await
+    }
+
     async fn test_delete_timeseries_meta_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -218,6 +523,83 @@ mod test_timeseries_data {
         test_delete_timeseries_meta_impl().await;
     }
 
+    #[test]
+    fn test_delete_timeseries_meta_response_results() {
+        let resp = DeleteTimeseriesMetaResponse {
+            keys: vec![
+                TimeseriesKey::new().measurement_name("m").datasource("d1"),
+                TimeseriesKey::new().measurement_name("m").datasource("d2"),
+                TimeseriesKey::new().measurement_name("m").datasource("d3"),
+            ],
+            failed_rows: vec![crate::protos::timeseries::FailedRowInfo {
+                row_index: 1,
+                error_code: Some("OTSParameterInvalid".to_string()),
+                error_message: None,
+            }],
+        };
+
+        assert!(!resp.is_all_succeeded());
+
+        let results = resp.results();
+        assert_eq!(3, results.len());
+        assert!(results[0].1.is_ok());
+        assert_eq!(&Some("d2".to_string()), &results[1].0.datasource);
+        assert_eq!(Some("OTSParameterInvalid"), results[1].1.unwrap_err().error_code.as_deref());
+        assert!(results[2].1.is_ok());
+    }
+
+    async fn test_delete_timeseries_meta_batch_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let measurement = format!("measure_del_{}", current_time_ms());
+        let keys = (0..3)
+            .map(|i| TimeseriesKey::new().measurement_name(&measurement).datasource(format!("data_{}", i)))
+            .collect::<Vec<_>>();
+
+        let req = UpdateTimeseriesMetaRequest::new("timeseries_demo_with_data").metas(keys.iter().map(|k| {
+            TimeseriesMeta {
+                key: k.clone(),
+                ..Default::default()
+            }
+            .attribute("attr1", "value")
+        }));
+        client.update_timeseries_meta(req).send().await.unwrap();
+
+        // 时间线元数据的索引是异步更新的
+        tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+
+        let condition = MetaQuery::Measurement(MeasurementMetaQuery::Equal(measurement.clone()));
+        let resp = client
+            .query_timeseries_meta(QueryTimeseriesMetaRequest::new("timeseries_demo_with_data", condition.clone()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(3, resp.metas.len());
+
+        let resp = client
+            .delete_timeseries_meta(DeleteTimeseriesMetaRequest::new("timeseries_demo_with_data").keys(keys))
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.is_all_succeeded());
+        assert_eq!(3, resp.results().len());
+
+        tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+
+        let resp = client
+            .query_timeseries_meta(QueryTimeseriesMetaRequest::new("timeseries_demo_with_data", condition))
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.metas.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_timeseries_meta_batch() {
+        test_delete_timeseries_meta_batch_impl().await;
+    }
+
     #[tokio::test]
     async fn test_split_timeseries_scan_task() {
         setup();
@@ -245,4 +627,85 @@ mod test_timeseries_data {
     async fn test_scan_timeseries_data() {
         test_scan_timeseries_data_impl().await;
     }
+
+    async fn test_scan_timeseries_data_with_split_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        // 写入一组已知的时间线数据，所有分片扫描结果的并集应该正好是这些数据
+        let measurement = format!("measure_split_{}", current_time_ms());
+        let ts_us = (current_time_ms() * 1000) as u64;
+
+        let mut request = PutTimeseriesDataRequest::new("timeseries_demo_with_data");
+        let mut expected = HashSet::new();
+
+        for ds in 0..3 {
+            for i in 0..5 {
+                let datasource = format!("data_{}", ds);
+                request = request.row(
+                    TimeseriesRow::new()
+                        .measurement_name(&measurement)
+                        .datasource(&datasource)
+                        .timestamp_us(ts_us + i * 1000)
+                        .field_integer("seq", i as i64),
+                );
+                expected.insert((datasource, ts_us + i * 1000));
+            }
+        }
+
+        client.put_timeseries_data(request).send().await.unwrap();
+
+        let resp = client
+            .split_timeseries_scan_task(SplitTimeseriesScanTaskRequest::new("timeseries_demo_with_data", 2))
+            .send()
+            .await
+            .unwrap();
+
+        assert!(!resp.split_infos.is_empty());
+
+        let mut scanned = vec![];
+
+        for split_info in resp.split_infos {
+            let mut token = None;
+            let mut total_rows = 0;
+
+            loop {
+                let mut req = ScanTimeseriesDataRequest::new("timeseries_demo_with_data")
+                    .split_info(split_info.clone())
+                    .start_time_us(ts_us)
+                    .end_time_us(ts_us + 5 * 1000)
+                    .limit(1000);
+
+                if let Some(t) = token.take() {
+                    req = req.token(t);
+                }
+
+                let resp = client.scan_timeseries_data(req).send().await.unwrap();
+                total_rows += resp.rows.len();
+
+                scanned.extend(
+                    resp.rows
+                        .into_iter()
+                        .filter(|r| r.key.measurement_name.as_deref() == Some(measurement.as_str()))
+                        .map(|r| (r.key.datasource.unwrap_or_default(), r.timestamp_us)),
+                );
+
+                match resp.next_token {
+                    Some(t) if !t.is_empty() => token = Some(t),
+                    _ => break,
+                }
+            }
+
+            log::debug!("{} rows scanned in split", total_rows);
+        }
+
+        // 每一行只出现在一个分片中
+        assert_eq!(expected.len(), scanned.len());
+        assert_eq!(expected, scanned.into_iter().collect::<HashSet<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_scan_timeseries_data_with_split() {
+        test_scan_timeseries_data_with_split_impl().await;
+    }
 }
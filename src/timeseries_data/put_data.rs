@@ -5,6 +5,7 @@ use crate::{
     error::OtsError,
     protos::timeseries::MetaUpdateMode,
     timeseries_model::{self, encode_flatbuf_rows, rules::validate_timeseries_table_name, TimeseriesRow, SUPPORTED_TABLE_VERSION},
+    util::current_time_ms,
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
 
@@ -18,7 +19,7 @@ use crate::{
 /// 目前在写入数据的之后，暂时不支持设置 `meta_cache_update_time`，交给系统默认处理。
 ///
 /// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/puttimeseriesdata>
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct PutTimeseriesDataRequest {
     /// 时序表名称
     pub table_name: String,
@@ -28,6 +29,20 @@ pub struct PutTimeseriesDataRequest {
 
     /// 元数据更新模式
     pub meta_update_mode: Option<MetaUpdateMode>,
+
+    /// 没有设置时间戳（`timestamp_us` 为 `0`）的行，是否在发送请求时自动使用当前时间作为时间戳。默认为 `true`
+    pub auto_timestamp: bool,
+}
+
+impl Default for PutTimeseriesDataRequest {
+    fn default() -> Self {
+        Self {
+            table_name: String::new(),
+            rows: vec![],
+            meta_update_mode: None,
+            auto_timestamp: true,
+        }
+    }
 }
 
 impl PutTimeseriesDataRequest {
@@ -59,6 +74,14 @@ impl PutTimeseriesDataRequest {
         self
     }
 
+    /// 设置是否自动为没有时间戳的行填充当前时间。回填历史数据时建议关闭，
+    /// 关闭之后如果有行没有设置时间戳，发送请求前的校验会失败，避免写入时间戳为 `0` 的数据
+    pub fn auto_timestamp(mut self, auto_timestamp: bool) -> Self {
+        self.auto_timestamp = auto_timestamp;
+
+        self
+    }
+
     pub(crate) fn validate(&self) -> OtsResult<()> {
         if self.rows.is_empty() {
             return Err(OtsError::ValidationFailed("can not put empty rows to timeseries table".to_string()));
@@ -77,6 +100,13 @@ impl PutTimeseriesDataRequest {
 
         for row in &self.rows {
             row.validate()?;
+
+            if !self.auto_timestamp && row.timestamp_us == 0 {
+                return Err(OtsError::ValidationFailed(format!(
+                    "timestamp of row is not set and auto timestamp is disabled: {:?}",
+                    row.key
+                )));
+            }
         }
 
         Ok(())
@@ -87,10 +117,17 @@ impl From<PutTimeseriesDataRequest> for crate::protos::timeseries::PutTimeseries
     fn from(value: PutTimeseriesDataRequest) -> Self {
         let PutTimeseriesDataRequest {
             table_name,
-            rows,
+            mut rows,
             meta_update_mode,
+            auto_timestamp,
         } = value;
 
+        if auto_timestamp {
+            let now_us = (current_time_ms() * 1000) as u64;
+
+            rows.iter_mut().filter(|row| row.timestamp_us == 0).for_each(|row| row.timestamp_us = now_us);
+        }
+
         let bytes = encode_flatbuf_rows(rows.as_slice()).unwrap();
 
         let checksum = crc32c::crc32c(&bytes);
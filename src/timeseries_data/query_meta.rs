@@ -1,7 +1,9 @@
+use futures_core::Stream;
 use prost::Message;
 
 use crate::{
     add_per_request_options,
+    data::row_stream::{PagedRowOperation, RowStream},
     error::OtsError,
     timeseries_model::{rules::validate_timeseries_table_name, MetaQuery, TimeseriesMeta, SUPPORTED_TABLE_VERSION},
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
@@ -173,4 +175,38 @@ impl QueryTimeseriesMetaOperation {
 
         Ok(resp_msg.into())
     }
+
+    /// 转换成自动翻页的时间线元数据读取流。
+    ///
+    /// 在内部使用响应中的 `next_token` 继续发送请求，直到服务端不再返回 token 为止。
+    /// 请求中的 `limit` 作为每一页的条数。出错之后返回这个错误，然后结束。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let stream = client.query_timeseries_meta(request).into_meta_stream();
+    /// tokio::pin!(stream);
+    ///
+    /// while let Some(meta) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+    ///     let meta = meta?;
+    ///     println!("{:?}", meta);
+    /// }
+    /// ```
+    pub fn into_meta_stream(self) -> impl Stream<Item = OtsResult<TimeseriesMeta>> {
+        RowStream::new(self)
+    }
+}
+
+impl PagedRowOperation<TimeseriesMeta> for QueryTimeseriesMetaOperation {
+    async fn next_page(self) -> OtsResult<(Vec<TimeseriesMeta>, Option<Self>)> {
+        let mut next_op = self.clone();
+        let QueryTimeseriesMetaResponse { metas, next_token, .. } = self.send().await?;
+
+        let next = next_token.filter(|token| !token.is_empty()).map(|token| {
+            next_op.request.token = Some(token);
+            next_op
+        });
+
+        Ok((metas, next))
+    }
 }
@@ -5,7 +5,7 @@ use crate::{error::OtsError, OtsResult};
 use super::rules::{validate_timeseries_datasource, validate_timeseries_measurement, validate_timeseries_tag_name, validate_timeseries_tag_value};
 
 /// 时间线标识
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct TimeseriesKey {
     /// 度量名称
     pub measurement_name: Option<String>,
@@ -46,25 +46,44 @@ impl TimeseriesKey {
         self
     }
 
-    pub(crate) fn validate(&self) -> OtsResult<()> {
+    /// 校验时间线标识。标签中的双引号和等号会破坏时间线标识字符串（例如 `["cluster=c1","region=r1"]`），
+    /// 写入之后很难再查询或者删除，所以在写入、查询和删除之前都会校验。
+    ///
+    /// - 度量名称：长度为 1~128 字节，不能包含空白字符和 `#`
+    /// - 数据源：长度不超过 256 字节
+    /// - 标签名：长度为 1~128 的可见 ASCII 字符，不能包含 `"` 和 `=`
+    /// - 标签值：长度为 1~256 字节，不能包含 `"`、`=` 和控制字符
+    pub fn validate(&self) -> OtsResult<()> {
         if let Some(s) = &self.measurement_name {
             if !validate_timeseries_measurement(s) {
-                return Err(OtsError::ValidationFailed(format!("invalid measurement name: {}", s)));
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid measurement name: {:?}. it must be 1~128 bytes without whitespace and `#`",
+                    s
+                )));
             }
         }
 
         if let Some(s) = &self.datasource {
             if !validate_timeseries_datasource(s) {
-                return Err(OtsError::ValidationFailed(format!("invalid datasource: {}", s)));
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid datasource: {:?}. it must be no longer than 256 bytes",
+                    s
+                )));
             }
         }
 
         for (k, v) in &self.tags {
             if !validate_timeseries_tag_name(k) {
-                return Err(OtsError::ValidationFailed(format!("invalid tag name: {}", k)));
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid tag name: {:?}. it must be 1~128 visible ASCII characters without `\"` and `=`",
+                    k
+                )));
             }
             if !validate_timeseries_tag_value(v) {
-                return Err(OtsError::ValidationFailed(format!("invalid tag value: {}", v)));
+                return Err(OtsError::ValidationFailed(format!(
+                    "invalid value of tag {}: {:?}. it must be 1~256 bytes without `\"`, `=` and control characters",
+                    k, v
+                )));
             }
         }
 
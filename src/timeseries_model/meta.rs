@@ -49,6 +49,16 @@ impl TimeseriesMeta {
         self
     }
 
+    /// 删除一个属性。
+    ///
+    /// 更新时间线元数据的时候，服务端使用请求中的属性覆盖已有的全部属性，所以删除属性的时候，
+    /// 需要先查询出时间线元数据，删除属性之后再更新，没有包含在请求中的属性都会被删除
+    pub fn delete_attribute(mut self, key: &str) -> Self {
+        self.attributes.remove(key);
+
+        self
+    }
+
     /// 设置属性
     pub fn attributes(mut self, pairs: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>) -> Self {
         self.attributes = pairs.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
@@ -18,9 +18,15 @@ pub const SUPPORTED_TABLE_VERSION: i64 = 1;
 
 #[cfg(test)]
 mod test_timeseries_model {
-    use crate::test_util::setup;
+    use prost::Message;
 
-    use super::{encode_flatbuf_rows, TimeseriesRow};
+    use crate::{
+        model::ColumnValue,
+        protos::timeseries::{MetaQueryCompositeOperator, MetaQueryConditionType, MetaQuerySingleOperator},
+        test_util::setup,
+    };
+
+    use super::{encode_flatbuf_rows, AttributeMetaQuery, MetaQuery, TimeseriesKey, TimeseriesMeta, TimeseriesRow};
 
     #[test]
     fn test_flat_buffer_rows() {
@@ -40,4 +46,107 @@ mod test_timeseries_model {
 
         let _ = encode_flatbuf_rows(&rows);
     }
+
+    #[test]
+    fn test_get_field_value() {
+        let row = TimeseriesRow::new()
+            .measurement_name("cpu")
+            .datasource("host_1")
+            .field_integer("count", 42)
+            .field_double("usage", 0.75)
+            .field_string("state", "running")
+            .field_bool("healthy", true)
+            .field_blob("raw", vec![1u8, 2, 3]);
+
+        assert_eq!(Some(&ColumnValue::Integer(42)), row.get_field_value("count"));
+        assert_eq!(Some(42), row.get_field_integer("count"));
+        assert_eq!(Some(0.75), row.get_field_double("usage"));
+        assert_eq!(Some("running"), row.get_field_string("state"));
+        assert_eq!(Some(true), row.get_field_bool("healthy"));
+        assert_eq!(Some(&[1u8, 2, 3][..]), row.get_field_blob("raw"));
+
+        assert!(row.get_field_value("missing").is_none());
+        assert!(row.get_field_integer("usage").is_none());
+        assert!(row.get_field_string("count").is_none());
+    }
+
+    #[test]
+    fn test_delete_attribute() {
+        let meta = TimeseriesMeta::new()
+            .measurement_name("cpu")
+            .datasource("host_1")
+            .attribute("owner", "team_a")
+            .attribute("region", "cn-beijing")
+            .delete_attribute("owner")
+            .delete_attribute("not_exists");
+
+        let msg = crate::protos::timeseries::TimeseriesMeta::from(meta);
+        assert_eq!(Some("[\"region=cn-beijing\"]".to_string()), msg.attributes);
+
+        let meta = TimeseriesMeta::new().attribute("owner", "team_a").delete_attribute("owner");
+        let msg = crate::protos::timeseries::TimeseriesMeta::from(meta);
+        assert_eq!(Some("[]".to_string()), msg.attributes);
+    }
+
+    #[test]
+    fn test_attribute_meta_query() {
+        let msg =
+            crate::protos::timeseries::MetaQueryCondition::from(MetaQuery::Attribute(AttributeMetaQuery::Prefix("owner".to_string(), "team".to_string())));
+        assert_eq!(MetaQueryConditionType::AttributeCondition as i32, msg.r#type);
+
+        let cond = crate::protos::timeseries::MetaQueryAttributeCondition::decode(msg.proto_data.as_slice()).unwrap();
+        assert_eq!(MetaQuerySingleOperator::OpPrefix as i32, cond.op);
+        assert_eq!("owner", cond.attr_name);
+        assert_eq!("team", cond.value);
+
+        let q = MetaQuery::attribute_in("owner", ["team_a", "team_b"]);
+        assert!(q.validate().is_ok());
+
+        let msg = crate::protos::timeseries::MetaQueryCondition::from(q);
+        assert_eq!(MetaQueryConditionType::CompositeCondition as i32, msg.r#type);
+
+        let cond = crate::protos::timeseries::MetaQueryCompositeCondition::decode(msg.proto_data.as_slice()).unwrap();
+        assert_eq!(MetaQueryCompositeOperator::OpOr as i32, cond.op);
+
+        let values = cond
+            .sub_conditions
+            .iter()
+            .map(|c| {
+                assert_eq!(MetaQueryConditionType::AttributeCondition as i32, c.r#type);
+                let c = crate::protos::timeseries::MetaQueryAttributeCondition::decode(c.proto_data.as_slice()).unwrap();
+                assert_eq!(MetaQuerySingleOperator::OpEqual as i32, c.op);
+                assert_eq!("owner", c.attr_name);
+                c.value
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["team_a".to_string(), "team_b".to_string()], values);
+
+        assert!(MetaQuery::attribute_in("owner", Vec::<String>::new()).validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_timeseries_key() {
+        let key = TimeseriesKey::new()
+            .measurement_name("cpu")
+            .datasource("host_1")
+            .tag("cluster", "cluster_1")
+            .tag("region", "cn-beijing");
+        assert!(key.validate().is_ok());
+
+        assert!(TimeseriesKey::new().measurement_name("cpu usage").validate().is_err());
+        assert!(TimeseriesKey::new().measurement_name("cpu#1").validate().is_err());
+        assert!(TimeseriesKey::new().measurement_name("").validate().is_err());
+        assert!(TimeseriesKey::new().datasource("d".repeat(257)).validate().is_err());
+
+        assert!(TimeseriesKey::new().tag("clu=ster", "c1").validate().is_err());
+        assert!(TimeseriesKey::new().tag("clu\"ster", "c1").validate().is_err());
+        assert!(TimeseriesKey::new().tag("clu ster", "c1").validate().is_err());
+        assert!(TimeseriesKey::new().tag("cluster", "c=1").validate().is_err());
+        assert!(TimeseriesKey::new().tag("cluster", "\"c1\"").validate().is_err());
+        assert!(TimeseriesKey::new().tag("cluster", "c1\n").validate().is_err());
+        assert!(TimeseriesKey::new().tag("cluster", "").validate().is_err());
+
+        let row = TimeseriesRow::new().measurement_name("cpu").tag("cluster", "c=1").field_integer("usage", 1);
+        assert!(row.validate().is_err());
+    }
 }
@@ -156,10 +156,11 @@ impl From<TagMetaQuery> for crate::protos::timeseries::MetaQueryTagCondition {
     }
 }
 
-/// 标签查询。
-/// 变体中的 `.0` 是查询的属性名，`.1` 是查询的属性值
+/// 属性查询。
+/// 变体中的 `.0` 是查询的属性名，`.1` 是查询的属性值。
+/// 查询属性值在多个值之中的时间线可以使用 [`MetaQuery::attribute_in`]
 #[derive(Debug, Clone)]
-pub enum AttributMetaQuery {
+pub enum AttributeMetaQuery {
     Equal(String, String),
     GreaterThan(String, String),
     GreaterEqual(String, String),
@@ -168,40 +169,44 @@ pub enum AttributMetaQuery {
     Prefix(String, String),
 }
 
-impl From<AttributMetaQuery> for crate::protos::timeseries::MetaQueryAttributeCondition {
-    fn from(value: AttributMetaQuery) -> Self {
+/// 拼写错误的旧名称，保留用于兼容
+#[deprecated(note = "use `AttributeMetaQuery` instead")]
+pub type AttributMetaQuery = AttributeMetaQuery;
+
+impl From<AttributeMetaQuery> for crate::protos::timeseries::MetaQueryAttributeCondition {
+    fn from(value: AttributeMetaQuery) -> Self {
         match value {
-            AttributMetaQuery::Equal(attr_name, value) => Self {
+            AttributeMetaQuery::Equal(attr_name, value) => Self {
                 op: MetaQuerySingleOperator::OpEqual as i32,
                 attr_name,
                 value,
             },
 
-            AttributMetaQuery::GreaterThan(attr_name, value) => Self {
+            AttributeMetaQuery::GreaterThan(attr_name, value) => Self {
                 op: MetaQuerySingleOperator::OpGreaterThan as i32,
                 attr_name,
                 value,
             },
 
-            AttributMetaQuery::GreaterEqual(attr_name, value) => Self {
+            AttributeMetaQuery::GreaterEqual(attr_name, value) => Self {
                 op: MetaQuerySingleOperator::OpGreaterEqual as i32,
                 attr_name,
                 value,
             },
 
-            AttributMetaQuery::LessThan(attr_name, value) => Self {
+            AttributeMetaQuery::LessThan(attr_name, value) => Self {
                 op: MetaQuerySingleOperator::OpLessThan as i32,
                 attr_name,
                 value,
             },
 
-            AttributMetaQuery::LessEqual(attr_name, value) => Self {
+            AttributeMetaQuery::LessEqual(attr_name, value) => Self {
                 op: MetaQuerySingleOperator::OpLessEqual as i32,
                 attr_name,
                 value,
             },
 
-            AttributMetaQuery::Prefix(attr_name, value) => Self {
+            AttributeMetaQuery::Prefix(attr_name, value) => Self {
                 op: MetaQuerySingleOperator::OpPrefix as i32,
                 attr_name,
                 value,
@@ -336,7 +341,7 @@ pub enum MetaQuery {
     Measurement(MeasurementMetaQuery),
     Datasource(DatasourceMetaQuery),
     Tag(TagMetaQuery),
-    Attribute(AttributMetaQuery),
+    Attribute(AttributeMetaQuery),
     UpdateTime(UpdateTimeMetaQuery),
     Composite(Box<CompositeMetaQuery>),
 }
@@ -378,6 +383,20 @@ impl From<MetaQuery> for crate::protos::timeseries::MetaQueryCondition {
 }
 
 impl MetaQuery {
+    /// 查询属性 `attr_name` 的值等于 `values` 中任意一个的时间线。
+    /// 服务端没有 `IN` 操作符，这里构造成多个 `Equal` 查询的 `OR` 组合查询
+    pub fn attribute_in(attr_name: impl Into<String>, values: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let attr_name = attr_name.into();
+
+        Self::Composite(Box::new(
+            CompositeMetaQuery::new(MetaQueryCompositeOperator::OpOr).sub_queries(
+                values
+                    .into_iter()
+                    .map(|v| Self::Attribute(AttributeMetaQuery::Equal(attr_name.clone(), v.into()))),
+            ),
+        ))
+    }
+
     pub(crate) fn validate(&self) -> OtsResult<()> {
         match self {
             MetaQuery::UpdateTime(q) => {
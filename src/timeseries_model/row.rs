@@ -108,7 +108,40 @@ impl TimeseriesRow {
         self
     }
 
+    /// 获取给定名称的列的值。因为添加/更新列的方法名已经使用了 `field_xxx`，所以获取列值的方法以 `get_` 开头。
+    /// 同名的列出现多次的时候，返回第一个
+    pub fn get_field_value(&self, name: &str) -> Option<&ColumnValue> {
+        self.fields.iter().find(|c| c.name.as_str() == name).map(|c| &c.value)
+    }
+
+    /// 获取整数列的值。列不存在或者类型不是整数的时候返回 `None`
+    pub fn get_field_integer(&self, name: &str) -> Option<i64> {
+        self.get_field_value(name).and_then(ColumnValue::as_i64)
+    }
+
+    /// 获取双精度浮点数列的值。列不存在或者类型不是双精度浮点数的时候返回 `None`
+    pub fn get_field_double(&self, name: &str) -> Option<f64> {
+        self.get_field_value(name).and_then(ColumnValue::as_f64)
+    }
+
+    /// 获取字符串列的值。列不存在或者类型不是字符串的时候返回 `None`
+    pub fn get_field_string(&self, name: &str) -> Option<&str> {
+        self.get_field_value(name).and_then(ColumnValue::as_str)
+    }
+
+    /// 获取布尔值列的值。列不存在或者类型不是布尔值的时候返回 `None`
+    pub fn get_field_bool(&self, name: &str) -> Option<bool> {
+        self.get_field_value(name).and_then(ColumnValue::as_bool)
+    }
+
+    /// 获取二进制列的值。列不存在或者类型不是二进制的时候返回 `None`
+    pub fn get_field_blob(&self, name: &str) -> Option<&[u8]> {
+        self.get_field_value(name).and_then(ColumnValue::as_bytes)
+    }
+
     pub(crate) fn validate(&self) -> OtsResult<()> {
+        self.key.validate()?;
+
         for f in &self.fields {
             if !validate_timeseries_field_name(&f.name) {
                 return Err(OtsError::ValidationFailed(format!("invalid field name: {}", f.name)));
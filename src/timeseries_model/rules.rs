@@ -145,7 +145,7 @@ pub fn validate_timeseries_tag_name(name: &str) -> bool {
     }
 
     for c in name.chars() {
-        if !('!'..='~').contains(&c) {
+        if !('!'..='~').contains(&c) || c == '"' || c == '=' {
             return false;
         }
     }
@@ -157,7 +157,7 @@ pub fn validate_timeseries_tag_name(name: &str) -> bool {
 ///
 /// - 支持 UTF-8 编码的字符串
 /// - 长度不能超过256个字符
-/// - 不能包含双引号和等号
+/// - 不能包含双引号、等号和控制字符
 pub fn validate_timeseries_tag_value(value: &str) -> bool {
     if value.is_empty() {
         return false;
@@ -167,7 +167,7 @@ pub fn validate_timeseries_tag_value(value: &str) -> bool {
         return false;
     }
 
-    if value.contains('"') || value.contains('=') {
+    if value.contains('"') || value.contains('=') || value.chars().any(|c| c.is_control()) {
         return false;
     }
 
@@ -1,11 +1,57 @@
 use prost::Message;
 
 use crate::{
-    add_per_request_options, error::OtsError, protos::timeseries::DescribeTimeseriesTableResponse, timeseries_model::rules::validate_timeseries_table_name,
+    add_per_request_options,
+    error::OtsError,
+    protos::timeseries::{AnalyticalStoreSyncType, DescribeTimeseriesTableResponse},
+    timeseries_model::rules::validate_timeseries_table_name,
     OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
 };
 
-/// 获取时序表信息
+impl DescribeTimeseriesTableResponse {
+    /// 时序表的状态
+    pub fn status(&self) -> Option<&str> {
+        self.table_meta.status.as_deref()
+    }
+
+    /// 时序表关联的 lastpoint 索引的名称
+    pub fn lastpoint_index_names(&self) -> Vec<&str> {
+        self.lastpoint_indexes.iter().filter_map(|idx| idx.index_table_name.as_deref()).collect()
+    }
+
+    /// 时序表关联的分析存储的名称
+    pub fn analytical_store_names(&self) -> Vec<&str> {
+        self.analytical_stores.iter().filter_map(|store| store.store_name.as_deref()).collect()
+    }
+
+    /// 时序表关联的分析存储的名称和同步方式（全量或者增量）
+    pub fn analytical_store_sync_options(&self) -> Vec<(&str, Option<AnalyticalStoreSyncType>)> {
+        self.analytical_stores
+            .iter()
+            .filter_map(|store| {
+                store
+                    .store_name
+                    .as_deref()
+                    .map(|name| (name, store.sync_option.and_then(|n| AnalyticalStoreSyncType::try_from(n).ok())))
+            })
+            .collect()
+    }
+
+    /// 数据生命周期，单位为秒。`-1` 表示永不过期
+    pub fn ttl_seconds(&self) -> Option<i32> {
+        self.table_meta.table_options.as_ref().and_then(|opts| opts.time_to_live)
+    }
+
+    /// 时间线生命周期，单位为秒。`-1` 表示永不过期
+    pub fn meta_ttl_seconds(&self) -> Option<i32> {
+        self.table_meta.meta_options.as_ref().and_then(|opts| opts.meta_time_to_live)
+    }
+}
+
+/// 获取时序表信息。响应中除了表的元数据之外，还包含关联的 lastpoint 索引和分析存储的列表。
+/// 可以通过 [`status`](`DescribeTimeseriesTableResponse::status`) 获取表的状态，
+/// 通过 [`analytical_store_sync_options`](`DescribeTimeseriesTableResponse::analytical_store_sync_options`) 获取分析存储的同步方式。
+/// 分析存储的同步进度需要通过 [`describe_timeseries_analytical_store`](`crate::OtsClient::describe_timeseries_analytical_store`) 查询。
 ///
 /// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/describetimeseriestable>
 #[derive(Clone)]
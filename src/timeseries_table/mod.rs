@@ -15,10 +15,99 @@ pub use update_table::*;
 
 #[cfg(test)]
 mod test_timeseries_table {
-    use crate::{test_util::setup, OtsClient};
+    use crate::{
+        lastpoint_index::CreateTimeseriesLastpointIndexRequest,
+        protos::timeseries::{
+            AnalyticalStoreSyncType, DescribeTimeseriesTableResponse, TimeseriesAnalyticalStore, TimeseriesLastpointIndex, TimeseriesTableMeta,
+        },
+        test_util::setup,
+        timeseries_model::rules::{DEFAULT_ANALYTICAL_NAME, MIN_ANALYTICAL_STORE_TTL_SECONDS},
+        util::current_time_ms,
+        OtsClient,
+    };
 
     use super::{CreateTimeseriesTableRequest, UpdateTimeseriesTableRequest};
 
+    #[test]
+    fn test_create_timeseries_table_request() {
+        let request = CreateTimeseriesTableRequest::new("ts_with_schema")
+            .ttl_seconds(30 * 86400)
+            .meta_ttl_seconds(7 * 86400)
+            .analytical_store(TimeseriesAnalyticalStore {
+                store_name: None,
+                time_to_live: Some(MIN_ANALYTICAL_STORE_TTL_SECONDS),
+                sync_option: None,
+            })
+            .timeseries_keys(["_m_name", "host"])
+            .field_primary_key_string("sequence");
+
+        assert!(request.validate().is_ok());
+
+        let msg = crate::protos::timeseries::CreateTimeseriesTableRequest::from(request);
+        assert_eq!(Some(30 * 86400), msg.table_meta.table_options.unwrap().time_to_live);
+        assert_eq!(Some(7 * 86400), msg.table_meta.meta_options.unwrap().meta_time_to_live);
+        assert_eq!(vec!["_m_name".to_string(), "host".to_string()], msg.table_meta.timeseries_key_schema);
+        assert_eq!("sequence", msg.table_meta.field_primary_key_schema[0].name);
+
+        assert_eq!(Some(true), msg.enable_analytical_store);
+        assert_eq!(1, msg.analytical_stores.len());
+        assert_eq!(Some(DEFAULT_ANALYTICAL_NAME), msg.analytical_stores[0].store_name.as_deref());
+        assert_eq!(Some(MIN_ANALYTICAL_STORE_TTL_SECONDS), msg.analytical_stores[0].time_to_live);
+
+        assert!(CreateTimeseriesTableRequest::new("ts_bad_ttl").ttl_seconds(3600).validate().is_err());
+        assert!(CreateTimeseriesTableRequest::new("ts_bad_ttl").meta_ttl_seconds(86400).validate().is_err());
+    }
+
+    #[test]
+    fn test_describe_timeseries_table_response_accessors() {
+        let resp = DescribeTimeseriesTableResponse {
+            table_meta: TimeseriesTableMeta {
+                table_name: "ts".to_string(),
+                status: Some("CREATED".to_string()),
+                ..Default::default()
+            },
+            analytical_stores: vec![TimeseriesAnalyticalStore {
+                store_name: Some(DEFAULT_ANALYTICAL_NAME.to_string()),
+                time_to_live: None,
+                sync_option: Some(AnalyticalStoreSyncType::SyncTypeIncr as i32),
+            }],
+            lastpoint_indexes: vec![TimeseriesLastpointIndex {
+                index_table_name: Some("my_lpi".to_string()),
+            }],
+        };
+
+        assert_eq!(Some("CREATED"), resp.status());
+        assert_eq!(vec!["my_lpi"], resp.lastpoint_index_names());
+        assert_eq!(vec![DEFAULT_ANALYTICAL_NAME], resp.analytical_store_names());
+        assert_eq!(
+            vec![(DEFAULT_ANALYTICAL_NAME, Some(AnalyticalStoreSyncType::SyncTypeIncr))],
+            resp.analytical_store_sync_options()
+        );
+    }
+
+    async fn test_create_timeseries_table_with_ttl_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let table_name = format!("ts_ttl_{}", current_time_ms());
+        let request = CreateTimeseriesTableRequest::new(&table_name).ttl_seconds(30 * 86400).meta_ttl_seconds(-1);
+
+        client.create_timeseries_table(request).send().await.unwrap();
+
+        let resp = client.describe_timeseries_table(&table_name).send().await.unwrap();
+        log::debug!("{:?}", resp);
+
+        assert_eq!(Some(30 * 86400), resp.ttl_seconds());
+        assert_eq!(Some(-1), resp.meta_ttl_seconds());
+
+        client.delete_timeseries_table(&table_name).send().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_timeseries_table_with_ttl() {
+        test_create_timeseries_table_with_ttl_impl().await;
+    }
+
     async fn test_create_timeseries_table_impl() {
         setup();
         let client = OtsClient::from_env();
@@ -46,6 +135,40 @@ mod test_timeseries_table {
         log::debug!("{:?}", resp);
     }
 
+    /// 测试时序表信息中包含 lastpoint 索引
+    async fn test_desc_timeseries_table_with_lastpoint_index_impl() {
+        setup();
+        let client = OtsClient::from_env();
+
+        let table_name = "timeseries_demo_with_data";
+        let index_name = format!("lpi_desc_{}", current_time_ms());
+        client
+            .create_timeseries_lastpoint_index(CreateTimeseriesLastpointIndexRequest::new(table_name, &index_name))
+            .send()
+            .await
+            .unwrap();
+
+        let resp = client.describe_timeseries_table(table_name).send().await;
+        log::debug!("{:?}", resp);
+
+        client.delete_timeseries_lastpoint_index(table_name, &index_name).send().await.unwrap();
+
+        assert!(resp.is_ok());
+
+        let resp = resp.unwrap();
+
+        log::debug!("lastpoint indexes: {:?}", resp.lastpoint_index_names());
+        log::debug!("analytical stores: {:?}", resp.analytical_store_sync_options());
+        log::debug!("status: {:?}", resp.status());
+
+        assert!(resp.lastpoint_index_names().contains(&index_name.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_desc_timeseries_table_with_lastpoint_index() {
+        test_desc_timeseries_table_with_lastpoint_index_impl().await;
+    }
+
     #[tokio::test]
     async fn test_list_timeseries_table() {
         setup();
@@ -0,0 +1,117 @@
+use prost::Message;
+
+use crate::{
+    add_per_request_options, error::OtsError, model::rules::validate_table_name, protos::tunnel::TunnelType, OtsClient, OtsOp, OtsRequest, OtsRequestOptions,
+    OtsResult,
+};
+
+/// 创建通道的请求
+#[derive(Debug, Clone)]
+pub struct CreateTunnelRequest {
+    /// 表名
+    pub table_name: String,
+
+    /// 通道名称
+    pub tunnel_name: String,
+
+    /// 通道类型。默认为 `BaseAndStream`，即先读取全量数据，然后读取增量数据
+    pub tunnel_type: TunnelType,
+}
+
+impl CreateTunnelRequest {
+    pub fn new(table_name: &str, tunnel_name: &str) -> Self {
+        Self {
+            table_name: table_name.to_string(),
+            tunnel_name: tunnel_name.to_string(),
+            tunnel_type: TunnelType::BaseAndStream,
+        }
+    }
+
+    /// 设置通道类型
+    pub fn tunnel_type(mut self, tunnel_type: TunnelType) -> Self {
+        self.tunnel_type = tunnel_type;
+
+        self
+    }
+
+    pub(crate) fn validate(&self) -> OtsResult<()> {
+        if !validate_table_name(&self.table_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid table name: {}", self.table_name)));
+        }
+
+        if !validate_table_name(&self.tunnel_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid tunnel name: {}", self.tunnel_name)));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<CreateTunnelRequest> for crate::protos::tunnel::CreateTunnelRequest {
+    fn from(value: CreateTunnelRequest) -> Self {
+        let CreateTunnelRequest {
+            table_name,
+            tunnel_name,
+            tunnel_type,
+        } = value;
+
+        crate::protos::tunnel::CreateTunnelRequest {
+            tunnel: crate::protos::tunnel::Tunnel {
+                table_name,
+                tunnel_name,
+                tunnel_type: tunnel_type.as_str_name().to_string(),
+                stream_tunnel_config: None,
+                need_all_timeseries_index: None,
+            },
+        }
+    }
+}
+
+/// 创建通道的响应
+#[derive(Debug, Default, Clone)]
+pub struct CreateTunnelResponse {
+    /// 通道 ID
+    pub tunnel_id: String,
+}
+
+/// 创建通道。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/create-a-tunnel>
+#[derive(Clone)]
+pub struct CreateTunnelOperation {
+    client: OtsClient,
+    request: CreateTunnelRequest,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(CreateTunnelOperation);
+
+impl CreateTunnelOperation {
+    pub(crate) fn new(client: OtsClient, request: CreateTunnelRequest) -> Self {
+        Self {
+            client,
+            request,
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    pub async fn send(self) -> OtsResult<CreateTunnelResponse> {
+        self.request.validate()?;
+
+        let Self { client, request, options } = self;
+
+        let msg = crate::protos::tunnel::CreateTunnelRequest::from(request);
+
+        let req = OtsRequest {
+            operation: OtsOp::CreateTunnel,
+            body: msg.encode_to_vec(),
+            options,
+            ..Default::default()
+        };
+
+        let response = client.send(req).await?;
+        let msg = crate::protos::tunnel::CreateTunnelResponse::decode(response.bytes().await?)?;
+
+        Ok(CreateTunnelResponse { tunnel_id: msg.tunnel_id })
+    }
+}
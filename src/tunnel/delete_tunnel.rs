@@ -0,0 +1,85 @@
+use prost::Message;
+
+use crate::{
+    add_per_request_options,
+    error::{ApiErrorCode, OtsError},
+    model::rules::validate_table_name,
+    OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult,
+};
+
+/// 删除通道。
+///
+/// 通道不存在的时候返回 [`OtsError::TunnelNotExist`]。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/delete-a-tunnel>
+#[derive(Clone)]
+pub struct DeleteTunnelOperation {
+    client: OtsClient,
+    table_name: String,
+    tunnel_name: String,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(DeleteTunnelOperation);
+
+impl DeleteTunnelOperation {
+    pub(crate) fn new(client: OtsClient, table_name: &str, tunnel_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            tunnel_name: tunnel_name.to_string(),
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    pub async fn send(self) -> OtsResult<()> {
+        let Self {
+            client,
+            table_name,
+            tunnel_name,
+            options,
+        } = self;
+
+        if !validate_table_name(&table_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid table name: {}", table_name)));
+        }
+
+        if !validate_table_name(&tunnel_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid tunnel name: {}", tunnel_name)));
+        }
+
+        let msg = crate::protos::tunnel::DeleteTunnelRequest {
+            table_name: table_name.clone(),
+            tunnel_name: tunnel_name.clone(),
+            tunnel_id: None,
+        };
+
+        let req = OtsRequest {
+            operation: OtsOp::DeleteTunnel,
+            body: msg.encode_to_vec(),
+            options,
+            ..Default::default()
+        };
+
+        match client.send(req).await {
+            Ok(response) => {
+                response.bytes().await?;
+                Ok(())
+            }
+
+            Err(e) => Err(map_not_exist_error(e, table_name, tunnel_name)),
+        }
+    }
+}
+
+/// 把服务端返回的对象不存在错误转换为 [`OtsError::TunnelNotExist`]
+pub(crate) fn map_not_exist_error(e: OtsError, table_name: String, tunnel_name: String) -> OtsError {
+    match e {
+        OtsError::ApiError(_, request_id) if e.code() == Some(ApiErrorCode::ObjectNotExist) => OtsError::TunnelNotExist {
+            table_name,
+            tunnel_name,
+            request_id,
+        },
+        _ => e,
+    }
+}
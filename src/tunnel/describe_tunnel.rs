@@ -0,0 +1,130 @@
+use prost::Message;
+
+use crate::{add_per_request_options, error::OtsError, model::rules::validate_table_name, OtsClient, OtsOp, OtsRequest, OtsRequestOptions, OtsResult};
+
+use super::TunnelInfo;
+
+/// 通道中一个数据通道（channel）的信息。每个数据通道对应一个全量数据分片或者一个 Stream 分区
+#[derive(Debug, Default, Clone)]
+pub struct ChannelInfo {
+    /// 数据通道 ID
+    pub channel_id: String,
+
+    /// 数据通道类型，例如 `BaseData`、`Stream`
+    pub channel_type: Option<String>,
+
+    /// 数据通道状态，例如 `WAIT`、`OPEN`、`CLOSING`、`CLOSE`、`TERMINATED`
+    pub channel_status: Option<String>,
+
+    /// 正在消费这个数据通道的客户端 ID
+    pub client_id: Option<String>,
+
+    /// 数据通道的消费位点，即已经消费到的数据的时间戳。单位为毫秒
+    pub channel_rpo: Option<i64>,
+}
+
+impl From<crate::protos::tunnel::ChannelInfo> for ChannelInfo {
+    fn from(value: crate::protos::tunnel::ChannelInfo) -> Self {
+        let crate::protos::tunnel::ChannelInfo {
+            channel_id,
+            channel_type,
+            channel_status,
+            client_id,
+            channel_rpo,
+        } = value;
+
+        Self {
+            channel_id,
+            channel_type,
+            channel_status,
+            client_id,
+            channel_rpo,
+        }
+    }
+}
+
+/// 查询通道详细信息的响应
+#[derive(Debug, Clone)]
+pub struct DescribeTunnelResponse {
+    /// 通道信息
+    pub tunnel: TunnelInfo,
+
+    /// 数据通道信息
+    pub channels: Vec<ChannelInfo>,
+
+    /// 通道的消费位点，即所有数据通道中最小的消费位点。单位为毫秒
+    pub tunnel_rpo: Option<i64>,
+}
+
+impl TryFrom<crate::protos::tunnel::DescribeTunnelResponse> for DescribeTunnelResponse {
+    type Error = OtsError;
+
+    fn try_from(value: crate::protos::tunnel::DescribeTunnelResponse) -> Result<Self, Self::Error> {
+        let crate::protos::tunnel::DescribeTunnelResponse { tunnel, channels, tunnel_rpo } = value;
+
+        Ok(Self {
+            tunnel: TunnelInfo::try_from(tunnel)?,
+            channels: channels.into_iter().map(ChannelInfo::from).collect(),
+            tunnel_rpo,
+        })
+    }
+}
+
+/// 查询通道的详细信息，包括每个数据通道的消费状态。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/query-the-information-about-a-tunnel>
+#[derive(Clone)]
+pub struct DescribeTunnelOperation {
+    client: OtsClient,
+    table_name: String,
+    tunnel_name: String,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(DescribeTunnelOperation);
+
+impl DescribeTunnelOperation {
+    pub(crate) fn new(client: OtsClient, table_name: &str, tunnel_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            tunnel_name: tunnel_name.to_string(),
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    pub async fn send(self) -> OtsResult<DescribeTunnelResponse> {
+        let Self {
+            client,
+            table_name,
+            tunnel_name,
+            options,
+        } = self;
+
+        if !validate_table_name(&table_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid table name: {}", table_name)));
+        }
+
+        if !validate_table_name(&tunnel_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid tunnel name: {}", tunnel_name)));
+        }
+
+        let msg = crate::protos::tunnel::DescribeTunnelRequest {
+            table_name,
+            tunnel_name,
+            tunnel_id: None,
+        };
+
+        let req = OtsRequest {
+            operation: OtsOp::DescribeTunnel,
+            body: msg.encode_to_vec(),
+            options,
+            ..Default::default()
+        };
+
+        let response = client.send(req).await?;
+        let msg = crate::protos::tunnel::DescribeTunnelResponse::decode(response.bytes().await?)?;
+
+        DescribeTunnelResponse::try_from(msg)
+    }
+}
@@ -0,0 +1,118 @@
+use prost::Message;
+
+use crate::{
+    add_per_request_options, error::OtsError, model::rules::validate_table_name, protos::tunnel::TunnelType, OtsClient, OtsOp, OtsRequest, OtsRequestOptions,
+    OtsResult,
+};
+
+/// 通道信息
+#[derive(Debug, Clone)]
+pub struct TunnelInfo {
+    /// 通道 ID
+    pub tunnel_id: String,
+
+    /// 通道名称
+    pub tunnel_name: String,
+
+    /// 通道类型
+    pub tunnel_type: TunnelType,
+
+    /// 通道所处的阶段，例如 `InitBaseDataAndStreamShard`、`ProcessBaseData`、`ProcessStream`
+    pub stage: String,
+
+    /// 表名
+    pub table_name: String,
+
+    /// 实例名
+    pub instance_name: String,
+
+    /// 通道使用的 Stream ID
+    pub stream_id: String,
+
+    /// 通道是否已经过期
+    pub expired: bool,
+
+    /// 通道的创建时间
+    pub create_time: Option<i64>,
+}
+
+impl TryFrom<crate::protos::tunnel::TunnelInfo> for TunnelInfo {
+    type Error = OtsError;
+
+    fn try_from(value: crate::protos::tunnel::TunnelInfo) -> Result<Self, Self::Error> {
+        let crate::protos::tunnel::TunnelInfo {
+            tunnel_id,
+            tunnel_type,
+            table_name,
+            instance_name,
+            stream_id,
+            stage,
+            expired,
+            tunnel_name,
+            public: _,
+            stream_tunnel_config: _,
+            create_time,
+        } = value;
+
+        let tunnel_type = match TunnelType::from_str_name(&tunnel_type) {
+            Some(t) => t,
+            None => return Err(OtsError::ValidationFailed(format!("invalid tunnel type: {}", tunnel_type))),
+        };
+
+        Ok(Self {
+            tunnel_id,
+            tunnel_name: tunnel_name.unwrap_or_default(),
+            tunnel_type,
+            stage,
+            table_name,
+            instance_name,
+            stream_id,
+            expired: expired.unwrap_or_default(),
+            create_time,
+        })
+    }
+}
+
+/// 列出一个表上的通道。
+///
+/// 官方文档：<https://help.aliyun.com/zh/tablestore/developer-reference/query-information-about-all-tunnels-of-a-data-table>
+#[derive(Clone)]
+pub struct ListTunnelOperation {
+    client: OtsClient,
+    table_name: String,
+    options: OtsRequestOptions,
+}
+
+add_per_request_options!(ListTunnelOperation);
+
+impl ListTunnelOperation {
+    pub(crate) fn new(client: OtsClient, table_name: &str) -> Self {
+        Self {
+            client,
+            table_name: table_name.to_string(),
+            options: OtsRequestOptions::default(),
+        }
+    }
+
+    pub async fn send(self) -> OtsResult<Vec<TunnelInfo>> {
+        let Self { client, table_name, options } = self;
+
+        if !validate_table_name(&table_name) {
+            return Err(OtsError::ValidationFailed(format!("invalid table name: {}", table_name)));
+        }
+
+        let msg = crate::protos::tunnel::ListTunnelRequest { table_name };
+
+        let req = OtsRequest {
+            operation: OtsOp::ListTunnel,
+            body: msg.encode_to_vec(),
+            options,
+            ..Default::default()
+        };
+
+        let response = client.send(req).await?;
+        let msg = crate::protos::tunnel::ListTunnelResponse::decode(response.bytes().await?)?;
+
+        msg.tunnels.into_iter().map(TunnelInfo::try_from).collect()
+    }
+}
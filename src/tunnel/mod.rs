@@ -0,0 +1,334 @@
+//! 通道服务（Tunnel）模块
+
+mod create_tunnel;
+mod delete_tunnel;
+mod describe_tunnel;
+mod list_tunnel;
+mod tunnel_worker;
+
+pub use create_tunnel::*;
+pub use delete_tunnel::*;
+pub use describe_tunnel::*;
+pub use list_tunnel::*;
+pub use tunnel_worker::*;
+
+#[cfg(test)]
+mod test_tunnel {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        error::OtsError,
+        model::Row,
+        protos,
+        protos::plain_buffer::MASK_HEADER,
+        protos::tunnel::{Channel, ChannelStatus, TunnelType},
+        stream::StreamRecord,
+        test_util::setup,
+        OtsClient, OtsOp,
+    };
+
+    use super::{
+        delete_tunnel::map_not_exist_error,
+        tunnel_worker::{apply_heartbeat, ChannelState},
+        CreateTunnelRequest, DescribeTunnelResponse,
+    };
+
+    async fn test_create_tunnel_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let resp = client
+            .create_tunnel(CreateTunnelRequest::new("data_types", "data_types_tunnel").tunnel_type(TunnelType::Stream))
+            .send()
+            .await;
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+        assert!(!resp.unwrap().tunnel_id.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_tunnel() {
+        test_create_tunnel_impl().await
+    }
+
+    #[test]
+    fn test_create_tunnel_request() {
+        assert!(CreateTunnelRequest::new("data_types", "data_types_tunnel").validate().is_ok());
+        assert!(CreateTunnelRequest::new("data_types", "").validate().is_err());
+        assert!(CreateTunnelRequest::new("data_types", "1tunnel").validate().is_err());
+        assert!(CreateTunnelRequest::new("data_types", "bad-tunnel").validate().is_err());
+        assert!(CreateTunnelRequest::new("", "data_types_tunnel").validate().is_err());
+
+        let msg = crate::protos::tunnel::CreateTunnelRequest::from(CreateTunnelRequest::new("data_types", "data_types_tunnel"));
+        assert_eq!("BaseAndStream", msg.tunnel.tunnel_type);
+
+        let msg = crate::protos::tunnel::CreateTunnelRequest::from(CreateTunnelRequest::new("data_types", "t").tunnel_type(TunnelType::BaseData));
+        assert_eq!("BaseData", msg.tunnel.tunnel_type);
+
+        assert_eq!("tunnel/create", OtsOp::CreateTunnel.path());
+        assert_eq!("GetRow", OtsOp::GetRow.path());
+    }
+
+    async fn test_list_and_describe_tunnel_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let resp = client.list_tunnel("data_types").send().await;
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+
+        let tunnels = resp.unwrap();
+        assert!(!tunnels.is_empty());
+        assert!(tunnels.iter().all(|t| t.table_name == "data_types"));
+
+        let resp = client.describe_tunnel("data_types", &tunnels[0].tunnel_name).send().await;
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+        assert_eq!(tunnels[0].tunnel_id, resp.unwrap().tunnel.tunnel_id);
+    }
+
+    #[tokio::test]
+    async fn test_list_and_describe_tunnel() {
+        test_list_and_describe_tunnel_impl().await
+    }
+
+    #[test]
+    fn test_describe_tunnel_response() {
+        let msg = crate::protos::tunnel::DescribeTunnelResponse {
+            tunnel: crate::protos::tunnel::TunnelInfo {
+                tunnel_id: "tunnel-id".to_string(),
+                tunnel_type: "Stream".to_string(),
+                table_name: "data_types".to_string(),
+                instance_name: "instance".to_string(),
+                stream_id: "stream-id".to_string(),
+                stage: "ProcessStream".to_string(),
+                expired: None,
+                tunnel_name: Some("data_types_tunnel".to_string()),
+                public: None,
+                stream_tunnel_config: None,
+                create_time: Some(1),
+            },
+            channels: vec![crate::protos::tunnel::ChannelInfo {
+                channel_id: "channel-id".to_string(),
+                channel_type: Some("Stream".to_string()),
+                channel_status: Some("OPEN".to_string()),
+                client_id: Some("client-id".to_string()),
+                channel_rpo: Some(100),
+            }],
+            tunnel_rpo: Some(100),
+        };
+
+        let resp = DescribeTunnelResponse::try_from(msg.clone()).unwrap();
+        assert_eq!(TunnelType::Stream, resp.tunnel.tunnel_type);
+        assert_eq!("data_types_tunnel", resp.tunnel.tunnel_name);
+        assert!(!resp.tunnel.expired);
+        assert_eq!(1, resp.channels.len());
+        assert_eq!(Some("OPEN".to_string()), resp.channels[0].channel_status);
+        assert_eq!(Some(100), resp.channels[0].channel_rpo);
+
+        let mut msg = msg;
+        msg.tunnel.tunnel_type = "Unknown".to_string();
+        assert!(DescribeTunnelResponse::try_from(msg).is_err());
+
+        assert!(OtsOp::ListTunnel.is_idempotent());
+        assert!(OtsOp::DescribeTunnel.is_idempotent());
+        assert!(!OtsOp::CreateTunnel.is_idempotent());
+    }
+
+    async fn test_delete_tunnel_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let tunnel_name = "data_types_tunnel_to_delete";
+
+        client
+            .create_tunnel(CreateTunnelRequest::new("data_types", tunnel_name).tunnel_type(TunnelType::Stream))
+            .send()
+            .await
+            .unwrap();
+
+        let tunnels = client.list_tunnel("data_types").send().await.unwrap();
+        assert!(tunnels.iter().any(|t| t.tunnel_name == tunnel_name));
+
+        let resp = client.delete_tunnel("data_types", tunnel_name).send().await;
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+
+        let tunnels = client.list_tunnel("data_types").send().await.unwrap();
+        assert!(!tunnels.iter().any(|t| t.tunnel_name == tunnel_name));
+
+        let resp = client.delete_tunnel("data_types", tunnel_name).send().await;
+        assert!(matches!(resp, Err(OtsError::TunnelNotExist { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_delete_tunnel() {
+        test_delete_tunnel_impl().await
+    }
+
+    #[test]
+    fn test_delete_tunnel_not_exist_error() {
+        let api_error = |code: &str| {
+            OtsError::ApiError(
+                Box::new(protos::Error {
+                    code: code.to_string(),
+                    message: None,
+                    access_denied_detail: None,
+                }),
+                Some("request-1".to_string()),
+            )
+        };
+
+        let e = map_not_exist_error(api_error("OTSObjectNotExist"), "data_types".to_string(), "t".to_string());
+        assert!(matches!(&e, OtsError::TunnelNotExist { table_name, tunnel_name, .. } if table_name == "data_types" && tunnel_name == "t"));
+        assert_eq!(Some("request-1"), e.request_id());
+
+        let e = map_not_exist_error(api_error("OTSParameterInvalid"), "data_types".to_string(), "t".to_string());
+        assert!(matches!(e, OtsError::ApiError(..)));
+    }
+
+    async fn test_tunnel_worker_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let tunnels = client.list_tunnel("data_types").send().await.unwrap();
+        assert!(!tunnels.is_empty());
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+        let mut total = 0usize;
+        let worker = client
+            .tunnel_worker(&tunnels[0].tunnel_id, |rows| {
+                total += rows.len();
+                Ok(())
+            })
+            .poll_interval_ms(200)
+            .shutdown_signal(shutdown_rx);
+
+        let stop = async move {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            shutdown_tx.send(true).unwrap();
+        };
+
+        let (resp, _) = tokio::join!(worker.run(), stop);
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+        log::debug!("{} rows consumed", total);
+    }
+
+    #[tokio::test]
+    async fn test_tunnel_worker() {
+        test_tunnel_worker_impl().await
+    }
+
+    async fn test_tunnel_record_worker_impl() {
+        setup();
+
+        let client = OtsClient::from_env();
+        let tunnels = client.list_tunnel("data_types").send().await.unwrap();
+        assert!(!tunnels.is_empty());
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+        let mut total = 0usize;
+        let worker = client
+            .tunnel_record_worker(&tunnels[0].tunnel_id, |records| {
+                for record in &records {
+                    log::debug!("{:?}: {:?}", record.action_type(), record.row().primary_key);
+                }
+                total += records.len();
+                Ok(())
+            })
+            .poll_interval_ms(200)
+            .shutdown_signal(shutdown_rx);
+
+        let stop = async move {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            shutdown_tx.send(true).unwrap();
+        };
+
+        let (resp, _) = tokio::join!(worker.run(), stop);
+        log::debug!("{:#?}", resp);
+        assert!(resp.is_ok());
+        log::debug!("{} records consumed", total);
+    }
+
+    #[tokio::test]
+    async fn test_tunnel_record_worker() {
+        test_tunnel_record_worker_impl().await
+    }
+
+    #[test]
+    fn test_tunnel_record() {
+        let row = Row::new().primary_key_column_string("str_id", "1").column_integer("int_col", 1);
+
+        let record = protos::tunnel::Record {
+            action_type: protos::tunnel::ActionType::UpdateRow as i32,
+            record: row.encode_plain_buffer(MASK_HEADER),
+        };
+
+        let record = StreamRecord::try_from(record).unwrap();
+        assert!(matches!(record, StreamRecord::Update(_)));
+        assert_eq!(row.primary_key, record.row().primary_key);
+
+        let record = protos::tunnel::Record {
+            action_type: 0,
+            record: row.encode_plain_buffer(MASK_HEADER),
+        };
+        assert!(StreamRecord::try_from(record).is_err());
+    }
+
+    #[test]
+    fn test_apply_heartbeat() {
+        let channel = |id: &str, version: i64, status: ChannelStatus| Channel {
+            channel_id: id.to_string(),
+            version,
+            status: status as i32,
+            detail: None,
+        };
+
+        let mut channels = BTreeMap::new();
+
+        // 新分配的数据通道
+        apply_heartbeat(&mut channels, vec![channel("a", 1, ChannelStatus::Open), channel("b", 1, ChannelStatus::Open)]);
+        assert_eq!(2, channels.len());
+        assert!(channels.values().all(|ch| ch.status == ChannelStatus::Open && ch.token.is_none()));
+
+        channels.get_mut("a").unwrap().token = Some("token-a".to_string());
+
+        // 客户端把 b 标记为结束之后，服务端返回旧的版本号，以客户端为准
+        let b = channels.get_mut("b").unwrap();
+        b.status = ChannelStatus::Terminated;
+        b.version = 2;
+
+        apply_heartbeat(&mut channels, vec![channel("a", 1, ChannelStatus::Open), channel("b", 1, ChannelStatus::Open)]);
+        assert_eq!(Some("token-a".to_string()), channels["a"].token);
+        assert_eq!(ChannelStatus::Terminated, channels["b"].status);
+
+        // 服务端确认 b 结束，要求关闭 a，分配新的 c
+        apply_heartbeat(
+            &mut channels,
+            vec![
+                channel("a", 2, ChannelStatus::Closing),
+                channel("b", 2, ChannelStatus::Terminated),
+                channel("c", 1, ChannelStatus::Open),
+            ],
+        );
+
+        assert_eq!(2, channels.len());
+        assert_eq!(
+            ChannelState {
+                version: 3,
+                status: ChannelStatus::Close,
+                token: None,
+                sequence_number: 0,
+            },
+            channels["a"]
+        );
+        assert_eq!(ChannelStatus::Open, channels["c"].status);
+
+        // 响应中没有的数据通道不再持有
+        apply_heartbeat(&mut channels, vec![channel("c", 1, ChannelStatus::Open)]);
+        assert_eq!(vec!["c"], channels.keys().map(|k| k.as_str()).collect::<Vec<_>>());
+    }
+}
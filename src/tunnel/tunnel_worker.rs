@@ -0,0 +1,361 @@
+use std::collections::BTreeMap;
+
+use prost::Message;
+use tokio::{sync::watch::Receiver, time::Instant};
+
+use crate::{
+    error::OtsError,
+    model::Row,
+    protos::{
+        plain_buffer::MASK_HEADER,
+        tunnel::{ActionType, ChannelStatus},
+    },
+    stream::StreamRecord,
+    util::sleep_or_shutdown,
+    OtsClient, OtsOp, OtsRequest, OtsResult,
+};
+
+/// 数据通道读取完毕之后，服务端返回的 token
+const FINISH_TAG: &str = "finished";
+
+/// 默认的心跳间隔，单位为毫秒
+const DEFAULT_HEARTBEAT_INTERVAL_MS: u64 = 30000;
+
+/// 默认的心跳超时时间，单位为秒。超过这个时间没有心跳，服务端会把数据通道分配给其他客户端
+const DEFAULT_HEARTBEAT_TIMEOUT_SECONDS: i64 = 300;
+
+/// 默认的轮询间隔，单位为毫秒
+const DEFAULT_POLL_INTERVAL_MS: u64 = 1000;
+
+/// 客户端持有的一个数据通道的状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ChannelState {
+    pub version: i64,
+    pub status: ChannelStatus,
+
+    /// 下一次读取使用的 token。为 `None` 表示还没有获取检查点
+    pub token: Option<String>,
+
+    /// 检查点的序号，每次记录检查点都要递增
+    pub sequence_number: i64,
+}
+
+impl ChannelState {
+    fn new(version: i64, status: ChannelStatus) -> Self {
+        Self {
+            version,
+            status,
+            token: None,
+            sequence_number: 0,
+        }
+    }
+}
+
+impl TryFrom<crate::protos::tunnel::Record> for StreamRecord {
+    type Error = OtsError;
+
+    fn try_from(value: crate::protos::tunnel::Record) -> Result<Self, Self::Error> {
+        let crate::protos::tunnel::Record { action_type, record } = value;
+
+        let action_type = match ActionType::try_from(action_type) {
+            Ok(t) => t,
+            Err(_) => return Err(OtsError::ValidationFailed(format!("invalid tunnel record action type: {}", action_type))),
+        };
+
+        let row = Row::decode_plain_buffer(record, MASK_HEADER)?;
+
+        Ok(match action_type {
+            ActionType::PutRow => Self::Put(row),
+            ActionType::UpdateRow => Self::Update(row),
+            ActionType::DeleteRow => Self::Delete(row),
+        })
+    }
+}
+
+/// 根据心跳响应更新客户端持有的数据通道。
+///
+/// - 服务端分配的新数据通道（`OPEN`）开始读取；
+/// - 服务端要求关闭的数据通道（`CLOSING`）停止读取，并在下一次心跳中回复 `CLOSE`；
+/// - 服务端已经确认关闭或者结束的数据通道，以及响应中没有的数据通道，不再持有。
+///
+/// 客户端改变数据通道状态的时候版本号加一，服务端返回的版本号比客户端旧的时候，以客户端为准
+pub(crate) fn apply_heartbeat(channels: &mut BTreeMap<String, ChannelState>, server_channels: Vec<crate::protos::tunnel::Channel>) {
+    let mut updated = BTreeMap::new();
+
+    for ch in server_channels {
+        let status = ChannelStatus::try_from(ch.status).unwrap_or(ChannelStatus::Close);
+        let local = channels.remove(&ch.channel_id);
+
+        match (status, local) {
+            (_, Some(local)) if local.version > ch.version => {
+                updated.insert(ch.channel_id, local);
+            }
+
+            (ChannelStatus::Open, Some(local)) if local.status == ChannelStatus::Open => {
+                updated.insert(ch.channel_id, ChannelState { version: ch.version, ..local });
+            }
+
+            (ChannelStatus::Open, _) => {
+                updated.insert(ch.channel_id, ChannelState::new(ch.version, ChannelStatus::Open));
+            }
+
+            (ChannelStatus::Closing, _) => {
+                updated.insert(ch.channel_id, ChannelState::new(ch.version + 1, ChannelStatus::Close));
+            }
+
+            (ChannelStatus::Close | ChannelStatus::Terminated, _) => {}
+        }
+    }
+
+    *channels = updated;
+}
+
+/// 通道消费者。
+///
+/// 连接到通道之后，通过心跳获取分配给当前客户端的数据通道，依次从每个数据通道读取数据，
+/// 把解码之后的行交给回调函数处理，回调函数成功之后在服务端记录检查点，所以重新启动之后会从上一次处理完的位置继续读取。
+/// 需要区分写入、更新和删除的时候，使用 [`tunnel_record_worker`](`crate::OtsClient::tunnel_record_worker`)，
+/// 回调函数收到的记录和 Stream 记录一样，包含操作类型和行，全量数据通道中的记录都是写入行。
+///
+/// - 全量数据通道读取完毕之后会被标记为结束，服务端随后会分配增量数据通道；
+/// - 增量数据通道暂时没有新数据的时候，等待 `poll_interval_ms` 之后再读取；
+/// - 回调函数返回错误的时候，关闭连接并返回这个错误，没有处理成功的数据不会记录检查点；
+/// - 收到停机信号之后，关闭连接并返回 `Ok(())`。没有设置停机信号的时候，使用客户端配置中的停机信号。
+///
+/// # Examples
+///
+/// ```ignore
+/// let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+///
+/// let worker = client
+///     .tunnel_worker(&tunnel_id, |rows: Vec<Row>| {
+///         println!("{} rows received", rows.len());
+///         Ok(())
+///     })
+///     .shutdown_signal(shutdown_rx);
+///
+/// worker.run().await?;
+/// ```
+pub struct TunnelWorker<F> {
+    client: OtsClient,
+    tunnel_id: String,
+    callback: F,
+    heartbeat_interval_ms: u64,
+    heartbeat_timeout_seconds: i64,
+    poll_interval_ms: u64,
+    shutdown_signal: Option<Receiver<bool>>,
+}
+
+impl<F> TunnelWorker<F>
+where
+    F: FnMut(Vec<StreamRecord>) -> OtsResult<()>,
+{
+    pub(crate) fn new(client: OtsClient, tunnel_id: &str, callback: F) -> Self {
+        let shutdown_signal = client.options.shutdown_signal.clone();
+
+        Self {
+            client,
+            tunnel_id: tunnel_id.to_string(),
+            callback,
+            heartbeat_interval_ms: DEFAULT_HEARTBEAT_INTERVAL_MS,
+            heartbeat_timeout_seconds: DEFAULT_HEARTBEAT_TIMEOUT_SECONDS,
+            poll_interval_ms: DEFAULT_POLL_INTERVAL_MS,
+            shutdown_signal,
+        }
+    }
+
+    /// 设置心跳间隔，单位为毫秒。默认为 `30000`
+    pub fn heartbeat_interval_ms(mut self, ms: u64) -> Self {
+        self.heartbeat_interval_ms = ms;
+
+        self
+    }
+
+    /// 设置心跳超时时间，单位为秒。默认为 `300`
+    pub fn heartbeat_timeout_seconds(mut self, seconds: i64) -> Self {
+        self.heartbeat_timeout_seconds = seconds;
+
+        self
+    }
+
+    /// 设置所有数据通道都没有新数据时的等待时间，单位为毫秒。默认为 `1000`
+    pub fn poll_interval_ms(mut self, ms: u64) -> Self {
+        self.poll_interval_ms = ms;
+
+        self
+    }
+
+    /// 设置停机信号。信号的值变为 `true` 之后，处理完当前的数据，关闭连接并返回
+    pub fn shutdown_signal(mut self, rx: Receiver<bool>) -> Self {
+        self.shutdown_signal = Some(rx);
+
+        self
+    }
+
+    async fn call<M: Message + Default>(&self, operation: OtsOp, msg: impl Message) -> OtsResult<M> {
+        let req = OtsRequest {
+            operation,
+            body: msg.encode_to_vec(),
+            ..Default::default()
+        };
+
+        let response = self.client.send(req).await?;
+
+        Ok(M::decode(response.bytes().await?)?)
+    }
+
+    async fn connect(&self) -> OtsResult<String> {
+        let msg = crate::protos::tunnel::ConnectRequest {
+            tunnel_id: self.tunnel_id.clone(),
+            client_config: crate::protos::tunnel::ClientConfig {
+                timeout: Some(self.heartbeat_timeout_seconds),
+                client_tag: None,
+            },
+        };
+
+        let resp: crate::protos::tunnel::ConnectResponse = self.call(OtsOp::ConnectTunnel, msg).await?;
+
+        Ok(resp.client_id)
+    }
+
+    async fn heartbeat(&self, client_id: &str, channels: &mut BTreeMap<String, ChannelState>) -> OtsResult<()> {
+        let msg = crate::protos::tunnel::HeartbeatRequest {
+            tunnel_id: self.tunnel_id.clone(),
+            client_id: client_id.to_string(),
+            channels: channels
+                .iter()
+                .map(|(id, ch)| crate::protos::tunnel::Channel {
+                    channel_id: id.clone(),
+                    version: ch.version,
+                    status: ch.status as i32,
+                    detail: None,
+                })
+                .collect(),
+        };
+
+        let resp: crate::protos::tunnel::HeartbeatResponse = self.call(OtsOp::Heartbeat, msg).await?;
+        apply_heartbeat(channels, resp.channels);
+
+        Ok(())
+    }
+
+    async fn shutdown(&self, client_id: &str) -> OtsResult<()> {
+        let msg = crate::protos::tunnel::ShutdownRequest {
+            tunnel_id: self.tunnel_id.clone(),
+            client_id: client_id.to_string(),
+        };
+
+        let _: crate::protos::tunnel::ShutdownResponse = self.call(OtsOp::ShutdownTunnel, msg).await?;
+
+        Ok(())
+    }
+
+    async fn checkpoint(&self, client_id: &str, channel_id: &str, checkpoint: &str, sequence_number: i64) -> OtsResult<()> {
+        let msg = crate::protos::tunnel::CheckpointRequest {
+            tunnel_id: self.tunnel_id.clone(),
+            client_id: client_id.to_string(),
+            channel_id: channel_id.to_string(),
+            checkpoint: checkpoint.to_string(),
+            sequence_number,
+        };
+
+        let _: crate::protos::tunnel::CheckpointResponse = self.call(OtsOp::Checkpoint, msg).await?;
+
+        Ok(())
+    }
+
+    /// 从一个数据通道读取一批数据并处理，返回是否读取到了数据
+    async fn process_channel(&mut self, client_id: &str, channel_id: &str, state: &mut ChannelState) -> OtsResult<bool> {
+        let token = match &state.token {
+            Some(t) => t.clone(),
+            None => {
+                let msg = crate::protos::tunnel::GetCheckpointRequest {
+                    tunnel_id: self.tunnel_id.clone(),
+                    client_id: client_id.to_string(),
+                    channel_id: channel_id.to_string(),
+                };
+
+                let resp: crate::protos::tunnel::GetCheckpointResponse = self.call(OtsOp::GetCheckpoint, msg).await?;
+                state.sequence_number = resp.sequence_number;
+                state.token = Some(resp.checkpoint.clone());
+                resp.checkpoint
+            }
+        };
+
+        if token == FINISH_TAG {
+            state.status = ChannelStatus::Terminated;
+            state.version += 1;
+            return Ok(false);
+        }
+
+        let msg = crate::protos::tunnel::ReadRecordsRequest {
+            tunnel_id: self.tunnel_id.clone(),
+            client_id: client_id.to_string(),
+            channel_id: channel_id.to_string(),
+            token: token.clone(),
+        };
+
+        let resp: crate::protos::tunnel::ReadRecordsResponse = self.call(OtsOp::ReadRecords, msg).await?;
+        let has_records = !resp.records.is_empty();
+
+        if has_records {
+            let records = resp.records.into_iter().map(StreamRecord::try_from).collect::<OtsResult<Vec<_>>>()?;
+
+            (self.callback)(records)?;
+        }
+
+        // token 没有变化说明增量数据通道暂时没有新数据，不需要记录检查点
+        if resp.next_token != token {
+            state.sequence_number += 1;
+            self.checkpoint(client_id, channel_id, &resp.next_token, state.sequence_number).await?;
+            state.token = Some(resp.next_token);
+        }
+
+        Ok(has_records)
+    }
+
+    /// 读取数据直到收到停机信号或者出错
+    async fn work(&mut self, client_id: &str) -> OtsResult<()> {
+        let mut channels = BTreeMap::new();
+        let mut last_heartbeat: Option<Instant> = None;
+
+        loop {
+            if self.shutdown_signal.as_ref().is_some_and(|rx| *rx.borrow()) {
+                return Ok(());
+            }
+
+            if last_heartbeat.map_or(true, |t| t.elapsed().as_millis() as u64 >= self.heartbeat_interval_ms) {
+                self.heartbeat(client_id, &mut channels).await?;
+                last_heartbeat = Some(Instant::now());
+            }
+
+            let mut busy = false;
+
+            for (channel_id, state) in channels.iter_mut() {
+                if state.status == ChannelStatus::Open {
+                    busy |= self.process_channel(client_id, channel_id, state).await?;
+                }
+            }
+
+            if !busy && sleep_or_shutdown(self.shutdown_signal.as_ref(), self.poll_interval_ms).await {
+                return Ok(());
+            }
+        }
+    }
+
+    /// 开始消费通道中的数据
+    pub async fn run(mut self) -> OtsResult<()> {
+        if self.tunnel_id.is_empty() {
+            return Err(OtsError::ValidationFailed("invalid tunnel id: can not be empty".to_string()));
+        }
+
+        let client_id = self.connect().await?;
+        let result = self.work(&client_id).await;
+
+        if let Err(e) = self.shutdown(&client_id).await {
+            log::warn!("shutdown tunnel client {} failed: {}", client_id, e);
+        }
+
+        result
+    }
+}
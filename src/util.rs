@@ -1,9 +1,10 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
 use sha1::Sha1;
 use sha2::{Digest, Sha256};
+use tokio::task::JoinSet;
 
 /// Get UTC date time string for aliyun ots API.
 /// e.g. 2023-12-03T12:12:12.123Z
@@ -16,6 +17,41 @@ pub(crate) fn get_iso8601_date_time_string() -> String {
     now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
 }
 
+/// 等待一段时间。如果在等待期间收到停机信号，立即返回 `true`
+pub(crate) async fn sleep_or_shutdown(shutdown_signal: Option<&tokio::sync::watch::Receiver<bool>>, delay_ms: u64) -> bool {
+    let mut sleep = std::pin::pin!(tokio::time::sleep(Duration::from_millis(delay_ms)));
+
+    let mut rx = match shutdown_signal {
+        Some(rx) => rx.clone(),
+        None => {
+            sleep.await;
+            return false;
+        }
+    };
+
+    let shutdown = tokio::select! {
+        _ = &mut sleep => return false,
+        res = rx.wait_for(|shutdown| *shutdown) => res.is_ok(),
+    };
+
+    // 发送端已经被丢弃，不会再收到停机信号，继续等待剩余的时间
+    if !shutdown {
+        sleep.await;
+    }
+
+    shutdown
+}
+
+/// 等待 `JoinSet` 中的任务完成一个，没有任务时返回 `None`。
+///
+/// 任务不会被取消，出错只可能是任务 panic 了，此时在当前任务中继续 panic
+pub(crate) async fn join_next_or_resume<T: 'static>(tasks: &mut JoinSet<T>) -> Option<T> {
+    match tasks.join_next().await? {
+        Ok(v) => Some(v),
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
 #[allow(dead_code)]
 pub(crate) fn current_time_ms() -> u128 {
     let now = SystemTime::now();
@@ -32,7 +68,6 @@ pub(crate) fn get_http_date() -> String {
     now.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
 }
 
-#[allow(dead_code)]
 /// Hmac-SHA256 digest
 pub(crate) fn hmac_sha256(key_data: &[u8], msg_data: &[u8]) -> Vec<u8> {
     type HmacSha256 = Hmac<Sha256>;
@@ -61,7 +96,6 @@ pub(crate) fn hmac_sha1(key_data: &[u8], msg_data: &[u8]) -> Vec<u8> {
     hasher.finalize().into_bytes().to_vec()
 }
 
-#[allow(dead_code)]
 pub(crate) fn debug_bytes(bytes: &[u8]) {
     // Print bytes in a hexadecimal format, 16 bytes per line, comma-separated
     log::debug!("Bytes dump:");